@@ -1,3 +1,5 @@
+#![allow(clippy::module_inception)]
+
 use criterion::criterion_main;
 
 mod aldous_broder;
@@ -61,6 +61,8 @@
 //! maze.save("output/maze_game_map.txt", GameMap::new().span(3)).unwrap();
 //! // Save as a PNG image (adjusting wall and passage sizes)
 //! maze.save("output/maze.png", Image::new().wall(10).passage(30)).unwrap();
+//! // Save as an SVG image (adjusting cell size and wall stroke width)
+//! maze.save("output/maze.svg", Svg::new().cell(20).wall(2)).unwrap();
 //! ```
 //!
 //! ## Format for Further Processing or Logging
@@ -79,6 +81,222 @@
 //!
 //! Read more about [maze formatters](maze::formatters)
 //!
+//! ## Solve Mazes
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! let maze = OrthogonalMazeBuilder::new().build();
+//!
+//! // Find the shortest path from the top left to the bottom right corner
+//! let path = maze.solve(BreadthFirstSearch, (0, 0), (9, 9)).unwrap();
+//! ```
+//!
+//! Read more about [maze solvers](maze::solvers)
+//!
+//! ## Parse Mazes
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Load a hand-edited maze back from the text written by a formatter
+//! let text = std::fs::read_to_string("output/maze.txt").unwrap();
+//! let maze = OrthogonalMaze::from_ascii_narrow(&text).unwrap();
+//! ```
+//!
+//! ## Shape Mazes with Masks
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Cut the cells marked with `X` out of a maze, or load a mask from a black and white image
+//! let mask = Mask::from_ascii("X..X\n....\nX..X\n").unwrap();
+//! let maze = OrthogonalMazeBuilder::new().mask(mask).build();
+//!
+//! let mask = Mask::from_image("mask.png").unwrap();
+//! let maze = OrthogonalMazeBuilder::new().mask(mask).build();
+//! ```
+//!
+//! Read more about [masks](maze::Mask)
+//!
+//! ## Weave Mazes
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Let passages tunnel under perpendicular corridors and draw them with inset walls
+//! let maze = OrthogonalMazeBuilder::new()
+//!     .algorithm(Box::new(Kruskal))
+//!     .weave(true)
+//!     .build();
+//!
+//! maze.save("weave.png", Image::new().passage(12).inset(2)).unwrap();
+//! ```
+//!
+//! Read more about [weave mazes](maze::OrthogonalMazeBuilder::weave)
+//!
+//! ## Wrap-around Mazes
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Let passages lead out of a maze on one side and back into it on the opposite one
+//! let maze = OrthogonalMazeBuilder::new()
+//!     .topology(Topology::Torus)
+//!     .algorithm(Box::new(Prim::new()))
+//!     .build();
+//!
+//! maze.save("torus.png", Image::new()).unwrap();
+//! ```
+//!
+//! Read more about [wrap-around mazes](maze::Topology)
+//!
+//! ## Polar Mazes
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Generate a circular maze of 12 rings and render it with arcs
+//! let maze = PolarMazeBuilder::new()
+//!     .rings(12)
+//!     .algorithm(Box::new(Kruskal))
+//!     .build();
+//!
+//! maze.save("polar.png", Image::new()).unwrap();
+//! ```
+//!
+//! Read more about [polar mazes](maze::PolarMazeBuilder) and the algorithms that generate them,
+//! see [`GraphAlgorithm`](maze::GraphAlgorithm)
+//!
+//! ## Hexagonal Mazes
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Generate a maze of hexagonal cells, print it and save it as an image
+//! let maze = HexMazeBuilder::new()
+//!     .width(12)
+//!     .height(8)
+//!     .algorithm(Box::new(Prim::new()))
+//!     .build();
+//!
+//! println!("{}", &maze);
+//! maze.save("hex.png", Image::new()).unwrap();
+//! ```
+//!
+//! Read more about [hexagonal mazes](maze::HexMazeBuilder)
+//!
+//! ## Triangular and Upsilon Mazes
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Generate a maze of triangles and a maze of octagons and squares and save them as images
+//! let maze = DeltaMazeBuilder::new().width(16).height(8).build();
+//! maze.save("delta.png", Image::new()).unwrap();
+//!
+//! let maze = UpsilonMazeBuilder::new()
+//!     .width(9)
+//!     .height(9)
+//!     .algorithm(Box::new(Kruskal))
+//!     .build();
+//! maze.save("upsilon.png", Image::new()).unwrap();
+//! ```
+//!
+//! Read more about [triangular](maze::DeltaMazeBuilder) and [upsilon](maze::UpsilonMazeBuilder)
+//! mazes
+//!
+//! ## Layered Mazes
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Generate a maze of three levels connected by stairs, print it and a map per floor
+//! let maze = LayeredMazeBuilder::new()
+//!     .width(10)
+//!     .height(10)
+//!     .levels(3)
+//!     .algorithm(Box::new(Kruskal))
+//!     .build();
+//!
+//! println!("{}", &maze);
+//! for floor in GameMap::new().format_levels(maze.grid()) {
+//!     println!("{}", floor.into_inner());
+//! }
+//! ```
+//!
+//! Read more about [layered mazes](maze::LayeredMazeBuilder)
+//!
+//! ## Mutate Mazes During Play
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Shift the origin of a perfect maze a few times on every turn, so the maze stays perfect
+//! let mut maze = OrthogonalMazeBuilder::new().build();
+//! let mut shifter = OriginShifter::new(&maze, Some(7)).unwrap();
+//!
+//! shifter.shift(&mut maze, 3).unwrap();
+//! ```
+//!
+//! Read more about [mutating mazes](maze::OriginShifter)
+//!
+//! ## Rooms and Chambers
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Leave half of the chambers up to 5x4 cells open as rooms
+//! let maze = OrthogonalMazeBuilder::new()
+//!     .algorithm(Box::new(ChamberDivision::new().room_size(5, 4).stop_probability(0.5)))
+//!     .build();
+//! ```
+//!
+//! Read more about [chamber division](maze::ChamberDivision)
+//!
+//! ## Dungeon Rooms
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Reserve the rooms first, fill the rest with a maze and connect the rooms through their doors
+//! let maze = OrthogonalMazeBuilder::new()
+//!     .width(40)
+//!     .height(25)
+//!     .rooms(Rooms::new(6).width(4..=8).height(3..=6).doors(1..=3))
+//!     .braid(0.5)
+//!     .build();
+//!
+//! for room in maze.rooms() {
+//!     println!("{:?} with doors {:?}", room.position, room.doors);
+//! }
+//! ```
+//!
+//! Read more about [rooms](maze::Rooms)
+//!
+//! ## Record Generation Events
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Record each step of an algorithm to replay or animate the generation later
+//! let maze = OrthogonalMazeBuilder::new()
+//!     .algorithm(Box::new(RecursiveBacktracking))
+//!     .record_events(true)
+//!     .build();
+//!
+//! let carved = maze
+//!     .events()
+//!     .iter()
+//!     .filter(|event| matches!(event, Event::Carve { .. }))
+//!     .count();
+//! println!("{} passages carved in {} steps", carved, maze.events().len());
+//! ```
+//!
+//! Read more about [events](maze::Event)
+//!
+//! ## Animate Generation
+//! ```rust,no_run
+//! use knossos::maze::*;
+//!
+//! // Save the recorded generation process as an animated GIF with a frame every 5 steps
+//! let maze = OrthogonalMazeBuilder::new()
+//!     .algorithm(Box::new(Prim::new()))
+//!     .record_events(true)
+//!     .build();
+//!
+//! maze.save("output/prim.gif", Animation::new().step(5)).unwrap();
+//! ```
+//!
+//! Read more about [animations](maze::Animation)
+//!
 //! ## Seeding for Deterministic Mazes
 //!
 //! By default, each generated maze is randomized, producing a different layout every time. However,
@@ -94,23 +312,39 @@
 //!
 //! Passing `None` as the seed (or omitting the `.seed()` method) will result in a random maze each time.
 //!
+//! ## Serialization
+//!
+//! With the optional `serde` feature enabled, [OrthogonalMaze](maze::OrthogonalMaze) implements
+//! `Serialize` and `Deserialize`, so a generated maze can be stored in any format supported by
+//! serde (JSON, RON, bincode, etc.) and loaded back exactly. The loaded data is validated, and a
+//! maze with inconsistent passages is rejected.
+//!
+//! ```no_test
+//! [dependencies]
+//! knossos = { version = "1.2.0", features = ["serde"] }
+//! ```
+//!
 //! # Algorithms
 //!
-//! You can find 10 different algorithms supported by this crate. Each of them has its own pros and
+//! You can find 14 different algorithms supported by this crate. Each of them has its own pros and
 //! cons: some of them are impressively efficient, some of them are slower but generate splendid
 //! mazes that look hard to puzzle out, and others are extremely flexible and customizable. Do give
 //! each of them a shot and find the best one that suits you:
 //!
 //! - [`AldousBroder`](maze::AldousBroder)
 //! - [`BinaryTree`](maze::BinaryTree)
+//! - [`ChamberDivision`](maze::ChamberDivision)
 //! - [`Eller`](maze::Eller)
 //! - [`GrowingTree`](maze::GrowingTree)
+//! - [`Houston`](maze::Houston)
 //! - [`HuntAndKill`](maze::HuntAndKill)
 //! - [`Kruskal`](maze::Kruskal)
+//! - [`OriginShift`](maze::OriginShift)
 //! - [`Prim`](maze::Prim)
 //! - [`RecursiveBacktracking`](maze::RecursiveBacktracking)
 //! - [`RecursiveDivision`](maze::RecursiveDivision)
 //! - [`Sidewinder`](maze::Sidewinder)
+//! - [`Wilson`](maze::Wilson)
 
 mod utils;
 
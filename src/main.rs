@@ -1,19 +1,29 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use knossos::Color;
-use knossos::maze::{self, formatters};
+use knossos::maze::{
+    self,
+    formatters::{self, Formatter},
+};
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::{process, thread, time::Duration};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum Algorithm {
     AldousBroder,
     BinaryTree,
+    ChamberDivision,
     Eller,
     GrowingTree,
+    Houston,
     HuntAndKill,
     Kruskal,
+    OriginShift,
     Prim,
     RecursiveBacktracking,
     RecursiveDivision,
     Sidewinder,
+    Wilson,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -36,44 +46,104 @@ enum Commands {
         #[command(subcommand)]
         output: OutputCommands,
 
-        /// Maze generation algorithm
-        #[arg(short = 'A', long, value_enum, default_value_t = Algorithm::RecursiveBacktracking)]
-        algorithm: Algorithm,
-
-        /// Grid height in a number of cells
-        #[arg(short = 'H', long, default_value_t = 10)]
-        height: usize,
-
-        #[arg(short = 'W', long, default_value_t = 10)]
-        /// Grid width in a number of cells
-        width: usize,
-
-        /// Seed value for deterministic generation (must be a valid u64)
-        #[arg(long)]
-        seed: Option<u64>,
+        #[command(flatten)]
+        maze: MazeArgs,
+    },
+    /// Replays generating a maze in the terminal
+    Animate {
+        #[command(flatten)]
+        maze: MazeArgs,
+
+        /// Delay between the frames in milliseconds
+        #[arg(long, default_value_t = 30)]
+        delay: u64,
+
+        /// Number of generation steps to replay between the frames
+        #[arg(long, default_value_t = 1)]
+        step: usize,
+    },
+}
 
-        /// Bias to use for the "Binary Tree" algorithm
-        #[arg(
-            long,
-            default_value_t = maze::Bias::NorthEast,
-            require_equals = true,
-            num_args = 0..=1,
-            default_missing_value = "north-east",
-            value_enum,
-        )]
-        bias: maze::Bias,
+#[derive(Debug, Args)]
+struct MazeArgs {
+    /// Maze generation algorithm
+    #[arg(short = 'A', long, value_enum, default_value_t = Algorithm::RecursiveBacktracking)]
+    algorithm: Algorithm,
+
+    /// Grid height in a number of cells
+    #[arg(short = 'H', long, default_value_t = 10)]
+    height: usize,
+
+    #[arg(short = 'W', long, default_value_t = 10)]
+    /// Grid width in a number of cells
+    width: usize,
+
+    /// Seed value for deterministic generation (must be a valid u64)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Bias to use for the "Binary Tree" algorithm
+    #[arg(
+        long,
+        default_value_t = maze::Bias::NorthEast,
+        require_equals = true,
+        num_args = 0..=1,
+        default_missing_value = "north-east",
+        value_enum,
+    )]
+    bias: maze::Bias,
+
+    /// Growing method to use for the "Growing Tree" algorithm
+    #[arg(
+        long,
+        default_value_t = maze::Method::Newest,
+        require_equals = true,
+        num_args = 0..=1,
+        default_missing_value = "newest",
+        value_enum,
+    )]
+    growing_method: maze::Method,
+
+    /// Largest width and height of a room to leave open for the "Chamber Division" algorithm
+    #[arg(long, default_value_t = 1)]
+    room_size: usize,
+
+    /// Probability to leave open a chamber that fits into a room for the "Chamber Division"
+    /// algorithm
+    #[arg(long, default_value_t = 1.0)]
+    stop_probability: f64,
+}
 
-        /// Growing method to use for the "Growing Tree" algorithm
-        #[arg(
-            long,
-            default_value_t = maze::Method::Newest,
-            require_equals = true,
-            num_args = 0..=1,
-            default_missing_value = "newest",
-            value_enum,
-        )]
-        growing_method: maze::Method,
-    },
+impl MazeArgs {
+    /// Returns a builder of a maze with a given algorithm, size and seed
+    fn builder(&self) -> maze::OrthogonalMazeBuilder {
+        let algorithm: Box<dyn maze::Algorithm> = match self.algorithm {
+            Algorithm::AldousBroder => Box::new(maze::AldousBroder),
+            Algorithm::BinaryTree => Box::new(maze::BinaryTree::new(self.bias)),
+            Algorithm::ChamberDivision => Box::new(
+                maze::ChamberDivision::new()
+                    .room_size(self.room_size, self.room_size)
+                    .stop_probability(self.stop_probability),
+            ),
+            Algorithm::Eller => Box::new(maze::Eller),
+            Algorithm::GrowingTree => Box::new(maze::GrowingTree::new(self.growing_method)),
+            Algorithm::Houston => Box::new(maze::Houston::new()),
+            Algorithm::HuntAndKill => Box::new(maze::HuntAndKill::new()),
+            Algorithm::Kruskal => Box::new(maze::Kruskal),
+            Algorithm::OriginShift => Box::new(maze::OriginShift::new()),
+            Algorithm::Prim => Box::new(maze::Prim::new()),
+            Algorithm::RecursiveBacktracking => Box::new(maze::RecursiveBacktracking),
+            Algorithm::RecursiveDivision => Box::new(maze::RecursiveDivision),
+            Algorithm::Sidewinder => Box::new(maze::Sidewinder),
+            Algorithm::Wilson => Box::new(maze::Wilson),
+        };
+
+        maze::OrthogonalMazeBuilder::new()
+            .height(self.height)
+            .width(self.width)
+            .seed(self.seed)
+            .algorithm(algorithm)
+    }
 }
 
 #[derive(Debug, Subcommand)]
@@ -151,34 +221,8 @@ fn main() -> Result<(), maze::MazeSaveError> {
     let args = Cli::parse();
 
     match args.command {
-        Commands::Generate {
-            output,
-            algorithm,
-            height,
-            width,
-            seed,
-            bias,
-            growing_method,
-        } => {
-            let algorithm: Box<dyn maze::Algorithm> = match algorithm {
-                Algorithm::AldousBroder => Box::new(maze::AldousBroder),
-                Algorithm::BinaryTree => Box::new(maze::BinaryTree::new(bias)),
-                Algorithm::Eller => Box::new(maze::Eller),
-                Algorithm::GrowingTree => Box::new(maze::GrowingTree::new(growing_method)),
-                Algorithm::HuntAndKill => Box::new(maze::HuntAndKill::new()),
-                Algorithm::Kruskal => Box::new(maze::Kruskal),
-                Algorithm::Prim => Box::new(maze::Prim::new()),
-                Algorithm::RecursiveBacktracking => Box::new(maze::RecursiveBacktracking),
-                Algorithm::RecursiveDivision => Box::new(maze::RecursiveDivision),
-                Algorithm::Sidewinder => Box::new(maze::Sidewinder),
-            };
-
-            let maze = maze::OrthogonalMazeBuilder::new()
-                .height(height)
-                .width(width)
-                .seed(seed)
-                .algorithm(algorithm)
-                .build();
+        Commands::Generate { output, maze } => {
+            let maze = maze.builder().build();
 
             let result;
 
@@ -246,9 +290,134 @@ fn main() -> Result<(), maze::MazeSaveError> {
                 Err(err) => Err(err),
             }
         }
+        Commands::Animate { maze, delay, step } => {
+            let maze = maze.builder().record_events(true).build();
+            animate(&maze, Duration::from_millis(delay), step).map_err(|err| maze::MazeSaveError {
+                reason: err.to_string(),
+            })
+        }
     }
 }
 
+/// The ANSI escape sequence to clear the screen
+const CLEAR: &str = "\x1b[2J";
+
+/// The ANSI escape sequence to hide the cursor while the frames are drawn
+const HIDE_CURSOR: &str = "\x1b[?25l";
+
+/// The ANSI escape sequence to show the cursor again
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+/// The ANSI escape sequence to move the cursor to the top left corner of the screen, so the next
+/// frame is drawn over the previous one
+const HOME: &str = "\x1b[H";
+
+/// The ANSI escape sequence to highlight the current cell of an algorithm with a red background
+const CURRENT: &str = "\x1b[41m";
+
+/// The ANSI escape sequence to highlight the cells an algorithm keeps track of with a blue
+/// background
+const FRONTIER: &str = "\x1b[44m";
+
+/// The ANSI escape sequence to reset a background color
+const RESET: &str = "\x1b[0m";
+
+/// Replays the events recorded while generating a maze in the terminal every given number of steps
+/// and ends with the complete maze
+fn animate(maze: &maze::OrthogonalMaze, delay: Duration, step: usize) -> io::Result<()> {
+    let step = step.max(1);
+    let mut stdout = io::stdout();
+    let mut replay = maze.replay();
+
+    write!(stdout, "{}", CLEAR)?;
+    let cursor = HiddenCursor::new()?;
+    while replay.by_ref().take(step).count() == step {
+        let frame = frame(replay.grid(), replay.current(), replay.frontier());
+        write!(stdout, "{}{}", HOME, frame)?;
+        stdout.flush()?;
+        thread::sleep(delay);
+    }
+
+    write!(stdout, "{}{}", HOME, maze)?;
+    drop(cursor);
+    Ok(())
+}
+
+/// Hides the cursor while an animation is drawn and shows it again once the guard is dropped,
+/// whether the animation ends, fails to write a frame or is interrupted with Ctrl-C
+///
+/// The guard can only be created once per process, since the Ctrl-C handler it sets up cannot be
+/// set again, so a second call to [HiddenCursor::new] returns an error.
+struct HiddenCursor;
+
+impl HiddenCursor {
+    fn new() -> io::Result<HiddenCursor> {
+        // The process exits right away on Ctrl-C, with no guards dropped, so the handler shows the
+        // cursor on its own
+        ctrlc::set_handler(|| {
+            show_cursor();
+            process::exit(130);
+        })
+        .map_err(io::Error::other)?;
+
+        let mut stdout = io::stdout();
+        write!(stdout, "{}", HIDE_CURSOR)?;
+        stdout.flush()?;
+        Ok(HiddenCursor)
+    }
+}
+
+impl Drop for HiddenCursor {
+    fn drop(&mut self) {
+        show_cursor();
+    }
+}
+
+/// Shows the cursor, ignoring the errors since there is no other way left to restore it
+fn show_cursor() {
+    let mut stdout = io::stdout();
+    _ = write!(stdout, "{}", SHOW_CURSOR).and_then(|_| stdout.flush());
+}
+
+/// Returns a grid in the narrow ASCII format with the current cell and the cells an algorithm keeps
+/// track of highlighted
+fn frame(
+    grid: &maze::Grid,
+    current: Option<maze::Coords>,
+    frontier: &HashSet<maze::Coords>,
+) -> String {
+    let ascii = formatters::AsciiNarrow.format(grid).into_inner();
+
+    // Every cell is drawn as its south wall followed by its east wall, so the south wall of a
+    // cell is the one to highlight, right after the west border of a row
+    let mut lines: Vec<String> = ascii.lines().map(String::from).collect();
+    for (y, line) in lines.iter_mut().enumerate().skip(1) {
+        *line = line
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let coords = (i / 2, y - 1);
+                let color = if i.is_multiple_of(2) {
+                    None
+                } else if current == Some(coords) {
+                    Some(CURRENT)
+                } else if frontier.contains(&coords) {
+                    Some(FRONTIER)
+                } else {
+                    None
+                };
+
+                match color {
+                    Some(color) => format!("{}{}{}", color, c, RESET),
+                    None => c.to_string(),
+                }
+            })
+            .collect();
+    }
+
+    lines.join("\n") + "\n"
+}
+
 fn hex_to_rgb(s: &str) -> Result<Color, ParseHexError> {
     let s = if let Some(hex) = s.strip_prefix('#') {
         hex
@@ -1,4 +1,6 @@
-use super::Algorithm;
+use super::{enabled_start, region_size, Algorithm, GraphAlgorithm};
+use crate::maze::events::Event;
+use crate::maze::graph::Graph;
 use crate::utils::types::Coords;
 use crate::maze::grid::{Grid, cell::Cell};
 
@@ -8,7 +10,9 @@ use rand::prelude::*;
 ///
 /// This is an easy one to implement. And yet, it is also one of the least intelligent algorithms,
 /// since the latest steps may take so much time that you may not want to wait until it's finished.
-/// It is not even guaranteed to finish if you get really unlucky with the random
+/// It is not even guaranteed to finish if you get really unlucky with the random. For large mazes,
+/// consider the [Wilson](crate::maze::Wilson) or [Houston](crate::maze::Houston) algorithms, which
+/// generate the mazes of the same kind much faster
 pub struct AldousBroder;
 
 impl AldousBroder {}
@@ -27,33 +31,88 @@ impl AldousBroder {}
 impl Algorithm for AldousBroder {
     fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
         let start_coords = get_start_coords(grid, rng);
-        let mut x = start_coords.0;
-        let mut y = start_coords.1;
-
-        let mut remaining = grid.width() * grid.height() - 1; // the number of remaining unvisited cells
-
-        while remaining > 0 {
-            let mut directions = [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST];
-            directions.shuffle(rng);
-
-            for dir in directions {
-                let next_cell = grid.get_next_cell_coords((x, y), dir);
-                if next_cell.is_err() {
-                    continue;
-                }
-
-                let (nx, ny) = next_cell.unwrap();
-                if !grid.is_cell_visited((nx, ny)) {
-                    grid.carve_passage((x, y), dir).unwrap();
-                    remaining -= 1;
-                }
-
-                x = nx;
-                y = ny;
-                break;
+        let Some((x, y)) = enabled_start(grid, start_coords, rng) else {
+            return;
+        };
+
+        // The number of remaining unvisited cells, which excludes the ones a mask cuts off
+        let remaining = region_size(grid, (x, y)) - 1;
+        carve_walk(grid, (x, y), remaining, rng);
+    }
+}
+
+/// An implementation of Aldous-Broder's algorithm for generating mazes of any shape
+impl GraphAlgorithm for AldousBroder {
+    fn carve(&mut self, graph: &mut Graph, rng: &mut StdRng) {
+        if graph.is_empty() {
+            return;
+        }
+
+        let cell = rng.random_range(0..graph.len());
+        link_walk(graph, cell, graph.len() - 1, rng);
+    }
+}
+
+/// Performs a random walk from a cell at the given coordinates, carving a passage into every
+/// unvisited cell it steps into, until a given number of cells is visited, and returns the
+/// coordinates of the cell it stops at
+pub(super) fn carve_walk(
+    grid: &mut Grid,
+    (mut x, mut y): Coords,
+    mut remaining: usize,
+    rng: &mut StdRng,
+) -> Coords {
+    grid.emit(Event::Visit((x, y)));
+
+    while remaining > 0 {
+        let mut directions = [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST];
+        directions.shuffle(rng);
+
+        for dir in directions {
+            let next_cell = grid.get_next_cell_coords((x, y), dir);
+            if next_cell.is_err() {
+                continue;
             }
+
+            let (nx, ny) = next_cell.unwrap();
+            if !grid.is_cell_visited((nx, ny)) {
+                grid.carve_passage((x, y), dir).unwrap();
+                remaining -= 1;
+            }
+
+            x = nx;
+            y = ny;
+            grid.emit(Event::Visit((x, y)));
+            break;
         }
     }
+
+    (x, y)
+}
+
+/// Performs a random walk from a cell of a graph with a given index, linking every unvisited cell
+/// it steps into, until a given number of cells is visited, and returns the index of the cell it
+/// stops at
+pub(super) fn link_walk(
+    graph: &mut Graph,
+    mut cell: usize,
+    mut remaining: usize,
+    rng: &mut StdRng,
+) -> usize {
+    while remaining > 0 {
+        let Some(&next) = graph.neighbours(cell).choose(rng) else {
+            break;
+        };
+
+        if !graph.is_visited(next) {
+            graph.link(cell, next).unwrap();
+            remaining -= 1;
+        }
+
+        cell = next;
+    }
+
+    cell
 }
 
 fn get_start_coords<R: Rng>(grid: &Grid, rng: &mut R) -> Coords {
@@ -1,16 +1,17 @@
 use super::Algorithm;
+use crate::maze::events::Event;
 use crate::maze::grid::cell::Cell;
 use crate::maze::grid::Grid;
 use crate::utils::types::Coords;
 
-use clap::ValueEnum;
 use rand::prelude::*;
 
 /// An enumeration over supported biases for the "Binary Tree" algorithm
 ///
 /// Each bias represents the two of four sides of the maze that will be spanned
 /// by a single corridor.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
 pub enum Bias {
     /// Produces two long corridors on the Northern and Western sides of the maze
     NorthWest,
@@ -87,6 +88,8 @@ impl BinaryTree {
             }
         }
 
+        // Passages never lead into the cells disabled by a mask
+        dirs.retain(|dir| grid.get_next_cell_coords(coords, *dir).is_ok());
         dirs
     }
 }
@@ -99,6 +102,10 @@ impl Algorithm for BinaryTree {
     fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
         for y in 0..grid.height() {
             for x in 0..grid.width() {
+                if grid.is_cell_enabled((x, y)) {
+                    grid.emit(Event::Visit((x, y)));
+                }
+
                 let dirs = self.populate_dirs((x, y), grid);
                 if let Some(dir) = dirs.choose(rng) {
                     grid.carve_passage((x, y), *dir).ok();
@@ -0,0 +1,201 @@
+use super::recursive_division::{choose_orientation, Orientation};
+use super::Algorithm;
+use crate::maze::events::Event;
+use crate::maze::grid::{cell::Cell, Grid};
+use crate::utils::types::Coords;
+
+use rand::prelude::*;
+
+/// The "Recursive Division" algorithm implemented as a wall adder, which can leave open rooms in
+/// a maze
+///
+/// Unlike the [RecursiveDivision](crate::maze::RecursiveDivision) algorithm, which carves the
+/// passages, this one starts with a grid with no walls at all and keeps building the walls with a
+/// single gap in each of them, dividing the grid into smaller and smaller chambers. Since it's free
+/// to stop dividing at any point, the chambers left undivided become open rooms connected by the
+/// maze corridors.
+///
+/// By default, it divides every chamber down to single cells, so a maze is perfect. A maze with
+/// rooms has loops inside them, so it's only valid.
+///
+/// # Example
+/// ```rust
+/// use knossos::maze::*;
+///
+/// let maze = OrthogonalMazeBuilder::new()
+///     .algorithm(Box::new(ChamberDivision::new().room_size(4, 4).stop_probability(0.5)))
+///     .build();
+///
+/// assert!(maze.is_valid());
+/// ```
+pub struct ChamberDivision {
+    room_width: usize,
+    room_height: usize,
+    stop_probability: f64,
+}
+
+impl ChamberDivision {
+    /// Create a new instance of the algorithm, which leaves no rooms
+    pub const fn new() -> ChamberDivision {
+        ChamberDivision {
+            room_width: 1,
+            room_height: 1,
+            stop_probability: 1.0,
+        }
+    }
+
+    /// Sets the largest width and height of a chamber to leave open as a room and returns itself
+    ///
+    /// Any chamber that fits into this size stops being divided, unless a
+    /// [stop probability](ChamberDivision::stop_probability) says otherwise.
+    pub const fn room_size(mut self, width: usize, height: usize) -> Self {
+        self.room_width = width;
+        self.room_height = height;
+        self
+    }
+
+    /// Sets a probability to leave a chamber that fits into a room size open, from `0.0` to `1.0`,
+    /// and returns itself
+    ///
+    /// A probability of `1.0` turns every such chamber into a room, while the lower ones keep
+    /// dividing some of them, so the rooms vary in number and size.
+    pub const fn stop_probability(mut self, probability: f64) -> Self {
+        self.stop_probability = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns `true` if a given chamber is to be left open as a room
+    fn is_room<R: Rng>(&self, chamber: &Chamber, rng: &mut R) -> bool {
+        chamber.width <= self.room_width
+            && chamber.height <= self.room_height
+            && rng.random_bool(self.stop_probability)
+    }
+}
+
+impl Default for ChamberDivision {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of the "Recursive Division" algorithm as a wall adder
+///
+/// It works like this:
+///
+/// 1. Begins with a grid with every wall between the cells removed as the first chamber.
+///
+/// 2. Stops dividing a chamber if it's a single cell wide or tall, or it's left open as a room.
+///
+/// 3. Otherwise, bisects the chamber either horizontally or vertically with a wall and leaves a
+///    gap in it at a random cell.
+///
+/// 4. Repeats steps 2 and 3 with the chambers on either side of the wall.
+impl Algorithm for ChamberDivision {
+    fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
+        open(grid);
+
+        let mut chambers = vec![Chamber {
+            x: 0,
+            y: 0,
+            width: grid.width(),
+            height: grid.height(),
+        }];
+
+        while let Some(chamber) = chambers.pop() {
+            if chamber.width < 2 || chamber.height < 2 || self.is_room(&chamber, rng) {
+                continue;
+            }
+
+            let (first, second) = divide(grid, chamber, rng);
+            chambers.push(second);
+            chambers.push(first);
+        }
+    }
+}
+
+/// A rectangular part of a grid with no walls inside
+#[derive(Clone, Copy)]
+struct Chamber {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+/// Carves the passages between all the adjacent enabled cells within the borders of a grid
+fn open(grid: &mut Grid) {
+    let cells: Vec<Coords> = grid.enabled_cells().collect();
+    for (x, y) in cells {
+        if x + 1 < grid.width() {
+            grid.carve_passage((x, y), Cell::EAST).ok();
+        }
+        if y + 1 < grid.height() {
+            grid.carve_passage((x, y), Cell::SOUTH).ok();
+        }
+    }
+}
+
+/// Bisects a chamber with a wall with a single gap in it and returns the chambers on either side
+/// of the wall
+fn divide<R: Rng>(grid: &mut Grid, chamber: Chamber, rng: &mut R) -> (Chamber, Chamber) {
+    let Chamber {
+        x,
+        y,
+        width,
+        height,
+    } = chamber;
+
+    let (dir, wall, first, second): (Cell, Vec<Coords>, Chamber, Chamber) =
+        match choose_orientation(width, height, rng) {
+            Orientation::Horizontal => {
+                let wy = rng.random_range(y..y + height - 1);
+                let top = wy - y + 1;
+                (
+                    Cell::SOUTH,
+                    (x..x + width).map(|cx| (cx, wy)).collect(),
+                    Chamber {
+                        height: top,
+                        ..chamber
+                    },
+                    Chamber {
+                        y: wy + 1,
+                        height: height - top,
+                        ..chamber
+                    },
+                )
+            }
+            Orientation::Vertical => {
+                let wx = rng.random_range(x..x + width - 1);
+                let left = wx - x + 1;
+                (
+                    Cell::EAST,
+                    (y..y + height).map(|cy| (wx, cy)).collect(),
+                    Chamber {
+                        width: left,
+                        ..chamber
+                    },
+                    Chamber {
+                        x: wx + 1,
+                        width: width - left,
+                        ..chamber
+                    },
+                )
+            }
+        };
+
+    // A mask may disable the cells on either side of some part of the wall, so the gap is left
+    // where both of them are enabled
+    let openings: Vec<Coords> = wall
+        .into_iter()
+        .filter(|coords| grid.get_next_cell_coords(*coords, dir).is_ok())
+        .collect();
+
+    if let Some(gap) = openings.choose(rng).copied() {
+        grid.emit(Event::Visit(gap));
+        for coords in openings.into_iter().filter(|coords| *coords != gap) {
+            grid.build_wall(coords, dir).unwrap();
+        }
+    }
+
+    (first, second)
+}
@@ -1,14 +1,18 @@
 use super::{Algorithm, BOOL_TRUE_PROBABILITY};
+use crate::maze::events::Event;
 use crate::utils::types::Coords;
 use crate::maze::grid::{Grid, cell::Cell as GridCell};
 
 use rand::prelude::*;
-use std::{cell::RefCell, collections::HashMap};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct CellId(usize);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct SetId(usize);
 
 #[derive(Debug, Clone, Copy)]
@@ -64,10 +68,17 @@ impl State {
         self.cells.insert(_id, RefCell::new(cell));
     }
 
+    /// Merges the whole set of a target cell into the set of a sink cell
     fn connect(&mut self, sink_id: CellId, target_id: CellId) {
-        let sink = self.cells.get(&sink_id).unwrap().borrow();
-        let mut target = self.cells.get(&target_id).unwrap().borrow_mut();
-        target.set_id = sink.set_id;
+        let sink_set_id = self.cells.get(&sink_id).unwrap().borrow().set_id;
+        let target_set_id = self.cells.get(&target_id).unwrap().borrow().set_id;
+
+        for cell in self.cells.values() {
+            let mut cell = cell.borrow_mut();
+            if cell.set_id == target_set_id {
+                cell.set_id = sink_set_id;
+            }
+        }
     }
 
     fn connected(&self, id: CellId, other_id: CellId) -> bool {
@@ -81,8 +92,10 @@ impl State {
         cell.coords
     }
 
-    fn sets(&self) -> HashMap<SetId, Vec<CellId>> {
-        let mut sets: HashMap<SetId, Vec<CellId>> = HashMap::new();
+    /// Returns the cells of each set, ordered by the set and cell ids, so a seed always gives the
+    /// same maze
+    fn sets(&self) -> BTreeMap<SetId, Vec<CellId>> {
+        let mut sets: BTreeMap<SetId, Vec<CellId>> = BTreeMap::new();
 
         self.cells.iter().for_each(|(id, cell)| {
             let cell = cell.borrow();
@@ -93,6 +106,7 @@ impl State {
                 sets.insert(cell.set_id, vec![*id]);
             }
         });
+        sets.values_mut().for_each(|cells| cells.sort());
 
         sets
     }
@@ -117,6 +131,10 @@ impl Eller {
         for c in 1..state.width {
             let cell_id = CellId(c);
             let next_cell_id = CellId(c + 1);
+            let (x, y) = state.get_cell_coords(cell_id);
+            if grid.is_cell_enabled((x, y)) {
+                grid.emit(Event::Visit((x, y)));
+            }
 
             if state.connected(cell_id, next_cell_id)
                 || (!is_last_row && rng.random_bool(BOOL_TRUE_PROBABILITY))
@@ -124,9 +142,10 @@ impl Eller {
                 continue;
             }
 
-            state.connect(cell_id, next_cell_id);
-            let (x, y) = state.get_cell_coords(cell_id);
-            grid.carve_passage((x, y), GridCell::EAST).unwrap();
+            // The sets remain disjoint if a mask disables either of the cells
+            if grid.carve_passage((x, y), GridCell::EAST).is_ok() {
+                state.connect(cell_id, next_cell_id);
+            }
         }
     }
 
@@ -144,7 +163,13 @@ impl Eller {
             return next_state.populate();
         }
 
-        for (set_id, cells) in state.sets() {
+        for (set_id, mut cells) in state.sets() {
+            // Only the cells with an enabled cell below them can connect downward
+            cells.retain(|id| {
+                let coords = state.get_cell_coords(*id);
+                grid.get_next_cell_coords(coords, GridCell::SOUTH).is_ok()
+            });
+
             for cell_id in self.cells_to_connect(cells, rng) {
                 let (x, y) = state.get_cell_coords(cell_id);
                 grid.carve_passage((x, y), GridCell::SOUTH).unwrap();
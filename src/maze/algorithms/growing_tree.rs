@@ -1,15 +1,17 @@
-use super::Algorithm;
+use super::{enabled_start, tunnel_under, Algorithm, GraphAlgorithm};
+use crate::maze::events::Event;
+use crate::maze::graph::Graph;
 use crate::maze::grid::{Grid, cell::Cell};
 use crate::utils::types::Coords;
 
-use clap::ValueEnum;
 use rand::prelude::*;
 
 /// An enumeration over supported cell selection methods for the "Growing Tree" algorithm
 ///
 /// Each method represents the way a new cell is selected causing the "Growing Tree" algorithm
 /// to imitate other algorithms or theirs combinations
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
 pub enum Method {
     /// Selects the most recently added cell, thus imitating the recursive backtracker
     Newest,
@@ -115,11 +117,16 @@ impl Algorithm for GrowingTree {
     fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
         let mut directions = [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST];
         let mut cells = vec![];
-        cells.push(get_rand_coords(grid, rng));
+        let start_coords = get_rand_coords(grid, rng);
+        cells.extend(enabled_start(grid, start_coords, rng));
+        if let Some(&start) = cells.first() {
+            grid.emit(Event::Mark(start));
+        }
 
         while !cells.is_empty() {
             let mut index = Some(self.choose_index(cells.len(), rng));
             let coords = cells[index.unwrap_or(0)];
+            grid.emit(Event::Visit(coords));
 
             directions.shuffle(rng);
             for dir in directions {
@@ -129,10 +136,19 @@ impl Algorithm for GrowingTree {
                 };
 
                 if grid.is_cell_visited(next) {
+                    // In a weave maze, try to tunnel under the visited cell to an unvisited one
+                    // behind it
+                    if let Some(next) = tunnel_under(grid, coords, dir) {
+                        grid.emit(Event::Mark(next));
+                        cells.push(next);
+                        index = None;
+                        break;
+                    }
                     continue;
                 }
 
                 if let Ok(next) = grid.carve_passage(coords, dir) {
+                    grid.emit(Event::Mark(next));
                     cells.push(next);
                     index = None;
                     break;
@@ -140,7 +156,39 @@ impl Algorithm for GrowingTree {
             }
 
             if let Some(index) = index {
-                cells.remove(index);
+                grid.emit(Event::Backtrack(cells.remove(index)));
+            }
+        }
+    }
+}
+
+/// An implementation of the "Growing Tree" algorithm for generating mazes of any shape
+impl GraphAlgorithm for GrowingTree {
+    fn carve(&mut self, graph: &mut Graph, rng: &mut StdRng) {
+        let mut cells = vec![];
+        if !graph.is_empty() {
+            cells.push(rng.random_range(0..graph.len()));
+        }
+
+        while !cells.is_empty() {
+            let index = self.choose_index(cells.len(), rng);
+            let cell = cells[index];
+
+            let unvisited: Vec<usize> = graph
+                .neighbours(cell)
+                .iter()
+                .copied()
+                .filter(|next| !graph.is_visited(*next))
+                .collect();
+
+            match unvisited.choose(rng) {
+                Some(&next) => {
+                    graph.link(cell, next).unwrap();
+                    cells.push(next);
+                }
+                None => {
+                    cells.remove(index);
+                }
             }
         }
     }
@@ -0,0 +1,106 @@
+use super::aldous_broder::{carve_walk, link_walk};
+use super::wilson::{carve_walks, link_walks};
+use super::{enabled_start, region, Algorithm, GraphAlgorithm};
+use crate::maze::graph::Graph;
+use crate::maze::grid::Grid;
+use crate::utils::types::Coords;
+
+use rand::prelude::*;
+
+/// A share of the cells the algorithm visits by default before switching to Wilson's algorithm
+const DEFAULT_THRESHOLD: f64 = 1.0 / 3.0;
+
+/// The Houston's algorithm for generating mazes
+///
+/// This is a hybrid of the Aldous-Broder's and Wilson's algorithms, which generates uniform
+/// spanning trees like both of them. The Aldous-Broder's algorithm quickly visits the first cells
+/// but slows down towards the end, while the Wilson's algorithm starts slowly and speeds up as the
+/// maze grows. So, this one starts with the former and switches to the latter once a given share
+/// of cells is visited, which makes it the fastest of the three.
+pub struct Houston {
+    threshold: f64,
+}
+
+impl Houston {
+    /// Create a new instance of the algorithm, which switches to the Wilson's algorithm once a
+    /// third of the cells is visited
+    pub const fn new() -> Houston {
+        Houston {
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+
+    /// Sets a share of the cells to visit before switching to the Wilson's algorithm, from `0.0`
+    /// to `1.0`, and returns itself
+    ///
+    /// A share of `0.0` makes it the Wilson's algorithm, while a share of `1.0` makes it the
+    /// Aldous-Broder's one.
+    pub const fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns the number of the cells to visit with the Aldous-Broder's algorithm, apart from
+    /// the first one, out of a given number of cells
+    fn walk_length(&self, cells: usize) -> usize {
+        ((cells as f64 * self.threshold).ceil() as usize).saturating_sub(1)
+    }
+}
+
+impl Default for Houston {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of the Houston's algorithm for generating mazes
+///
+/// Here is how it works:
+///
+/// 1. Performs a random walk from any cell, carving a passage into every unvisited cell it steps
+///    into, until a given share of cells is visited.
+///
+/// 2. Performs a loop-erased random walk from every cell not in the maze yet until it reaches a
+///    cell in the maze, carving the passages along the walk.
+impl Algorithm for Houston {
+    fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
+        let start_coords = get_start_coords(grid, rng);
+        let Some(start) = enabled_start(grid, start_coords, rng) else {
+            return;
+        };
+
+        let cells = region(grid, start);
+        let (x, y) = carve_walk(grid, start, self.walk_length(cells.len()), rng);
+
+        let mut in_maze: Vec<bool> = (0..grid.height())
+            .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+            .map(|coords| grid.is_cell_visited(coords))
+            .collect();
+        in_maze[y * grid.width() + x] = true;
+
+        carve_walks(grid, &cells, &mut in_maze, rng);
+    }
+}
+
+/// An implementation of the Houston's algorithm for generating mazes of any shape
+impl GraphAlgorithm for Houston {
+    fn carve(&mut self, graph: &mut Graph, rng: &mut StdRng) {
+        if graph.is_empty() {
+            return;
+        }
+
+        let start = rng.random_range(0..graph.len());
+        let cell = link_walk(graph, start, self.walk_length(graph.len()), rng);
+
+        let mut in_maze: Vec<bool> = (0..graph.len()).map(|i| graph.is_visited(i)).collect();
+        in_maze[cell] = true;
+
+        link_walks(graph, &mut in_maze, rng);
+    }
+}
+
+fn get_start_coords<R: Rng>(grid: &Grid, rng: &mut R) -> Coords {
+    let y = rng.random_range(0..grid.height());
+    let x = rng.random_range(0..grid.width());
+    (x, y)
+}
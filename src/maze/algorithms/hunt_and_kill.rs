@@ -1,4 +1,6 @@
-use super::Algorithm;
+use super::{enabled_start, Algorithm, GraphAlgorithm};
+use crate::maze::events::Event;
+use crate::maze::graph::Graph;
 use crate::utils::types::Coords;
 use crate::maze::grid::{Grid, cell::Cell};
 
@@ -43,7 +45,7 @@ impl HuntAndKill {
             let mut unvisited_cells_count = 0;
 
             for x in 0..grid.width() {
-                if grid.is_cell_visited((x, y)) {
+                if grid.is_cell_visited((x, y)) || !grid.is_cell_enabled((x, y)) {
                     continue;
                 } else {
                     unvisited_cells_count += 1;
@@ -100,10 +102,13 @@ impl Default for HuntAndKill {
 impl Algorithm for HuntAndKill {
     fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
         let start_coords = get_start_coords(grid, rng);
-        let mut x = start_coords.0;
-        let mut y = start_coords.1;
+        let Some((mut x, mut y)) = enabled_start(grid, start_coords, rng) else {
+            return;
+        };
 
         loop {
+            grid.emit(Event::Visit((x, y)));
+
             if let Some((nx, ny)) = self.walk((x, y), grid, rng) {
                 x = nx;
                 y = ny;
@@ -117,6 +122,59 @@ impl Algorithm for HuntAndKill {
     }
 }
 
+/// An implementation of the "Hunt & Kill" algorithm for generating mazes of any shape
+///
+/// The "hunt" phase scans the cells in the order of their indices, skipping the ones at the start
+/// that are all visited already.
+impl GraphAlgorithm for HuntAndKill {
+    fn carve(&mut self, graph: &mut Graph, rng: &mut StdRng) {
+        if graph.is_empty() {
+            return;
+        }
+
+        let mut cell = rng.random_range(0..graph.len());
+        let mut hunt_start_index = 0;
+
+        loop {
+            let unvisited: Vec<usize> = graph
+                .neighbours(cell)
+                .iter()
+                .copied()
+                .filter(|next| !graph.is_visited(*next))
+                .collect();
+
+            if let Some(&next) = unvisited.choose(rng) {
+                graph.link(cell, next).unwrap();
+                cell = next;
+                continue;
+            }
+
+            while hunt_start_index < graph.len() && graph.is_visited(hunt_start_index) {
+                hunt_start_index += 1;
+            }
+
+            let hunted = (hunt_start_index..graph.len()).find_map(|index| {
+                let visited = graph
+                    .neighbours(index)
+                    .iter()
+                    .find(|next| graph.is_visited(**next));
+                (!graph.is_visited(index))
+                    .then_some(visited)
+                    .flatten()
+                    .map(|next| (index, *next))
+            });
+
+            match hunted {
+                Some((index, next)) => {
+                    graph.link(index, next).unwrap();
+                    cell = index;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
 fn get_start_coords<R: Rng>(grid: &Grid, rng: &mut R) -> Coords {
     let y = rng.random_range(0..grid.height());
     let x = rng.random_range(0..grid.width());
@@ -1,13 +1,19 @@
-use super::Algorithm;
+use super::{Algorithm, GraphAlgorithm, BOOL_TRUE_PROBABILITY};
+use crate::maze::events::Event;
+use crate::maze::graph::Graph;
 use crate::maze::grid::cell::Cell;
 use crate::maze::grid::Grid;
 use crate::utils::arena::{ArenaTree, NodeId};
+use crate::utils::types::Coords;
 
 use rand::prelude::*;
 
 type Edge = (usize, usize, Cell);
 type Edges = Vec<Edge>;
 
+/// A probability of placing a crossing at each cell of a weave maze
+const CROSSING_PROBABILITY: f64 = 0.3;
+
 /// The Kruskal's algorithm for generating mazes
 ///
 /// Kruskal’s algorithm is a method for producing a minimal spanning tree from a weighted graph.
@@ -25,9 +31,16 @@ pub struct Kruskal;
 ///    Otherwise, throw that edge away.
 ///
 /// 3. Repeat until there are no more edges left in the set.
+///
+/// For a weave maze, some crossings are placed at random before all that, each joining the trees
+/// of the cells it connects. The edges cut off by the crossing tunnels are then thrown away.
 impl Algorithm for Kruskal {
     fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
         let mut arena = populate_arena(grid);
+        if grid.allows_tunnels() {
+            place_crossings(grid, &mut arena, rng);
+        }
+
         let mut edges: Edges = populate_edges(grid);
         edges.shuffle(rng);
 
@@ -39,12 +52,40 @@ impl Algorithm for Kruskal {
 
             let (x, y, direction) = edge.unwrap();
             let (nx, ny) = grid.get_next_cell_coords((x, y), direction).unwrap();
+            grid.emit(Event::Visit((x, y)));
 
             let node1 = NodeId(y * grid.width() + x);
             let node2 = NodeId(ny * grid.width() + nx);
-            if !arena.connected(node1, node2) {
+            if !arena.connected(node1, node2) && grid.carve_passage((x, y), direction).is_ok() {
                 arena.connect(node1, node2);
-                grid.carve_passage((x, y), direction).unwrap();
+            }
+        }
+    }
+}
+
+/// An implementation of the Kruskal's algorithm for generating mazes of any shape
+impl GraphAlgorithm for Kruskal {
+    fn carve(&mut self, graph: &mut Graph, rng: &mut StdRng) {
+        let mut arena = ArenaTree::new();
+        for _ in 0..graph.len() {
+            arena.new_node();
+        }
+
+        let mut edges: Vec<(usize, usize)> = (0..graph.len())
+            .flat_map(|cell| {
+                graph
+                    .neighbours(cell)
+                    .iter()
+                    .filter(move |next| cell < **next)
+                    .map(move |next| (cell, *next))
+            })
+            .collect();
+        edges.shuffle(rng);
+
+        while let Some((cell, next)) = edges.pop() {
+            if !arena.connected(NodeId(cell), NodeId(next)) {
+                graph.link(cell, next).unwrap();
+                arena.connect(NodeId(cell), NodeId(next));
             }
         }
     }
@@ -58,18 +99,63 @@ fn populate_arena(grid: &Grid) -> ArenaTree {
     arena
 }
 
+/// Places crossings at random cells of a grid, each made of a straight corridor across a cell and a
+/// tunnel under it, as long as they connect the cells of disjoint trees
+fn place_crossings<R: Rng>(grid: &mut Grid, arena: &mut ArenaTree, rng: &mut R) {
+    let width = grid.width();
+    let node = |(x, y): Coords| NodeId(y * width + x);
+
+    for y in 1..grid.height().saturating_sub(1) {
+        for x in 1..grid.width().saturating_sub(1) {
+            if !rng.random_bool(CROSSING_PROBABILITY) {
+                continue;
+            }
+
+            let (corridor, tunnel) = if rng.random_bool(BOOL_TRUE_PROBABILITY) {
+                (Cell::NORTH, Cell::EAST)
+            } else {
+                (Cell::WEST, Cell::SOUTH)
+            };
+
+            let coords = (x, y);
+            let Ok(ends) = [corridor, corridor.opposite(), tunnel, tunnel.opposite()]
+                .map(|dir| grid.get_next_cell_coords(coords, dir))
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()
+            else {
+                continue;
+            };
+
+            if !grid.get_cell(coords).is_empty()
+                || arena.connected(node(ends[0]), node(ends[1]))
+                || arena.connected(node(ends[2]), node(ends[3]))
+            {
+                continue;
+            }
+
+            grid.carve_passage(coords, corridor).unwrap();
+            grid.carve_passage(coords, corridor.opposite()).unwrap();
+            grid.carve_tunnel(ends[3], tunnel).unwrap();
+
+            arena.connect(node(ends[0]), node(coords));
+            arena.connect(node(coords), node(ends[1]));
+            arena.connect(node(ends[3]), node(ends[2]));
+        }
+    }
+}
+
 fn populate_edges(grid: &Grid) -> Edges {
     let mut edges: Vec<Edge> = vec![];
 
     for y in 0..grid.height() {
         for x in 0..grid.width() {
-            if y > 0 {
-                edges.push((x, y, Cell::NORTH))
-            }
-            if x > 0 {
-                edges.push((x, y, Cell::WEST))
-            }
+            edges.push((x, y, Cell::NORTH));
+            edges.push((x, y, Cell::WEST));
         }
     }
+
+    // Drop the edges leading out of the grid, unless its borders wrap around, or into or out of
+    // the cells disabled by a mask
+    edges.retain(|(x, y, dir)| grid.get_next_cell_coords((*x, *y), *dir).is_ok());
     edges
 }
@@ -2,35 +2,160 @@
 
 mod aldous_broder;
 mod binary_tree;
+mod chamber_division;
 mod eller;
 mod growing_tree;
+mod houston;
 mod hunt_and_kill;
 mod kruskal;
+mod origin_shift;
 mod prim;
 mod recursive_backtracking;
 mod recursive_division;
 mod sidewinder;
+mod wilson;
 
 pub use aldous_broder::AldousBroder;
 pub use binary_tree::{Bias, BinaryTree};
+pub use chamber_division::ChamberDivision;
 pub use eller::Eller;
 pub use growing_tree::{GrowingTree, Method};
+pub use houston::Houston;
 pub use hunt_and_kill::HuntAndKill;
 pub use kruskal::Kruskal;
+pub use origin_shift::{OriginShift, OriginShifter};
 pub use prim::Prim;
 pub use recursive_backtracking::RecursiveBacktracking;
 pub use recursive_division::RecursiveDivision;
 pub use sidewinder::Sidewinder;
+pub use wilson::Wilson;
 
+use rand::prelude::*;
 use rand::rngs::StdRng;
 
-use crate::maze::grid::Grid;
+use crate::maze::graph::Graph;
+use crate::maze::grid::{cell::Cell, Grid};
+use crate::utils::types::Coords;
 
 pub(super) const BOOL_TRUE_PROBABILITY: f64 = 0.5;
 
+/// Returns the given coordinates of a cell to start generating a maze from if the cell is enabled.
+/// Otherwise, returns the coordinates of a random enabled cell, or `None` if all the cells are
+/// disabled by a mask
+pub(super) fn enabled_start<R: Rng>(grid: &Grid, coords: Coords, rng: &mut R) -> Option<Coords> {
+    if grid.is_cell_enabled(coords) {
+        return Some(coords);
+    }
+
+    grid.enabled_cells().choose(rng)
+}
+
+/// Carves a tunnel out of a cell at the given coordinates in a given direction, if a grid allows
+/// tunnels and the cell on the other side of the adjacent one has not been visited yet, and returns
+/// the coordinates of that cell
+pub(super) fn tunnel_under(grid: &mut Grid, coords: Coords, direction: Cell) -> Option<Coords> {
+    if !grid.allows_tunnels() {
+        return None;
+    }
+
+    let next = grid.get_next_cell_coords(coords, direction).ok()?;
+    let beyond = grid.get_next_cell_coords(next, direction).ok()?;
+    if grid.is_cell_visited(beyond) {
+        return None;
+    }
+
+    grid.carve_tunnel(coords, direction).ok()
+}
+
+/// Returns the number of cells reachable from a cell at the given coordinates by moving between
+/// adjacent enabled cells, the cell itself included
+pub(super) fn region_size(grid: &Grid, coords: Coords) -> usize {
+    region(grid, coords).len()
+}
+
+/// Returns the coordinates of all the cells reachable from a cell at the given coordinates by
+/// moving between adjacent enabled cells, the cell itself included
+pub(super) fn region(grid: &Grid, coords: Coords) -> Vec<Coords> {
+    let mut seen = vec![false; grid.width() * grid.height()];
+    let mut stack = vec![coords];
+    let mut cells = Vec::new();
+
+    while let Some((x, y)) = stack.pop() {
+        if seen[y * grid.width() + x] {
+            continue;
+        }
+        seen[y * grid.width() + x] = true;
+        cells.push((x, y));
+
+        for (_, next) in grid.get_adjacent_cells((x, y)) {
+            stack.push(next);
+        }
+    }
+
+    cells
+}
+
 /// A trait for generating a maze using a selected algorithm
+///
+/// Apart from the algorithms shipped with this crate, you can implement your own one on top of the
+/// public [Grid] API and pass it to the
+/// [OrthogonalMazeBuilder::algorithm](crate::maze::OrthogonalMazeBuilder::algorithm). Such
+/// mazes get seeding, validation and all the formatters for free.
+///
+/// # Example
+/// ```
+/// use knossos::maze::*;
+/// use rand::{rngs::StdRng, Rng};
+///
+/// /// Carves a serpentine corridor through the entire grid
+/// struct Serpentine;
+///
+/// impl Algorithm for Serpentine {
+///     fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
+///         for y in 0..grid.height() {
+///             for x in 0..grid.width() - 1 {
+///                 grid.carve_passage((x, y), Cell::EAST).unwrap();
+///             }
+///             if y + 1 < grid.height() {
+///                 let x = if rng.random() { 0 } else { grid.width() - 1 };
+///                 grid.carve_passage((x, y), Cell::SOUTH).unwrap();
+///             }
+///         }
+///     }
+/// }
+///
+/// let maze = OrthogonalMazeBuilder::new()
+///     .algorithm(Box::new(Serpentine))
+///     .build();
+///
+/// assert!(maze.is_valid());
+/// ```
 pub trait Algorithm {
     /// Runs algorithm through the given Grid object, thus mutating the grid and generating a new
     /// maze
     fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng);
 }
+
+/// A trait for generating a maze of any shape using a selected algorithm
+///
+/// Unlike an [Algorithm], which works on the rows and columns of an orthogonal [Grid], a graph
+/// algorithm only relies on the cells adjacent to each other, so it generates mazes on the grids
+/// of other shapes, such as a [PolarGrid](crate::maze::PolarGrid). The
+/// [RecursiveBacktracking], [AldousBroder], [Kruskal], [Prim], [GrowingTree], [HuntAndKill],
+/// [Wilson] and [Houston] algorithms implement both traits.
+///
+/// # Example
+/// ```
+/// use knossos::maze::*;
+///
+/// let maze = PolarMazeBuilder::new()
+///     .algorithm(Box::new(Kruskal))
+///     .build();
+///
+/// assert!(maze.is_perfect());
+/// ```
+pub trait GraphAlgorithm {
+    /// Runs algorithm through the given Graph object, thus mutating the graph and generating a new
+    /// maze
+    fn carve(&mut self, graph: &mut Graph, rng: &mut StdRng);
+}
@@ -0,0 +1,343 @@
+use super::{enabled_start, Algorithm};
+use crate::maze::errors::MutationError;
+use crate::maze::events::Event;
+use crate::maze::grid::{cell::Cell, Grid};
+use crate::maze::maze::OrthogonalMaze;
+use crate::utils::types::Coords;
+
+use rand::prelude::*;
+use std::collections::VecDeque;
+
+/// The number of times per cell the algorithm shifts the origin by default
+const SHIFTS_PER_CELL: usize = 10;
+
+/// The "origin shift" algorithm for generating mazes
+///
+/// The algorithm keeps a maze as a tree, where each cell points to the adjacent cell on its way to
+/// the root of the tree, called the origin. It starts with a simple maze and shifts the origin to
+/// a random adjacent cell over and over again, which rebuilds a single wall at a time. Since a
+/// maze remains perfect after every shift, it can also be mutated while in use, see
+/// [OriginShifter].
+pub struct OriginShift {
+    shifts: Option<usize>,
+}
+
+impl OriginShift {
+    /// Create a new instance of the algorithm, which shifts the origin ten times per cell
+    pub const fn new() -> OriginShift {
+        OriginShift { shifts: None }
+    }
+
+    /// Sets a number of times to shift the origin and returns itself
+    ///
+    /// The fewer shifts, the more a maze resembles the simple one the algorithm starts with.
+    pub const fn shifts(mut self, shifts: usize) -> Self {
+        self.shifts = Some(shifts);
+        self
+    }
+}
+
+impl Default for OriginShift {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of the "origin shift" algorithm for generating mazes
+///
+/// Here is how it works:
+///
+/// 1. Chooses a cell to be the origin and carves a maze, where each cell points to the adjacent
+///    cell it's reached from by a breadth-first search from the origin.
+///
+/// 2. Chooses a random cell adjacent to the origin and makes the origin point to it, carving a
+///    passage between them.
+///
+/// 3. Makes the chosen cell the new origin, which points nowhere, so builds a wall between it and
+///    the cell it used to point to.
+///
+/// 4. Repeats steps 2 and 3 a given number of times.
+impl Algorithm for OriginShift {
+    fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
+        let start_coords = get_start_coords(grid, rng);
+        let Some(origin) = enabled_start(grid, start_coords, rng) else {
+            return;
+        };
+
+        let mut tree = Tree::carve(grid, origin);
+        let shifts = self.shifts.unwrap_or(SHIFTS_PER_CELL * tree.size);
+        for _ in 0..shifts {
+            tree.shift(grid, rng).unwrap();
+        }
+    }
+}
+
+/// A mutator that keeps changing a perfect maze by shifting its origin, so the maze remains
+/// perfect after every single shift
+///
+/// It's the [OriginShift] algorithm picking up an existing maze, which may be generated by any
+/// other algorithm. Each shift rebuilds at most one wall, so a maze slowly morphs over time, which
+/// suits the games that change a maze during play.
+///
+/// # Example
+/// ```rust
+/// use knossos::maze::*;
+///
+/// let mut maze = OrthogonalMazeBuilder::new().seed(Some(1)).build();
+/// let mut shifter = OriginShifter::new(&maze, Some(1)).unwrap();
+///
+/// for _ in 0..10 {
+///     shifter.shift(&mut maze, 5).unwrap();
+///     assert!(maze.is_perfect());
+/// }
+/// ```
+pub struct OriginShifter {
+    tree: Tree,
+    rng: StdRng,
+}
+
+impl OriginShifter {
+    /// Creates a mutator of a given maze with the origin in a random cell
+    ///
+    /// A seed makes the origin and the shifts deterministic. Without it, they are random.
+    ///
+    /// # Errors
+    /// Returns a [MutationError] if a maze is not perfect or has tunnels.
+    pub fn new(maze: &OrthogonalMaze, seed: Option<u64>) -> Result<OriginShifter, MutationError> {
+        let mut rng = match seed {
+            Some(val) => StdRng::seed_from_u64(val),
+            None => StdRng::from_os_rng(),
+        };
+
+        let grid = maze.get_grid();
+        let origin = grid
+            .enabled_cells()
+            .choose(&mut rng)
+            .ok_or_else(|| MutationError {
+                reason: String::from("Maze has no enabled cells"),
+            })?;
+        let tree = Tree::read(grid, origin)?;
+
+        Ok(OriginShifter { tree, rng })
+    }
+
+    /// Returns the coordinates of the origin, i.e. the cell every path through the tree leads to
+    pub const fn origin(&self) -> Coords {
+        self.tree.origin
+    }
+
+    /// Shifts the origin a given number of times, mutating a given maze
+    ///
+    /// The maze must be the one the mutator is created for and must not be changed by anything
+    /// else.
+    ///
+    /// # Errors
+    /// Returns a [MutationError] if the maze doesn't match the tree of the mutator.
+    pub fn shift(&mut self, maze: &mut OrthogonalMaze, shifts: usize) -> Result<(), MutationError> {
+        let grid = maze.get_grid_mut();
+        if grid.width() != self.tree.width || grid.width() * grid.height() != self.tree.len() {
+            return Err(MutationError {
+                reason: String::from("Maze size doesn't match the mutator"),
+            });
+        }
+
+        for _ in 0..shifts {
+            self.tree.shift(grid, &mut self.rng)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A perfect maze as a tree, where each cell points to the adjacent cell on its way to the origin
+struct Tree {
+    origin: Coords,
+    parents: Vec<Cell>,
+    width: usize,
+    size: usize,
+}
+
+impl Tree {
+    /// Carves a tree out of a grid by a breadth-first search from the origin
+    fn carve(grid: &mut Grid, origin: Coords) -> Tree {
+        let mut tree = Tree::new(grid, origin);
+        let mut seen = vec![false; tree.len()];
+        seen[tree.index(origin)] = true;
+
+        let mut queue = VecDeque::from([origin]);
+        while let Some(coords) = queue.pop_front() {
+            for (dir, next) in grid.get_adjacent_cells(coords) {
+                if seen[tree.index(next)] {
+                    continue;
+                }
+
+                seen[tree.index(next)] = true;
+                grid.carve_passage(next, dir.opposite()).unwrap();
+                tree.attach(next, dir.opposite());
+                queue.push_back(next);
+            }
+        }
+
+        tree
+    }
+
+    /// Reads a tree of the passages of a perfect maze by a breadth-first search from the origin
+    fn read(grid: &Grid, origin: Coords) -> Result<Tree, MutationError> {
+        let error = |reason: &str| MutationError {
+            reason: reason.to_string(),
+        };
+
+        if grid
+            .enabled_cells()
+            .any(|coords| !grid.get_tunnel(coords).is_empty())
+        {
+            return Err(error("Maze has tunnels"));
+        }
+
+        let mut tree = Tree::new(grid, origin);
+        let mut seen = vec![false; tree.len()];
+        seen[tree.index(origin)] = true;
+
+        let mut queue = VecDeque::from([origin]);
+        while let Some(coords) = queue.pop_front() {
+            let parent = tree.parents[tree.index(coords)];
+            for dir in [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST] {
+                let Some(next) = grid.get_linked_cell_coords(coords, dir) else {
+                    continue;
+                };
+                if dir == parent {
+                    continue;
+                }
+                if seen[tree.index(next)] {
+                    return Err(error("Maze has loops"));
+                }
+
+                seen[tree.index(next)] = true;
+                tree.attach(next, dir.opposite());
+                queue.push_back(next);
+            }
+        }
+
+        if tree.size != grid.enabled_cells().count() {
+            return Err(error("Maze has unreachable cells"));
+        }
+
+        Ok(tree)
+    }
+
+    fn new(grid: &Grid, origin: Coords) -> Tree {
+        Tree {
+            origin,
+            parents: vec![Cell::empty(); grid.width() * grid.height()],
+            width: grid.width(),
+            size: 1,
+        }
+    }
+
+    /// Makes a cell at the given coordinates point to its parent in a given direction
+    fn attach(&mut self, coords: Coords, direction: Cell) {
+        let index = self.index(coords);
+        self.parents[index] = direction;
+        self.size += 1;
+    }
+
+    /// Shifts the origin to a random adjacent cell
+    fn shift(&mut self, grid: &mut Grid, rng: &mut StdRng) -> Result<(), MutationError> {
+        let Some((dir, next)) = grid.get_adjacent_cells(self.origin).choose(rng).copied() else {
+            return Ok(());
+        };
+
+        // The chosen cell is linked with the origin only if it already points to the origin
+        let parent = self.parents[self.index(next)];
+        if parent != dir.opposite() {
+            if parent.is_empty() || !grid.is_carved(next, parent) {
+                return Err(MutationError {
+                    reason: String::from("Maze doesn't match the mutator"),
+                });
+            }
+
+            grid.carve_passage(self.origin, dir).unwrap();
+            grid.build_wall(next, parent).unwrap();
+        }
+
+        let (origin, next_index) = (self.index(self.origin), self.index(next));
+        self.parents[origin] = dir;
+        self.parents[next_index] = Cell::empty();
+        self.origin = next;
+        grid.emit(Event::Visit(next));
+
+        Ok(())
+    }
+
+    const fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    const fn index(&self, (x, y): Coords) -> usize {
+        y * self.width + x
+    }
+}
+
+fn get_start_coords<R: Rng>(grid: &Grid, rng: &mut R) -> Coords {
+    let y = rng.random_range(0..grid.height());
+    let x = rng.random_range(0..grid.width());
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shift_keeps_maze_perfect() {
+        let mut maze = OrthogonalMaze::new(6, 4);
+        let mut rng = StdRng::seed_from_u64(3);
+        OriginShift::new()
+            .shifts(0)
+            .generate(maze.get_grid_mut(), &mut rng);
+        assert!(maze.is_perfect());
+
+        let mut shifter = OriginShifter::new(&maze, Some(3)).unwrap();
+        for _ in 0..100 {
+            let origin = shifter.origin();
+            shifter.shift(&mut maze, 1).unwrap();
+
+            assert!(maze.is_perfect());
+            assert_eq!(
+                1,
+                origin.0.abs_diff(shifter.origin().0) + origin.1.abs_diff(shifter.origin().1)
+            );
+        }
+    }
+
+    #[test]
+    fn shift_mismatched_maze() {
+        let mut maze = OrthogonalMaze::new(3, 3);
+        let mut rng = StdRng::seed_from_u64(3);
+        OriginShift::new().generate(maze.get_grid_mut(), &mut rng);
+        let mut shifter = OriginShifter::new(&maze, Some(3)).unwrap();
+
+        let mut other = OrthogonalMaze::new(3, 2);
+        let error = shifter.shift(&mut other, 1).err().unwrap();
+        assert_eq!("Maze size doesn't match the mutator", error.reason);
+
+        // An empty maze has no passages the tree of the mutator points along
+        let mut other = OrthogonalMaze::new(3, 3);
+        assert!(shifter.shift(&mut other, 10).is_err());
+    }
+
+    #[test]
+    fn read_imperfect_maze() {
+        let mut maze = OrthogonalMaze::new(2, 2);
+        let reason =
+            |maze: &OrthogonalMaze| OriginShifter::new(maze, Some(1)).err().unwrap().reason;
+        assert_eq!("Maze has unreachable cells", reason(&maze));
+
+        let grid = maze.get_grid_mut();
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 1), Cell::EAST).unwrap();
+        assert_eq!("Maze has loops", reason(&maze));
+    }
+}
@@ -1,4 +1,6 @@
-use super::Algorithm;
+use super::{enabled_start, Algorithm, GraphAlgorithm};
+use crate::maze::events::Event;
+use crate::maze::graph::Graph;
 use crate::utils::types::Coords;
 use crate::maze::grid::{Grid, cell::Cell};
 
@@ -26,47 +28,30 @@ impl Prim {
     fn mark(&mut self, coords: Coords, grid: &mut Grid) {
         grid.mark_cell(coords);
 
-        let (x, y) = coords;
-        self.add_frontier((x + 1, y), grid);
-        self.add_frontier((x, y + 1), grid);
-        if x > 0 {
-            self.add_frontier((x - 1, y), grid);
-        }
-        if y > 0 {
-            self.add_frontier((x, y - 1), grid);
+        for dir in [Cell::EAST, Cell::SOUTH, Cell::WEST, Cell::NORTH] {
+            if let Ok(next) = grid.get_next_cell_coords(coords, dir) {
+                self.add_frontier(next, grid);
+            }
         }
     }
 
-    fn add_frontier(&mut self, (x, y): Coords, grid: &mut Grid) {
-        if x < grid.width()
-            && y < grid.height()
-            && !grid.is_cell_marked((x, y))
-            && !self.frontiers.iter().any(|f| *f == (x, y))
-        {
-            self.frontiers.push((x, y));
+    fn add_frontier(&mut self, coords: Coords, grid: &mut Grid) {
+        if !grid.is_cell_marked(coords) && !self.frontiers.contains(&coords) {
+            self.frontiers.push(coords);
+            grid.emit(Event::Mark(coords));
         }
     }
 
-    fn neighbours(&self, (x, y): Coords, grid: &mut Grid) -> Vec<Coords> {
-        let mut neighbours = vec![];
-
-        if x > 0 && grid.is_cell_marked((x - 1, y)) {
-            neighbours.push((x - 1, y))
-        }
-
-        if x + 1 < grid.width() && grid.is_cell_marked((x + 1, y)) {
-            neighbours.push((x + 1, y))
-        }
-
-        if y > 0 && grid.is_cell_marked((x, y - 1)) {
-            neighbours.push((x, y - 1))
-        }
-
-        if y + 1 < grid.height() && grid.is_cell_marked((x, y + 1)) {
-            neighbours.push((x, y + 1))
-        }
-
-        neighbours
+    /// Returns the directions towards the adjacent cells that are already in the maze along with
+    /// their coordinates
+    fn neighbours(&self, coords: Coords, grid: &mut Grid) -> Vec<(Cell, Coords)> {
+        [Cell::WEST, Cell::EAST, Cell::NORTH, Cell::SOUTH]
+            .into_iter()
+            .filter_map(|dir| {
+                let next = grid.get_next_cell_coords(coords, dir).ok()?;
+                grid.is_cell_marked(next).then_some((dir, next))
+            })
+            .collect()
     }
 }
 
@@ -97,23 +82,72 @@ impl Default for Prim {
 /// 5. Repeats steps 3 and 4 until the F is empty.
 impl Algorithm for Prim {
     fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
-        self.mark(get_rand_coords(grid, rng), grid);
+        let start_coords = get_rand_coords(grid, rng);
+        let Some(start_coords) = enabled_start(grid, start_coords, rng) else {
+            return;
+        };
+        grid.emit(Event::Visit(start_coords));
+        self.mark(start_coords, grid);
 
         while !self.frontiers.is_empty() {
             let index = rng.random_range(0..self.frontiers.len());
             let coords = self.frontiers.remove(index);
+            grid.emit(Event::Visit(coords));
+            grid.emit(Event::Backtrack(coords));
 
             let neighbours = self.neighbours(coords, grid);
 
             let index = rng.random_range(0..neighbours.len());
-            let (nx, ny) = neighbours[index];
+            let (dir, _) = neighbours[index];
 
-            let (x, y) = coords;
+            grid.carve_passage(coords, dir).unwrap();
+            self.mark(coords, grid);
+        }
+    }
+}
 
-            if let Some(dir) = direction(x, y, nx, ny) {
-                grid.carve_passage(coords, dir).unwrap();
-                self.mark(coords, grid);
-            }
+/// An implementation of the Prim's algorithm for generating mazes of any shape
+impl GraphAlgorithm for Prim {
+    fn carve(&mut self, graph: &mut Graph, rng: &mut StdRng) {
+        if graph.is_empty() {
+            return;
+        }
+
+        let mut in_maze = vec![false; graph.len()];
+        let mut frontiers = vec![];
+        add_cell(
+            rng.random_range(0..graph.len()),
+            graph,
+            &mut in_maze,
+            &mut frontiers,
+        );
+
+        while !frontiers.is_empty() {
+            let index = rng.random_range(0..frontiers.len());
+            let cell = frontiers.remove(index);
+
+            let neighbours: Vec<usize> = graph
+                .neighbours(cell)
+                .iter()
+                .copied()
+                .filter(|next| in_maze[*next])
+                .collect();
+
+            let next = neighbours[rng.random_range(0..neighbours.len())];
+            graph.link(cell, next).unwrap();
+            add_cell(cell, graph, &mut in_maze, &mut frontiers);
+        }
+    }
+}
+
+/// Adds a cell with a given index to the maze and its neighbours that are not in the maze yet to
+/// the frontier cells
+fn add_cell(cell: usize, graph: &Graph, in_maze: &mut [bool], frontiers: &mut Vec<usize>) {
+    in_maze[cell] = true;
+
+    for &next in graph.neighbours(cell) {
+        if !in_maze[next] && !frontiers.contains(&next) {
+            frontiers.push(next);
         }
     }
 }
@@ -124,23 +158,6 @@ fn get_rand_coords<R: Rng>(grid: &Grid, rng: &mut R) -> Coords {
     (x, y)
 }
 
-fn direction(x: usize, y: usize, nx: usize, ny: usize) -> Option<Cell> {
-    if x < nx {
-        return Some(Cell::EAST);
-    }
-    if x > nx {
-        return Some(Cell::WEST);
-    }
-    if y < ny {
-        return Some(Cell::SOUTH);
-    }
-    if y > ny {
-        return Some(Cell::NORTH);
-    }
-
-    unreachable!("The x and y coordinates are never equal to nx and ny")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
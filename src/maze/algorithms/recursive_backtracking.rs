@@ -1,4 +1,6 @@
-use super::Algorithm;
+use super::{enabled_start, tunnel_under, Algorithm, GraphAlgorithm};
+use crate::maze::events::Event;
+use crate::maze::graph::Graph;
 use crate::maze::grid::{Grid, cell::Cell};
 use crate::utils::types::Coords;
 
@@ -30,12 +32,48 @@ pub struct RecursiveBacktracking;
 ///    point.
 impl Algorithm for RecursiveBacktracking {
     fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
-        let start_coords = (0, 0);
-        carve_passages_from(start_coords, grid, rng);
+        if let Some(start_coords) = enabled_start(grid, (0, 0), rng) {
+            carve_passages_from(start_coords, grid, rng);
+        }
+    }
+}
+
+/// An implementation of the "Recursive Backtracking" algorithm for generating mazes of any shape
+///
+/// It works the same way, but keeps the path back to the starting point on a stack instead of
+/// recursing, so the size of a maze is not limited by the call stack.
+impl GraphAlgorithm for RecursiveBacktracking {
+    fn carve(&mut self, graph: &mut Graph, rng: &mut StdRng) {
+        let mut stack = Vec::new();
+        if !graph.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(&cell) = stack.last() {
+            let unvisited: Vec<usize> = graph
+                .neighbours(cell)
+                .iter()
+                .copied()
+                .filter(|next| !graph.is_visited(*next))
+                .collect();
+
+            match unvisited.choose(rng) {
+                Some(&next) => {
+                    graph.link(cell, next).unwrap();
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
     }
 }
 
 fn carve_passages_from<R: Rng>(coords: Coords, grid: &mut Grid, rng: &mut R) {
+    grid.emit(Event::Visit(coords));
+    grid.emit(Event::Mark(coords));
+
     let mut dirs = [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST];
     dirs.shuffle(rng);
 
@@ -46,11 +84,19 @@ fn carve_passages_from<R: Rng>(coords: Coords, grid: &mut Grid, rng: &mut R) {
         };
 
         if grid.is_cell_visited(next) {
+            // In a weave maze, try to tunnel under the visited cell to an unvisited one behind it
+            if let Some(next) = tunnel_under(grid, coords, dir) {
+                carve_passages_from(next, grid, rng);
+                grid.emit(Event::Visit(coords));
+            }
             continue;
         }
 
         if let Ok(next) = grid.carve_passage(coords, dir) {
             carve_passages_from(next, grid, rng);
+            grid.emit(Event::Visit(coords));
         }
     }
+
+    grid.emit(Event::Backtrack(coords));
 }
@@ -1,9 +1,11 @@
 use super::{Algorithm, BOOL_TRUE_PROBABILITY};
+use crate::maze::events::Event;
 use crate::maze::grid::{cell::Cell, Grid};
+use crate::utils::types::Coords;
 
 use rand::prelude::*;
 
-enum Orientation {
+pub(super) enum Orientation {
     Horizontal,
     Vertical,
 }
@@ -47,12 +49,12 @@ impl RecursiveDivision {
             if w > 1 {
                 // Carve passages till the horizontal end of the subfield
                 for cx in x..ax {
-                    grid.carve_passage((cx, y), Cell::EAST).unwrap();
+                    grid.carve_passage((cx, y), Cell::EAST).ok();
                 }
             } else if h > 1 {
                 // Carve passages till the vertical end of the subfield
                 for cy in y..ay {
-                    grid.carve_passage((x, cy), Cell::SOUTH).unwrap();
+                    grid.carve_passage((x, cy), Cell::SOUTH).ok();
                 }
             }
             return;
@@ -71,8 +73,24 @@ impl RecursiveDivision {
             Orientation::Vertical => Cell::EAST,
         };
 
-        // Carve passage
-        let (nx, ny) = grid.carve_passage((px, py), dir).unwrap();
+        // Carve passage. If a mask disables either of the cells, carve it through any other part
+        // of the wall instead, if there is one
+        grid.emit(Event::Visit((px, py)));
+        if grid.carve_passage((px, py), dir).is_err() {
+            let wall: Vec<Coords> = match orientation {
+                Orientation::Horizontal => (x..=ax).map(|cx| (cx, py)).collect(),
+                Orientation::Vertical => (y..=ay).map(|cy| (px, cy)).collect(),
+            };
+            let openings: Vec<Coords> = wall
+                .into_iter()
+                .filter(|coords| grid.get_next_cell_coords(*coords, dir).is_ok())
+                .collect();
+
+            if let Some(coords) = openings.choose(rng) {
+                grid.carve_passage(*coords, dir).unwrap();
+            }
+        }
+        let (nx, ny) = (px + 1, py + 1);
 
         // Determine the bounds of the subfields and get them split
         match orientation {
@@ -113,7 +131,9 @@ impl Algorithm for RecursiveDivision {
     }
 }
 
-fn choose_orientation<R: Rng>(width: usize, height: usize, rng: &mut R) -> Orientation {
+/// Returns the orientation of a wall to bisect a chamber of the given size with, which splits its
+/// longer side in two, or a random one for a square chamber
+pub(super) fn choose_orientation<R: Rng>(width: usize, height: usize, rng: &mut R) -> Orientation {
     if width < height {
         return Orientation::Horizontal;
     }
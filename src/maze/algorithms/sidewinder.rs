@@ -1,7 +1,9 @@
 use super::Algorithm;
+use crate::maze::events::Event;
 use crate::maze::grid::{Grid, cell::Cell};
 
 use rand::prelude::*;
+use std::ops::Range;
 
 /// The "Sidewinder" algorithm for generating mazes
 ///
@@ -36,14 +38,47 @@ impl Algorithm for Sidewinder {
             for x in 0..grid.width() {
                 let carve_east: bool = rng.random();
 
-                if y == 0 || (carve_east && x + 1 < grid.width()) {
-                    grid.carve_passage((x, y), Cell::EAST).ok();
+                // A cell disabled by a mask breaks a run
+                if !grid.is_cell_enabled((x, y)) {
+                    leave_run(grid, y, run_start..x);
+                    run_start = x + 1;
+                    continue;
+                }
+                grid.emit(Event::Visit((x, y)));
+                grid.emit(Event::Mark((x, y)));
+
+                // Wrapping around a row joins its last run to the first one, which has a passage
+                // North already, unless both runs are the same
+                let is_wrapping = x + 1 == grid.width();
+                let can_carve_east = grid.get_next_cell_coords((x, y), Cell::EAST).is_ok()
+                    && (!is_wrapping || (y > 0 && run_start > 0));
+                if can_carve_east && (y == 0 || carve_east) {
+                    grid.carve_passage((x, y), Cell::EAST).unwrap();
                 } else {
-                    let rand_x = rng.random_range(run_start..=x);
-                    grid.carve_passage((rand_x, y), Cell::NORTH).ok();
+                    // Close the run by carving North out of any of its cells that can go there
+                    // The first row never wraps around to the last one, which would make a loop
+                    let run: Vec<usize> = (run_start..=x)
+                        .filter(|_| y > 0)
+                        .filter(|rx| grid.get_next_cell_coords((*rx, y), Cell::NORTH).is_ok())
+                        .collect();
+                    if !run.is_empty() {
+                        let rand_x = run[rng.random_range(0..run.len())];
+                        grid.carve_passage((rand_x, y), Cell::NORTH).unwrap();
+                    }
+                    leave_run(grid, y, run_start..x + 1);
                     run_start = x + 1;
                 }
             }
+
+            // The last run of a wrapped row joins the first one instead of being closed
+            leave_run(grid, y, run_start..grid.width());
         }
     }
 }
+
+/// Emits the events of the cells in a given range of a row leaving a run
+fn leave_run(grid: &mut Grid, y: usize, run: Range<usize>) {
+    for x in run {
+        grid.emit(Event::Backtrack((x, y)));
+    }
+}
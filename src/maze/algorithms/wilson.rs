@@ -0,0 +1,127 @@
+use super::{enabled_start, region, Algorithm, GraphAlgorithm};
+use crate::maze::events::Event;
+use crate::maze::graph::Graph;
+use crate::maze::grid::{cell::Cell, Grid};
+use crate::utils::types::Coords;
+
+use rand::prelude::*;
+
+/// The Wilson's algorithm for generating mazes
+///
+/// Like the Aldous-Broder's algorithm, it generates uniform spanning trees, i.e. every possible
+/// maze is equally likely, so the mazes have no bias towards any direction or texture. Unlike that
+/// one, it's slow to start but speeds up as the maze grows, which makes it usable on large grids.
+pub struct Wilson;
+
+/// An implementation of the Wilson's algorithm for generating mazes
+///
+/// The algorithm builds a maze out of loop-erased random walks. Here is how it works:
+///
+/// 1. Chooses any cell and adds it to the maze.
+///
+/// 2. Chooses any cell not in the maze yet and performs a random walk from it until it reaches a
+///    cell in the maze. Whenever the walk crosses its own path, erases the loop it has made.
+///
+/// 3. Carves the passages along the loop-erased path and adds its cells to the maze.
+///
+/// 4. Repeats steps 2 and 3 until all the cells are in the maze.
+impl Algorithm for Wilson {
+    fn generate(&mut self, grid: &mut Grid, rng: &mut StdRng) {
+        let start_coords = get_start_coords(grid, rng);
+        let Some((x, y)) = enabled_start(grid, start_coords, rng) else {
+            return;
+        };
+
+        let mut in_maze = vec![false; grid.width() * grid.height()];
+        in_maze[y * grid.width() + x] = true;
+
+        let cells = region(grid, (x, y));
+        carve_walks(grid, &cells, &mut in_maze, rng);
+    }
+}
+
+/// An implementation of the Wilson's algorithm for generating mazes of any shape
+impl GraphAlgorithm for Wilson {
+    fn carve(&mut self, graph: &mut Graph, rng: &mut StdRng) {
+        if graph.is_empty() {
+            return;
+        }
+
+        let mut in_maze = vec![false; graph.len()];
+        in_maze[rng.random_range(0..graph.len())] = true;
+
+        link_walks(graph, &mut in_maze, rng);
+    }
+}
+
+/// Carves a loop-erased random walk from each of the given cells that is not in a maze yet until
+/// it reaches a cell in the maze, and adds the cells along the walk to the maze
+pub(super) fn carve_walks(
+    grid: &mut Grid,
+    cells: &[Coords],
+    in_maze: &mut [bool],
+    rng: &mut StdRng,
+) {
+    let width = grid.width();
+    let index = |(x, y): Coords| y * width + x;
+    let mut exits = vec![Cell::empty(); in_maze.len()];
+
+    for &start in cells {
+        // Remembering only the last exit out of each cell erases the loops made along the walk
+        let mut coords = start;
+        while !in_maze[index(coords)] {
+            grid.emit(Event::Visit(coords));
+            let (dir, next) = random_step(grid, coords, rng);
+            exits[index(coords)] = dir;
+            coords = next;
+        }
+
+        let mut coords = start;
+        while !in_maze[index(coords)] {
+            in_maze[index(coords)] = true;
+            coords = grid.carve_passage(coords, exits[index(coords)]).unwrap();
+        }
+    }
+}
+
+/// Links a loop-erased random walk from each cell of a graph that is not in a maze yet until it
+/// reaches a cell in the maze, and adds the cells along the walk to the maze
+pub(super) fn link_walks(graph: &mut Graph, in_maze: &mut [bool], rng: &mut StdRng) {
+    let mut exits = vec![0; graph.len()];
+
+    for start in 0..graph.len() {
+        let mut cell = start;
+        while !in_maze[cell] {
+            let Some(&next) = graph.neighbours(cell).choose(rng) else {
+                return;
+            };
+            exits[cell] = next;
+            cell = next;
+        }
+
+        let mut cell = start;
+        while !in_maze[cell] {
+            in_maze[cell] = true;
+            graph.link(cell, exits[cell]).unwrap();
+            cell = exits[cell];
+        }
+    }
+}
+
+/// Returns the direction and coordinates of a random cell adjacent to a cell at the given
+/// coordinates, which must have at least one adjacent cell
+fn random_step(grid: &Grid, coords: Coords, rng: &mut StdRng) -> (Cell, Coords) {
+    let directions = [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST];
+    loop {
+        let dir = *directions.choose(rng).unwrap();
+        if let Ok(next) = grid.get_next_cell_coords(coords, dir) {
+            return (dir, next);
+        }
+    }
+}
+
+fn get_start_coords<R: Rng>(grid: &Grid, rng: &mut R) -> Coords {
+    let y = rng.random_range(0..grid.height());
+    let x = rng.random_range(0..grid.width());
+    (x, y)
+}
@@ -0,0 +1,106 @@
+use super::grid::Grid;
+use crate::utils::types::Coords;
+use rand::prelude::*;
+
+/// Removes dead ends from a given grid by carving passages into their neighbours, which creates
+/// loops and turns a perfect maze into a braided one
+///
+/// Each dead end is removed with a given probability. A dead end is preferably joined with an
+/// adjacent dead end, so both of them are removed at once. Otherwise, it's joined with a random
+/// adjacent cell it has no passage to.
+pub fn braid(grid: &mut Grid, probability: f64, rng: &mut StdRng) {
+    let mut dead_ends: Vec<Coords> = (0..grid.height())
+        .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+        .filter(|coords| is_dead_end(grid, *coords))
+        .collect();
+    dead_ends.shuffle(rng);
+
+    for coords in dead_ends {
+        // A dead end might have been already removed by joining it with a previous one
+        if !is_dead_end(grid, coords) || !rng.random_bool(probability) {
+            continue;
+        }
+
+        let candidates: Vec<_> = grid
+            .get_adjacent_cells(coords)
+            .into_iter()
+            .filter(|(dir, next)| {
+                !grid.is_carved(coords, *dir) && !grid.get_tunnel(*next).contains(*dir)
+            })
+            .collect();
+        let dead_end_candidates: Vec<_> = candidates
+            .iter()
+            .filter(|(_, next)| is_dead_end(grid, *next))
+            .collect();
+
+        let chosen = match dead_end_candidates.choose(rng) {
+            Some(candidate) => Some(**candidate),
+            None => candidates.choose(rng).copied(),
+        };
+
+        if let Some((dir, _)) = chosen {
+            grid.carve_passage(coords, dir).unwrap();
+        }
+    }
+}
+
+fn is_dead_end(grid: &Grid, coords: Coords) -> bool {
+    grid.get_linked_cells(coords).count() == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::grid::cell::Cell;
+    use crate::maze::solvers::tests::generate_maze;
+
+    fn dead_ends(grid: &Grid) -> usize {
+        (0..grid.height())
+            .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+            .filter(|coords| is_dead_end(grid, *coords))
+            .count()
+    }
+
+    #[test]
+    fn remove_all_dead_ends() {
+        let (mut grid, _) = generate_maze();
+        assert_eq!(4, dead_ends(&grid));
+
+        braid(&mut grid, 1.0, &mut StdRng::seed_from_u64(1));
+        assert_eq!(0, dead_ends(&grid));
+    }
+
+    #[test]
+    fn keep_dead_ends() {
+        let (mut grid, _) = generate_maze();
+        let expected = grid.to_string();
+
+        braid(&mut grid, 0.0, &mut StdRng::seed_from_u64(1));
+        assert_eq!(expected, grid.to_string());
+    }
+
+    #[test]
+    fn prefer_joining_dead_ends() {
+        // Two dead ends next to each other at the ends of a U-shaped corridor
+        let mut grid = Grid::new(2, 2);
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 1), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::NORTH).unwrap();
+
+        braid(&mut grid, 1.0, &mut StdRng::seed_from_u64(1));
+
+        assert!(grid.is_carved((0, 0), Cell::EAST));
+        assert_eq!(0, dead_ends(&grid));
+    }
+
+    #[test]
+    fn deterministic_with_seed() {
+        let (mut grid, _) = generate_maze();
+        let (mut other, _) = generate_maze();
+
+        braid(&mut grid, 0.5, &mut StdRng::seed_from_u64(7));
+        braid(&mut other, 0.5, &mut StdRng::seed_from_u64(7));
+
+        assert_eq!(grid.to_string(), other.to_string());
+    }
+}
@@ -2,7 +2,10 @@ use rand::rngs::StdRng;
 use rand::SeedableRng;
 
 use crate::maze::algorithms::{Algorithm, RecursiveBacktracking};
-use crate::maze::OrthogonalMaze;
+use crate::maze::braid::braid;
+use crate::maze::regions::connect_regions;
+use crate::maze::rooms::{add_doors, open_rooms, place_rooms};
+use crate::maze::{Mask, OrthogonalMaze, Rooms, Topology};
 
 /// An orthogonal maze builder for constructing a maze step by step
 pub struct OrthogonalMazeBuilder {
@@ -10,6 +13,12 @@ pub struct OrthogonalMazeBuilder {
     height: usize,
     algorithm: Box<dyn Algorithm>,
     seed: Option<u64>,
+    braid: f64,
+    mask: Option<Mask>,
+    weave: bool,
+    topology: Topology,
+    rooms: Option<Rooms>,
+    record_events: bool,
 }
 
 impl OrthogonalMazeBuilder {
@@ -20,6 +29,12 @@ impl OrthogonalMazeBuilder {
             height: 10,
             algorithm: Box::new(RecursiveBacktracking),
             seed: None,
+            braid: 0.0,
+            mask: None,
+            weave: false,
+            topology: Topology::Plane,
+            rooms: None,
+            record_events: false,
         }
     }
 
@@ -47,14 +62,186 @@ impl OrthogonalMazeBuilder {
         self
     }
 
+    /// Sets a probability of removing each dead end after a maze is generated and returns itself
+    ///
+    /// A removed dead end is joined with one of its neighbours, preferably another dead end,
+    /// which creates a loop. The value is clamped to the `0.0..=1.0` range, where `0.0` keeps a
+    /// maze perfect (the default) and `1.0` removes all the dead ends. Braiding uses the same
+    /// random number generator as the algorithm, so it's deterministic for a given seed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new().braid(1.0).build();
+    ///
+    /// assert!(maze.is_valid());
+    /// assert!(!maze.is_perfect());
+    /// assert_eq!(0, maze.statistics().dead_ends);
+    /// ```
+    pub const fn braid(mut self, probability: f64) -> Self {
+        self.braid = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets a mask of the cells that make up a maze and returns itself
+    ///
+    /// The mask dimensions take precedence over the [width](OrthogonalMazeBuilder::width) and
+    /// [height](OrthogonalMazeBuilder::height) of a maze.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let mut mask = Mask::new(5, 5);
+    /// mask.disable((2, 2));
+    /// let maze = OrthogonalMazeBuilder::new().mask(mask).build();
+    ///
+    /// assert!(maze.is_perfect());
+    /// assert_eq!(None, maze.neighbours((2, 2)).next());
+    /// ```
+    pub fn mask(mut self, mask: Mask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Sets whether to generate a weave maze and returns itself
+    ///
+    /// In a weave maze, passages can tunnel under the perpendicular corridors, so a maze gets
+    /// over and under crossings. The [Kruskal](crate::maze::Kruskal),
+    /// [RecursiveBacktracking](crate::maze::RecursiveBacktracking) and
+    /// [GrowingTree](crate::maze::GrowingTree) algorithms support it, while the others ignore it.
+    /// Use the [Image](crate::maze::Image) formatter with an
+    /// [inset](crate::maze::Image::inset) to see the crossings, since the other formatters draw a
+    /// cell a tunnel runs under as open on all four sides.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new()
+    ///     .algorithm(Box::new(Kruskal))
+    ///     .weave(true)
+    ///     .build();
+    ///
+    /// assert!(maze.is_perfect());
+    /// ```
+    pub const fn weave(mut self, weave: bool) -> Self {
+        self.weave = weave;
+        self
+    }
+
+    /// Sets a topology of a maze, i.e. which of its borders wrap around to the opposite ones, and
+    /// returns itself
+    ///
+    /// See [Topology] for the algorithms that carve the passages across the wrapped borders. The
+    /// formatters draw such passages as the openings in the borders.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new()
+    ///     .width(6)
+    ///     .height(4)
+    ///     .topology(Topology::Cylinder)
+    ///     .seed(Some(2))
+    ///     .build();
+    ///
+    /// assert!(maze.is_perfect());
+    /// assert!((0..4).any(|y| maze.is_carved((5, y), Cell::EAST)));
+    /// ```
+    pub const fn topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Sets a layout of the rooms to place before a maze is generated and returns itself
+    ///
+    /// The rooms are reserved first, then an algorithm fills the rest of the space with a maze,
+    /// and finally each room is opened up and connected with the maze through its doors, which
+    /// creates loops. Combined with [braid](OrthogonalMazeBuilder::braid), which runs last, it
+    /// makes a roguelike dungeon level. See [Rooms] for the details.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new()
+    ///     .width(30)
+    ///     .height(20)
+    ///     .rooms(Rooms::new(5).width(3..=6).height(3..=4))
+    ///     .braid(1.0)
+    ///     .seed(Some(1))
+    ///     .build();
+    ///
+    /// assert!(maze.is_valid());
+    /// assert_eq!(5, maze.rooms().len());
+    /// assert_eq!(0, maze.statistics().dead_ends);
+    /// ```
+    pub const fn rooms(mut self, rooms: Rooms) -> Self {
+        self.rooms = Some(rooms);
+        self
+    }
+
+    /// Sets whether to record the events of generating a maze and returns itself
+    ///
+    /// The recorded [Event](crate::maze::Event)s, available via
+    /// [OrthogonalMaze::events](crate::maze::OrthogonalMaze::events), replay every step of an
+    /// algorithm, the rooms and the braiding. Recording them doesn't change a maze generated with
+    /// a given seed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new().record_events(true).build();
+    /// let carved = maze
+    ///     .events()
+    ///     .iter()
+    ///     .filter(|event| matches!(event, Event::Carve { .. }))
+    ///     .count();
+    ///
+    /// assert_eq!(maze.passages().count(), carved);
+    /// ```
+    pub const fn record_events(mut self, record: bool) -> Self {
+        self.record_events = record;
+        self
+    }
+
     /// Builds a maze and returns a resulting object of the generated orthogonal maze
     pub fn build(mut self) -> OrthogonalMaze {
-        let mut maze = OrthogonalMaze::new(self.width, self.height);
+        let mut maze = match &self.mask {
+            Some(mask) => OrthogonalMaze::with_mask(mask),
+            None => OrthogonalMaze::new(self.width, self.height),
+        };
         let mut rng = match self.seed {
             Some(val) => StdRng::seed_from_u64(val),
             None => StdRng::from_os_rng(),
         };
-        self.algorithm.generate(maze.get_grid_mut(), &mut rng);
+        if self.weave {
+            maze.get_grid_mut().allow_tunnels();
+        }
+        maze.get_grid_mut().set_topology(self.topology);
+        if self.record_events {
+            maze.get_grid_mut().record_events();
+        }
+        if let Some(layout) = &self.rooms {
+            let mut rooms = place_rooms(maze.get_grid_mut(), layout, &mut rng);
+            self.algorithm.generate(maze.get_grid_mut(), &mut rng);
+            connect_regions(maze.get_grid_mut(), &mut rng);
+            open_rooms(maze.get_grid_mut(), &mut rooms, layout, &mut rng);
+            // The rooms may split the space a mask leaves around them
+            connect_regions(maze.get_grid_mut(), &mut rng);
+            add_doors(maze.get_grid(), &mut rooms);
+            maze.get_grid_mut().set_rooms(rooms);
+        } else {
+            self.algorithm.generate(maze.get_grid_mut(), &mut rng);
+            connect_regions(maze.get_grid_mut(), &mut rng);
+        }
+        if self.braid > 0.0 {
+            braid(maze.get_grid_mut(), self.braid, &mut rng);
+        }
         maze
     }
 }
@@ -74,4 +261,19 @@ mod tests {
         let maze = OrthogonalMazeBuilder::default().build();
         assert!(maze.is_valid());
     }
+
+    #[test]
+    fn braid_change() {
+        assert_eq!(0.0, OrthogonalMazeBuilder::new().braid);
+        assert_eq!(0.3, OrthogonalMazeBuilder::new().braid(0.3).braid);
+        assert_eq!(1.0, OrthogonalMazeBuilder::new().braid(2.0).braid);
+        assert_eq!(0.0, OrthogonalMazeBuilder::new().braid(-1.0).braid);
+    }
+
+    #[test]
+    fn build_braided() {
+        let maze = OrthogonalMazeBuilder::new().braid(1.0).build();
+        assert!(maze.is_valid());
+        assert!(!maze.is_perfect());
+    }
 }
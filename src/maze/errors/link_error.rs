@@ -0,0 +1,44 @@
+use std::fmt;
+
+#[derive(Debug, Clone)]
+/// A link error
+///
+/// Represents a custom error when a passage cannot be carved between two cells of a
+/// [Graph](crate::maze::Graph)
+pub struct LinkError {
+    /// Indices of the cells the passage was attempted between
+    pub cells: (usize, usize),
+    /// A reason why the passage cannot be carved
+    pub reason: String,
+}
+
+/// An implementation of [fmt::Display](fmt::Display) trait
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (cell, other) = self.cells;
+
+        write!(
+            f,
+            "Cannot link cells. Reason: {}. Cells: {} and {}",
+            self.reason, cell, other
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = LinkError {
+            cells: (0, 1),
+            reason: String::from("It's a fake reason"),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Cannot link cells. Reason: It's a fake reason. Cells: 0 and 1"
+        )
+    }
+}
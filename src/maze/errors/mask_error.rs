@@ -0,0 +1,34 @@
+use std::fmt;
+
+#[derive(Debug, Clone)]
+/// A mask load error
+///
+/// Represents a custom error when a mask cannot be loaded from an image
+pub struct MaskError {
+    /// A reason why a mask cannot be loaded
+    pub reason: String,
+}
+
+/// An implementation of [fmt::Display](fmt::Display) trait
+impl fmt::Display for MaskError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cannot load mask. Reason: {}", self.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = MaskError {
+            reason: String::from("It's a fake reason"),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Cannot load mask. Reason: It's a fake reason"
+        )
+    }
+}
@@ -1,5 +1,13 @@
+mod link_error;
+mod mask_error;
+mod mutation_error;
+mod parse_error;
 mod save_error;
 mod transit_error;
 
+pub use link_error::LinkError;
+pub use mask_error::MaskError;
+pub use mutation_error::MutationError;
+pub use parse_error::MazeParseError;
 pub use save_error::MazeSaveError;
 pub use transit_error::TransitError;
@@ -0,0 +1,34 @@
+use std::fmt;
+
+#[derive(Debug, Clone)]
+/// A maze mutation error
+///
+/// Represents a custom error when a maze cannot be mutated, e.g. it's not perfect
+pub struct MutationError {
+    /// A reason why a maze cannot be mutated
+    pub reason: String,
+}
+
+/// An implementation of [fmt::Display](fmt::Display) trait
+impl fmt::Display for MutationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cannot mutate maze. Reason: {}", self.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = MutationError {
+            reason: String::from("It's a fake reason"),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Cannot mutate maze. Reason: It's a fake reason"
+        )
+    }
+}
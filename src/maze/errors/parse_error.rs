@@ -0,0 +1,44 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A maze parse error
+///
+/// Represents a custom error when a text cannot be parsed into a maze
+pub struct MazeParseError {
+    /// A line of the malformed input, starting from 1
+    pub line: usize,
+    /// A column of the malformed input, starting from 1
+    pub column: usize,
+    /// A reason why the input cannot be parsed
+    pub reason: String,
+}
+
+/// An implementation of [fmt::Display](fmt::Display) trait
+impl fmt::Display for MazeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Cannot parse maze at line {}, column {}. Reason: {}",
+            self.line, self.column, self.reason
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = MazeParseError {
+            line: 2,
+            column: 5,
+            reason: String::from("It's a fake reason"),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Cannot parse maze at line 2, column 5. Reason: It's a fake reason"
+        )
+    }
+}
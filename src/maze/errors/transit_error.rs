@@ -1,12 +1,19 @@
 use crate::utils::types::Coords;
 use std::fmt;
 
+/// A transit error
+///
+/// Represents a custom error when a move from a cell cannot be done, e.g. because it leads out of
+/// the grid or into a disabled cell, or a passage would cut through a tunnel
 #[derive(Debug, Clone)]
 pub struct TransitError {
+    /// Coordinates of a cell the move was attempted from
     pub coords: Coords,
+    /// A reason why the move cannot be done
     pub reason: String,
 }
 
+/// An implementation of [fmt::Display](fmt::Display) trait
 impl fmt::Display for TransitError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let (x, y) = self.coords;
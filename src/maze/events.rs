@@ -0,0 +1,158 @@
+use super::grid::{cell::Cell, Grid};
+use crate::utils::types::Coords;
+use std::collections::HashSet;
+use std::slice;
+
+/// A step of generating a maze, recorded to replay the generation process, e.g. to animate it
+///
+/// A [Grid](crate::maze::Grid) records the events of the passages, tunnels and walls on its own,
+/// while every algorithm emits the events of its own steps with [Grid::emit](crate::maze::Grid::emit),
+/// such as the current cell or the cells it keeps track of. The events change nothing in a grid
+/// and use no random numbers, so a maze generated with a given seed is the same either way.
+///
+/// Replaying the [Carve](Event::Carve), [Tunnel](Event::Tunnel) and [Wall](Event::Wall) events
+/// in order on an empty grid of the same size rebuilds a maze exactly.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// An algorithm moves to a cell, which becomes its current one
+    Visit(Coords),
+
+    /// A passage is carved out of a cell in a given direction
+    Carve {
+        /// The coordinates of the cell the passage is carved out of
+        from: Coords,
+        /// The direction of the passage
+        dir: Cell,
+    },
+
+    /// A tunnel is carved out of a cell in a given direction under the adjacent cell
+    Tunnel {
+        /// The coordinates of the cell the tunnel is carved out of
+        from: Coords,
+        /// The direction of the tunnel
+        dir: Cell,
+    },
+
+    /// A wall is built between a cell and the adjacent cell in a given direction
+    Wall {
+        /// The coordinates of the cell the wall is built next to
+        from: Coords,
+        /// The direction of the wall
+        dir: Cell,
+    },
+
+    /// A cell joins a set an algorithm keeps track of, e.g. its stack, list of active cells,
+    /// frontier or a run of cells
+    Mark(Coords),
+
+    /// A cell leaves the set an algorithm keeps track of, since the algorithm is done with it
+    Backtrack(Coords),
+}
+
+/// An iterator replaying the [Event]s recorded on a grid, one at a time, onto a blank copy of it
+///
+/// The copy has the same size, disabled cells, weave and topology as the original grid, but no
+/// passages at first. Each call to `next` applies an event to the copy and returns it, so between
+/// the calls the [grid](Replay::grid) shows a maze as it was at that step of the generation, along
+/// with the [current](Replay::current) cell of an algorithm and the cells it keeps track of.
+///
+/// # Example
+/// ```rust
+/// use knossos::maze::*;
+///
+/// let maze = OrthogonalMazeBuilder::new()
+///     .width(5)
+///     .height(5)
+///     .record_events(true)
+///     .build();
+///
+/// let mut replay = maze.replay();
+/// while replay.by_ref().take(10).count() == 10 {
+///     assert!(replay.current().is_some());
+/// }
+/// assert_eq!(maze.to_string(), replay.grid().to_string());
+/// ```
+pub struct Replay<'a> {
+    events: slice::Iter<'a, Event>,
+    grid: Grid,
+    current: Option<Coords>,
+    frontier: HashSet<Coords>,
+}
+
+impl<'a> Replay<'a> {
+    /// Returns a new replay of the events recorded on a given grid
+    pub(crate) fn new(grid: &'a Grid) -> Replay<'a> {
+        Replay {
+            events: grid.events().iter(),
+            grid: grid.blank(),
+            current: None,
+            frontier: HashSet::new(),
+        }
+    }
+
+    /// Returns the grid with all the events replayed so far
+    pub const fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Returns the coordinates of the last cell an algorithm visited, if any
+    pub const fn current(&self) -> Option<Coords> {
+        self.current
+    }
+
+    /// Returns the coordinates of the cells an algorithm keeps track of
+    pub const fn frontier(&self) -> &HashSet<Coords> {
+        &self.frontier
+    }
+}
+
+impl Iterator for Replay<'_> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = *self.events.next()?;
+
+        // The events were recorded on the same grid, so replaying them always succeeds
+        match event {
+            Event::Visit(coords) => self.current = Some(coords),
+            Event::Carve { from, dir } => _ = self.grid.carve_passage(from, dir),
+            Event::Tunnel { from, dir } => _ = self.grid.carve_tunnel(from, dir),
+            Event::Wall { from, dir } => _ = self.grid.build_wall(from, dir),
+            Event::Mark(coords) => _ = self.frontier.insert(coords),
+            Event::Backtrack(coords) => _ = self.frontier.remove(&coords),
+        }
+
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay_onto_blank_copy() {
+        let mut grid = Grid::new(3, 1);
+        grid.disable_cell((2, 0));
+        grid.record_events();
+        grid.emit(Event::Visit((0, 0)));
+        grid.emit(Event::Mark((1, 0)));
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.emit(Event::Backtrack((1, 0)));
+
+        let mut replay = Replay::new(&grid);
+        assert!(!replay.grid().is_cell_enabled((2, 0)));
+        assert_eq!(None, replay.current());
+
+        assert_eq!(Some(Event::Visit((0, 0))), replay.next());
+        assert_eq!(Some(Event::Mark((1, 0))), replay.next());
+        assert_eq!(Some((0, 0)), replay.current());
+        assert!(replay.frontier().contains(&(1, 0)));
+        assert!(!replay.grid().is_carved((0, 0), Cell::EAST));
+
+        assert_eq!(2, replay.by_ref().count());
+        assert!(replay.grid().is_carved((0, 0), Cell::EAST));
+        assert!(replay.frontier().is_empty());
+        assert_eq!(None, replay.next());
+    }
+}
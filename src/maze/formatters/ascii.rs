@@ -1,8 +1,14 @@
 use crate::maze::grid::cell::Cell;
 use crate::maze::{formatters::Formatter, grid::Grid};
+use crate::utils::types::Coords;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
+use std::marker::PhantomData;
 
-use super::StringWrapper;
+use super::{is_wall, StringWrapper};
+
+mod hex;
+mod layered;
 
 /// A formatter to emit the maze as ASCII with narrow passages
 ///
@@ -34,83 +40,341 @@ pub struct AsciiNarrow;
 /// ```
 pub struct AsciiBroad;
 
+/// An ASCII formatter that marks a path through the maze along with its start and goal cells
+///
+/// It wraps either an [AsciiNarrow] or an [AsciiBroad] formatter and is created by their
+/// `with_path` methods. Every cell of the path gets a marker character, and so do the passages
+/// between two consecutive cells of the path wherever the wrapped formatter has room for it. With
+/// narrow passages, only the passages to the East can be marked, and a marker is drawn only where
+/// the floor is open, since a cell with a wall to the South has no room left for it. The broad
+/// passages have room to mark every cell and passage of a path.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use knossos::maze::*;
+///
+/// let maze = OrthogonalMazeBuilder::new().width(4).height(4).build();
+/// let path = maze.solve(BreadthFirstSearch, (0, 0), (3, 3)).unwrap();
+///
+/// let formatter = AsciiBroad.with_path(path).start((0, 0)).goal((3, 3));
+/// ```
+///
+/// ```no_test
+/// +---+---+---+---+
+/// | S |           |
+/// + * +---+---+   +
+/// | * * * * * |   |
+/// +---+   + * +   +
+/// | * * * * * |   |
+/// + * +---+---+---+
+/// | * * * * * * G |
+/// +---+---+---+---+
+/// ```
+pub struct AsciiPath<F> {
+    formatter: PhantomData<F>,
+    path: Vec<Coords>,
+    marker: char,
+    start: Option<Coords>,
+    start_marker: char,
+    goal: Option<Coords>,
+    goal_marker: char,
+}
+
+impl AsciiNarrow {
+    /// Returns a new instance of a narrow ASCII formatter marking a given path through the maze
+    pub const fn with_path(self, path: Vec<Coords>) -> AsciiPath<AsciiNarrow> {
+        AsciiPath::new(path)
+    }
+}
+
+impl AsciiBroad {
+    /// Returns a new instance of a broad ASCII formatter marking a given path through the maze
+    pub const fn with_path(self, path: Vec<Coords>) -> AsciiPath<AsciiBroad> {
+        AsciiPath::new(path)
+    }
+}
+
+impl<F> AsciiPath<F> {
+    const fn new(path: Vec<Coords>) -> AsciiPath<F> {
+        AsciiPath {
+            formatter: PhantomData,
+            path,
+            marker: '*',
+            start: None,
+            start_marker: 'S',
+            goal: None,
+            goal_marker: 'G',
+        }
+    }
+
+    /// Sets a path marker character and returns itself
+    pub const fn marker(mut self, marker: char) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    /// Sets a cell to mark as the start and returns itself
+    pub const fn start(mut self, coords: Coords) -> Self {
+        self.start = Some(coords);
+        self
+    }
+
+    /// Sets a start marker character and returns itself
+    pub const fn start_marker(mut self, marker: char) -> Self {
+        self.start_marker = marker;
+        self
+    }
+
+    /// Sets a cell to mark as the goal and returns itself
+    pub const fn goal(mut self, coords: Coords) -> Self {
+        self.goal = Some(coords);
+        self
+    }
+
+    /// Sets a goal marker character and returns itself
+    pub const fn goal_marker(mut self, marker: char) -> Self {
+        self.goal_marker = marker;
+        self
+    }
+
+    fn overlay(&self) -> Overlay {
+        let mut overlay = Overlay {
+            marker: self.marker,
+            ..Overlay::default()
+        };
+
+        for coords in self.path.iter() {
+            overlay.cells.insert(*coords, self.marker);
+        }
+        // Only the passages actually linking the cells are marked, so the steps through a tunnel
+        // or across a wrapped border are marked too, while the gaps in a path are not
+        for pair in self.path.windows(2) {
+            overlay
+                .passages
+                .insert((pair[0].min(pair[1]), pair[0].max(pair[1])));
+        }
+        if let Some(coords) = self.start {
+            overlay.cells.insert(coords, self.start_marker);
+        }
+        if let Some(coords) = self.goal {
+            overlay.cells.insert(coords, self.goal_marker);
+        }
+
+        overlay
+    }
+}
+
+/// Characters to draw over the cells and passages of a maze
+#[derive(Default)]
+struct Overlay {
+    marker: char,
+    cells: HashMap<Coords, char>,
+    passages: HashSet<(Coords, Coords)>,
+}
+
+impl Overlay {
+    fn cell(&self, coords: Coords) -> Option<char> {
+        self.cells.get(&coords).copied()
+    }
+
+    fn passage(&self, from: Coords, to: Coords) -> Option<char> {
+        self.passages
+            .contains(&(from.min(to), from.max(to)))
+            .then_some(self.marker)
+    }
+}
+
 /// An implementation of a narrow ASCII formatter
 impl Formatter<StringWrapper> for AsciiNarrow {
     /// Converts a given grid into ASCII characters and returns an [StringWrapper] over that image
     fn format(&self, grid: &Grid) -> StringWrapper {
-        let mut result = String::new();
+        format_narrow(grid, &Overlay::default())
+    }
+}
+
+/// An implementation of a narrow ASCII formatter with a path marked
+impl Formatter<StringWrapper> for AsciiPath<AsciiNarrow> {
+    /// Converts a given grid into ASCII characters and returns an [StringWrapper] over that image
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        format_narrow(grid, &self.overlay())
+    }
+}
+
+/// An implementation of an broad ASCII formatter
+impl Formatter<StringWrapper> for AsciiBroad {
+    /// Converts a given grid into ASCII characters and returns an [StringWrapper] over that image
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        format_broad(grid, &Overlay::default())
+    }
+}
 
-        let top_border = "_".repeat(grid.width() * 2 - 1);
+/// An implementation of an broad ASCII formatter with a path marked
+impl Formatter<StringWrapper> for AsciiPath<AsciiBroad> {
+    /// Converts a given grid into ASCII characters and returns an [StringWrapper] over that image
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        format_broad(grid, &self.overlay())
+    }
+}
 
-        writeln!(result, " {} ", top_border).unwrap();
+fn format_narrow(grid: &Grid, overlay: &Overlay) -> StringWrapper {
+    let mut result = String::from(" ");
 
-        for y in 0..grid.height() {
-            write!(result, "|").unwrap();
+    // The top border runs over the enabled cells of the first row only
+    for x in 0..grid.width() {
+        let is_top_wall = is_wall(grid, (x, 0), Cell::NORTH);
+        result.push(if is_top_wall { '_' } else { ' ' });
 
-            for x in 0..grid.width() {
-                if grid.is_carved((x, y), Cell::SOUTH) {
-                    write!(result, " ").unwrap();
-                } else {
-                    write!(result, "_").unwrap();
-                }
+        if x + 1 < grid.width() {
+            let is_joint_wall = is_top_wall && is_wall(grid, (x + 1, 0), Cell::NORTH);
+            result.push(if is_joint_wall { '_' } else { ' ' });
+        }
+    }
+    writeln!(result, " ").unwrap();
 
-                if grid.is_carved((x, y), Cell::EAST) {
-                    if grid.is_carved((x, y), Cell::SOUTH)
-                        || grid.is_carved((x + 1, y), Cell::SOUTH)
-                    {
-                        write!(result, " ").unwrap();
-                    } else {
-                        write!(result, "_").unwrap();
-                    }
-                } else {
-                    write!(result, "|").unwrap();
-                }
+    for y in 0..grid.height() {
+        let west_border = if is_wall(grid, (0, y), Cell::WEST) {
+            '|'
+        } else {
+            ' '
+        };
+        result.push(west_border);
+
+        for x in 0..grid.width() {
+            let is_floor = is_wall(grid, (x, y), Cell::SOUTH);
+
+            // A marker takes the place of the floor, so that every cell of a path stays visible
+            if let Some(marker) = marker(grid, overlay, (x, y)) {
+                write!(result, "{}", marker).unwrap();
+            } else if is_floor {
+                write!(result, "_").unwrap();
+            } else {
+                write!(result, " ").unwrap();
             }
 
-            writeln!(result).unwrap();
+            if is_wall(grid, (x, y), Cell::EAST) {
+                write!(result, "|").unwrap();
+            } else if is_floor && x + 1 < grid.width() && is_wall(grid, (x + 1, y), Cell::SOUTH) {
+                write!(result, "_").unwrap();
+            } else if let Some(marker) = passage_marker(grid, overlay, (x, y), Cell::EAST) {
+                write!(result, "{}", marker).unwrap();
+            } else {
+                write!(result, " ").unwrap();
+            }
         }
 
-        StringWrapper(result)
+        writeln!(result).unwrap();
     }
+
+    StringWrapper(result)
 }
 
-/// An implementation of an broad ASCII formatter
-impl Formatter<StringWrapper> for AsciiBroad {
-    /// Converts a given grid into ASCII characters and returns an [StringWrapper] over that image
-    fn format(&self, grid: &Grid) -> StringWrapper {
-        let mut output = format!("+{}\n", "---+".to_string().repeat(grid.width()));
-
-        for y in 0..grid.height() {
-            let mut top_line = "|".to_string();
-            let mut bottom_line = "+".to_string();
-
-            for x in 0..grid.width() {
-                top_line.push_str("   ");
-                let east_boundary = if grid.is_carved((x, y), Cell::EAST) {
-                    " "
-                } else {
-                    "|"
-                };
-                top_line.push_str(east_boundary);
-
-                let south_boundary = if grid.is_carved((x, y), Cell::SOUTH) {
-                    "   "
-                } else {
-                    "---"
-                };
-                bottom_line.push_str(south_boundary);
-                bottom_line.push('+');
-
-                if x == grid.width() - 1 {
-                    output.push_str(&top_line);
-                    output.push('\n');
-                    output.push_str(&bottom_line);
-                    output.push('\n');
+fn format_broad(grid: &Grid, overlay: &Overlay) -> StringWrapper {
+    let mut output = String::new();
+
+    output.push(corner(grid, (0, 0)));
+    for x in 0..grid.width() {
+        let top_wall = if is_wall(grid, (x, 0), Cell::NORTH) {
+            "---"
+        } else {
+            "   "
+        };
+        output.push_str(top_wall);
+        output.push(corner(grid, (x + 1, 0)));
+    }
+    output.push('\n');
+
+    for y in 0..grid.height() {
+        let mut top_line = String::new();
+        let mut bottom_line = String::new();
+
+        top_line.push(if is_wall(grid, (0, y), Cell::WEST) {
+            '|'
+        } else {
+            ' '
+        });
+        bottom_line.push(corner(grid, (0, y + 1)));
+
+        for x in 0..grid.width() {
+            match marker(grid, overlay, (x, y)) {
+                Some(marker) => write!(top_line, " {} ", marker).unwrap(),
+                None => top_line.push_str("   "),
+            }
+            let east_boundary = if is_wall(grid, (x, y), Cell::EAST) {
+                '|'
+            } else {
+                passage_marker(grid, overlay, (x, y), Cell::EAST).unwrap_or(' ')
+            };
+            top_line.push(east_boundary);
+
+            if is_wall(grid, (x, y), Cell::SOUTH) {
+                bottom_line.push_str("---");
+            } else {
+                match passage_marker(grid, overlay, (x, y), Cell::SOUTH) {
+                    Some(marker) => write!(bottom_line, " {} ", marker).unwrap(),
+                    None => bottom_line.push_str("   "),
                 }
             }
+            bottom_line.push(corner(grid, (x + 1, y + 1)));
         }
 
-        StringWrapper(output)
+        output.push_str(&top_line);
+        output.push('\n');
+        output.push_str(&bottom_line);
+        output.push('\n');
+    }
+
+    StringWrapper(output)
+}
+
+/// Returns a marker to draw over an enabled cell at the given coordinates, if any
+fn marker(grid: &Grid, overlay: &Overlay, coords: Coords) -> Option<char> {
+    overlay
+        .cell(coords)
+        .filter(|_| grid.is_cell_enabled(coords))
+}
+
+/// Returns a marker to draw over a passage carved out of a cell at the given coordinates in a
+/// given direction, if any
+///
+/// A passage tunneling under a cell opens both sides of it, so the marker of the path through the
+/// tunnel is drawn on both sides as well.
+fn passage_marker(grid: &Grid, overlay: &Overlay, coords: Coords, direction: Cell) -> Option<char> {
+    let next = grid.get_next_cell_coords(coords, direction).ok()?;
+    let forward = grid
+        .get_linked_cell_coords(coords, direction)
+        .and_then(|to| overlay.passage(coords, to));
+    let backward = grid
+        .get_linked_cell_coords(next, direction.opposite())
+        .and_then(|from| overlay.passage(from, next));
+
+    forward.or(backward)
+}
+
+/// Returns a character to draw at a given corner between cells, which is a `+` for the corners
+/// touching at least one enabled cell and a blank otherwise
+///
+/// The corners inside the grid with no walls meeting at them, like the ones in the middle of an
+/// open room, are blank as well.
+fn corner(grid: &Grid, (cx, cy): Coords) -> char {
+    let xs = [cx.checked_sub(1), Some(cx)];
+    let ys = [cy.checked_sub(1), Some(cy)];
+    let touches_enabled_cell = ys
+        .iter()
+        .flatten()
+        .any(|y| xs.iter().flatten().any(|x| grid.is_cell_enabled((*x, *y))));
+
+    let is_inner = cx > 0 && cy > 0 && cx < grid.width() && cy < grid.height();
+    let is_open = is_inner
+        && !is_wall(grid, (cx - 1, cy - 1), Cell::EAST)
+        && !is_wall(grid, (cx - 1, cy - 1), Cell::SOUTH)
+        && !is_wall(grid, (cx, cy), Cell::WEST)
+        && !is_wall(grid, (cx, cy), Cell::NORTH);
+
+    if touches_enabled_cell && !is_open {
+        '+'
+    } else {
+        ' '
     }
 }
 
@@ -154,6 +418,140 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn path_params_change() {
+        let formatter = AsciiNarrow
+            .with_path(vec![(0, 0)])
+            .marker('.')
+            .start((0, 0))
+            .start_marker('A')
+            .goal((1, 1))
+            .goal_marker('B');
+
+        assert_eq!(vec![(0, 0)], formatter.path);
+        assert_eq!('.', formatter.marker);
+        assert_eq!(Some((0, 0)), formatter.start);
+        assert_eq!('A', formatter.start_marker);
+        assert_eq!(Some((1, 1)), formatter.goal);
+        assert_eq!('B', formatter.goal_marker);
+    }
+
+    #[test]
+    fn format_narrow_with_path() {
+        let mut expected = String::new();
+        expected.push_str(" _______ \n");
+        expected.push_str("|S|___  |\n");
+        expected.push_str("|*** _| |\n");
+        expected.push_str("|***____|\n");
+        expected.push_str("|*_*_*_G|\n");
+
+        let formatter = AsciiNarrow.with_path(path()).start((0, 0)).goal((3, 3));
+        let grid = generate_maze();
+        let actual = formatter.format(&grid).0;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn format_broad_with_path() {
+        let mut expected = String::new();
+        expected.push_str("+---+---+---+---+\n");
+        expected.push_str("| S |           |\n");
+        expected.push_str("+ * +---+---+   +\n");
+        expected.push_str("| * * *     |   |\n");
+        expected.push_str("+---+ * +---+   +\n");
+        expected.push_str("| * * *         |\n");
+        expected.push_str("+ * +---+---+---+\n");
+        expected.push_str("| * * * * * * G |\n");
+        expected.push_str("+---+---+---+---+\n");
+
+        let formatter = AsciiBroad.with_path(path()).start((0, 0)).goal((3, 3));
+        let grid = generate_maze();
+        let actual = formatter.format(&grid).0;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn format_broad_with_custom_markers() {
+        let formatter = AsciiBroad
+            .with_path(vec![(0, 0), (0, 1), (1, 1)])
+            .marker('.')
+            .start((0, 0))
+            .start_marker('>');
+        let grid = generate_maze();
+        let actual = formatter.format(&grid).0;
+
+        assert!(actual.starts_with("+---+---+---+---+\n| > |"));
+        assert!(actual.contains("| . . . "));
+    }
+
+    fn path() -> Vec<Coords> {
+        vec![
+            (0, 0),
+            (0, 1),
+            (1, 1),
+            (1, 2),
+            (0, 2),
+            (0, 3),
+            (1, 3),
+            (2, 3),
+            (3, 3),
+        ]
+    }
+
+    #[test]
+    fn format_broad_open_room() {
+        let mut expected = String::new();
+        expected.push_str("+---+---+---+\n");
+        expected.push_str("|           |\n");
+        expected.push_str("+           +\n");
+        expected.push_str("|           |\n");
+        expected.push_str("+---+---+---+\n");
+
+        let actual = AsciiBroad.format(&generate_room()).0;
+
+        assert_eq!(actual, expected);
+    }
+
+    fn generate_room() -> Grid {
+        let mut grid = Grid::new(3, 2);
+        for x in 0..3 {
+            grid.carve_passage((x, 0), Cell::SOUTH).unwrap();
+        }
+        for y in 0..2 {
+            grid.carve_passage((0, y), Cell::EAST).unwrap();
+            grid.carve_passage((1, y), Cell::EAST).unwrap();
+        }
+
+        grid
+    }
+
+    #[test]
+    fn format_tunnel() {
+        let mut grid = Grid::new(3, 3);
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_tunnel((0, 1), Cell::EAST).unwrap();
+
+        // Both sides of a cell a tunnel runs under are open, along with the path through it
+        let mut expected = String::new();
+        expected.push_str("+---+---+---+\n");
+        expected.push_str("|   |   |   |\n");
+        expected.push_str("+---+   +---+\n");
+        expected.push_str("| * *   * * |\n");
+        expected.push_str("+---+   +---+\n");
+        expected.push_str("|   |   |   |\n");
+        expected.push_str("+---+---+---+\n");
+
+        let formatter = AsciiBroad.with_path(vec![(0, 1), (2, 1)]);
+        assert_eq!(expected, formatter.format(&grid).0);
+
+        let formatter = AsciiNarrow.with_path(vec![(0, 1), (2, 1)]);
+        let expected = " _____ \n|_| |_|\n|** **|\n|_|_|_|\n";
+        assert_eq!(expected, formatter.format(&grid).0);
+    }
+
     fn generate_maze() -> Grid {
         let mut grid = Grid::new(4, 4);
 
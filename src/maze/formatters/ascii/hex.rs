@@ -0,0 +1,88 @@
+use super::AsciiNarrow;
+use crate::maze::formatters::{Formatter, StringWrapper};
+use crate::maze::grid::hex::{HexCell, HexGrid};
+
+/// An implementation of a formatter for hexagonal mazes
+///
+/// Every hexagon is drawn with `__` on its top and bottom and `/` and `\` on its sides, where the
+/// adjacent hexagons share their sides.
+///
+/// # Example:
+///
+/// ```no_test
+///  __    __
+/// /  \__/  \
+/// \      __/
+/// /  \__/  \
+/// \__/  \__/
+///    \__/
+/// ```
+impl Formatter<StringWrapper, HexGrid> for AsciiNarrow {
+    /// Converts a given hexagonal grid into the ASCII text and returns a [StringWrapper] over it
+    fn format(&self, grid: &HexGrid) -> StringWrapper {
+        // Every odd column is shifted down by half a cell, i.e. a line of text
+        let lines = grid.height() * 2 + 1 + usize::from(grid.width() > 1);
+        let mut chars = vec![vec![' '; grid.width() * 3 + 1]; lines];
+
+        for coords in grid.cells() {
+            let (x, y) = coords;
+            let (left, top) = (x * 3, y * 2 + x % 2);
+            let walls = [
+                (HexCell::NORTH, (left + 1, top), "__"),
+                (HexCell::NORTH_WEST, (left, top + 1), "/"),
+                (HexCell::NORTH_EAST, (left + 3, top + 1), "\\"),
+                (HexCell::SOUTH_WEST, (left, top + 2), "\\"),
+                (HexCell::SOUTH, (left + 1, top + 2), "__"),
+                (HexCell::SOUTH_EAST, (left + 3, top + 2), "/"),
+            ];
+
+            for (dir, (column, line), wall) in walls {
+                if !grid.is_carved(coords, dir) {
+                    for (i, char) in wall.chars().enumerate() {
+                        chars[line][column + i] = char;
+                    }
+                }
+            }
+        }
+
+        let mut result = String::new();
+        for line in chars {
+            result.extend(line);
+            result.push('\n');
+        }
+
+        StringWrapper(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_hex_grid() {
+        let mut grid = HexGrid::new(3, 2);
+        grid.graph_mut().link(0, 3).unwrap();
+        grid.graph_mut().link(0, 1).unwrap();
+        grid.graph_mut().link(1, 2).unwrap();
+
+        let expected = concat!(
+            " __    __ \n",
+            "/  \\__/  \\\n",
+            "\\      __/\n",
+            "/  \\__/  \\\n",
+            "\\__/  \\__/\n",
+            "   \\__/   \n",
+        );
+        assert_eq!(expected, AsciiNarrow.format(&grid).into_inner());
+    }
+
+    #[test]
+    fn format_single_column() {
+        let grid = HexGrid::new(1, 1);
+        assert_eq!(
+            " __ \n/  \\\n\\__/\n",
+            AsciiNarrow.format(&grid).into_inner()
+        );
+    }
+}
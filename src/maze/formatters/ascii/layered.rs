@@ -0,0 +1,96 @@
+use super::{format_broad, format_narrow, AsciiBroad, AsciiNarrow, Overlay};
+use crate::maze::formatters::{stairs_marker, Formatter, StringWrapper};
+use crate::maze::grid::{layered::LayeredGrid, Grid};
+
+/// An implementation of a narrow ASCII formatter for layered mazes
+///
+/// The levels are drawn side by side from the ground one up, with the stairs marked by `U` when
+/// they lead up, `D` when they lead down and `X` when they lead both ways.
+///
+/// # Example:
+///
+/// ```no_test
+///  _______     _______
+/// |U|U  |U|   |D_D|__D|
+/// |U____|U|   |D_____D|
+/// ```
+impl Formatter<StringWrapper, LayeredGrid> for AsciiNarrow {
+    /// Converts a given layered grid into ASCII characters and returns a [StringWrapper] over it
+    fn format(&self, grid: &LayeredGrid) -> StringWrapper {
+        format_levels(grid, format_narrow)
+    }
+}
+
+/// An implementation of a broad ASCII formatter for layered mazes
+///
+/// The levels are drawn side by side from the ground one up, with the stairs marked by `U` when
+/// they lead up, `D` when they lead down and `X` when they lead both ways.
+impl Formatter<StringWrapper, LayeredGrid> for AsciiBroad {
+    /// Converts a given layered grid into ASCII characters and returns a [StringWrapper] over it
+    fn format(&self, grid: &LayeredGrid) -> StringWrapper {
+        format_levels(grid, format_broad)
+    }
+}
+
+/// Formats every level of a grid with a given function and joins the results line by line
+fn format_levels(
+    grid: &LayeredGrid,
+    format: fn(&Grid, &Overlay) -> StringWrapper,
+) -> StringWrapper {
+    let levels: Vec<String> = (0..grid.levels())
+        .filter_map(|z| {
+            let level = grid.level(z)?;
+            let mut overlay = Overlay::default();
+            for (coords, stairs) in grid.stairs(z) {
+                overlay
+                    .cells
+                    .extend(stairs_marker(stairs).map(|m| (coords, m)));
+            }
+            Some(format(&level, &overlay).into_inner())
+        })
+        .collect();
+
+    let mut lines: Vec<_> = levels.iter().map(|level| level.lines()).collect();
+    let mut result = String::new();
+    // All the levels have the same size, so they run out of lines at once
+    while let Some(line) = lines
+        .iter_mut()
+        .map(Iterator::next)
+        .collect::<Option<Vec<_>>>()
+        .filter(|line| !line.is_empty())
+    {
+        result.push_str(&line.join("   "));
+        result.push('\n');
+    }
+
+    StringWrapper(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_grid() -> LayeredGrid {
+        let mut grid = LayeredGrid::new(2, 1, 2);
+        grid.graph_mut().link(0, 1).unwrap();
+        grid.graph_mut().link(1, 3).unwrap();
+        grid.graph_mut().link(3, 2).unwrap();
+        grid
+    }
+
+    #[test]
+    fn format_narrow_levels() {
+        let expected = concat!(" ___     ___ \n", "|__U|   |__D|\n");
+        assert_eq!(expected, AsciiNarrow.format(&generate_grid()).into_inner());
+    }
+
+    #[test]
+    fn format_broad_levels() {
+        let expected = concat!(
+            "+---+---+   +---+---+\n",
+            "|     U |   |     D |\n",
+            "+---+---+   +---+---+\n",
+        );
+        assert_eq!(expected, AsciiBroad.format(&generate_grid()).into_inner());
+    }
+}
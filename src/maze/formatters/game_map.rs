@@ -7,7 +7,9 @@ use crate::utils::types::Coords;
 use crate::maze::grid::{Grid, cell::Cell};
 use crate::maze::formatters::Formatter;
 
-use super::StringWrapper;
+use super::{is_open, StringWrapper};
+
+mod layered;
 
 pub trait ExtraState {}
 pub struct NoStartGoal;
@@ -181,8 +183,8 @@ impl GameMap<WithStartGoal> {
             .nth(0)
             .unwrap(); // the smallest grid with a single cell formatted into a map has 3 available positions for a goal
 
-        let start_idx = srow * rows + scol;
-        let goal_idx = grow * rows + gcol;
+        let start_idx = srow * cols + scol;
+        let goal_idx = grow * cols + gcol;
         (start_idx, goal_idx)
     }
 
@@ -205,7 +207,7 @@ impl GameMap<WithStartGoal> {
                 }
 
                 let adjacent_passages_count = iter_neighbors((row, col), cols, rows)
-                    .filter(move |(ny, nx)| map[ny * rows + nx] == self.state.passage)
+                    .filter(move |(ny, nx)| map[ny * cols + nx] == self.state.passage)
                     .count();
 
                 if adjacent_passages_count == 0 {
@@ -239,13 +241,21 @@ impl Formatter<StringWrapper> for GameMap<NoStartGoal> {
         let map_cols = grid.width() * span + 1;
 
         // Add the north wall
-        for _ in 0..map_cols {
-            map.push(self.state.wall);
+        for x in 0..map_cols {
+            if is_north_border_open(x, span, grid) {
+                map.push(self.state.passage);
+            } else {
+                map.push(self.state.wall);
+            }
         }
 
         for y in 0..map_rows - 1 {
             // Add the west wall
-            map.push(self.state.wall);
+            if is_west_border_open(y, span, grid) {
+                map.push(self.state.passage);
+            } else {
+                map.push(self.state.wall);
+            }
 
             for x in 0..map_cols - 1 {
                 // X coordinate of a cell in the grid
@@ -259,16 +269,23 @@ impl Formatter<StringWrapper> for GameMap<NoStartGoal> {
                 let is_last_col = (x as f64 + 1.0) / span as f64 == cx as f64 + 1.0;
 
                 match (is_last_row, is_last_col) {
-                    (false, false) => map.push(self.state.passage),
+                    (false, false) => {
+                        // The cells disabled by a mask are solid walls
+                        if grid.is_cell_enabled((cx, cy)) {
+                            map.push(self.state.passage);
+                        } else {
+                            map.push(self.state.wall);
+                        }
+                    }
                     (false, true) => {
-                        if grid.is_carved((cx, cy), Cell::EAST) {
+                        if is_open(grid, (cx, cy), Cell::EAST) {
                             map.push(self.state.passage);
                         } else {
                             map.push(self.state.wall);
                         }
                     }
                     (true, false) => {
-                        if grid.is_carved((cx, cy), Cell::SOUTH) {
+                        if is_open(grid, (cx, cy), Cell::SOUTH) {
                             map.push(self.state.passage);
                         } else {
                             map.push(self.state.wall);
@@ -308,13 +325,21 @@ impl Formatter<StringWrapper> for GameMap<WithStartGoal> {
         let map_cols = grid.width() * span + 1;
 
         // Add the north wall
-        for _ in 0..map_cols {
-            map.push(self.state.wall);
+        for x in 0..map_cols {
+            if is_north_border_open(x, span, grid) {
+                map.push(self.state.passage);
+            } else {
+                map.push(self.state.wall);
+            }
         }
 
         for y in 0..map_rows - 1 {
             // Add the west wall
-            map.push(self.state.wall);
+            if is_west_border_open(y, span, grid) {
+                map.push(self.state.passage);
+            } else {
+                map.push(self.state.wall);
+            }
 
             for x in 0..map_cols - 1 {
                 // X coordinate of a cell in the grid
@@ -328,16 +353,23 @@ impl Formatter<StringWrapper> for GameMap<WithStartGoal> {
                 let is_last_col = (x as f64 + 1.0) / span as f64 == cx as f64 + 1.0;
 
                 match (is_last_row, is_last_col) {
-                    (false, false) => map.push(self.state.passage),
+                    (false, false) => {
+                        // The cells disabled by a mask are solid walls
+                        if grid.is_cell_enabled((cx, cy)) {
+                            map.push(self.state.passage);
+                        } else {
+                            map.push(self.state.wall);
+                        }
+                    }
                     (false, true) => {
-                        if grid.is_carved((cx, cy), Cell::EAST) {
+                        if is_open(grid, (cx, cy), Cell::EAST) {
                             map.push(self.state.passage);
                         } else {
                             map.push(self.state.wall);
                         }
                     }
                     (true, false) => {
-                        if grid.is_carved((cx, cy), Cell::SOUTH) {
+                        if is_open(grid, (cx, cy), Cell::SOUTH) {
                             map.push(self.state.passage);
                         } else {
                             map.push(self.state.wall);
@@ -370,12 +402,26 @@ impl Formatter<StringWrapper> for GameMap<WithStartGoal> {
     }
 }
 
+/// Checks if a column of the map's north border opens a passage across the wrapped border
+fn is_north_border_open(x: usize, span: usize, grid: &Grid) -> bool {
+    !x.is_multiple_of(span) && grid.is_carved(((x - 1) / span, 0), Cell::NORTH)
+}
+
+/// Checks if a row of the map's west border opens a passage across the wrapped border
+fn is_west_border_open(y: usize, span: usize, grid: &Grid) -> bool {
+    !(y + 1).is_multiple_of(span) && grid.is_carved((0, y / span), Cell::WEST)
+}
+
+/// Checks if the cells to the East and South of a cell are linked to a cell diagonal to it, so
+/// the four cells make up an open room with no wall joint between them
 fn bottom_right_neighbour_exists(cx: usize, cy: usize, grid: &Grid) -> bool {
-    if cy + 1 >= grid.width() || cx + 1 >= grid.height() {
+    let east = grid.get_next_cell_coords((cx, cy), Cell::EAST);
+    let south = grid.get_next_cell_coords((cx, cy), Cell::SOUTH);
+    let (Ok(east), Ok(south)) = (east, south) else {
         return false;
-    }
+    };
 
-    grid.is_carved((cx + 1, cy + 1), Cell::WEST) && grid.is_carved((cx + 1, cy + 1), Cell::NORTH)
+    grid.is_carved(east, Cell::SOUTH) && grid.is_carved(south, Cell::EAST)
 }
 
 fn write_map(map: &[char], cols: usize) -> String {
@@ -500,6 +546,29 @@ mod tests {
         assert_eq!(positions, result.collect::<Vec<Coords>>());
     }
 
+    #[test]
+    fn format_non_square_with_start_and_goal() {
+        let mut grid = Grid::new(3, 1);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+
+        for seed in 0..20 {
+            let formatter = GameMap::new().span(1).with_start_goal().seed(Some(seed));
+            let actual = formatter.format(&grid).0;
+            let lines: Vec<&str> = actual.lines().collect();
+
+            assert_eq!(
+                "#######\n#.....#\n#######\n",
+                actual.replace(['S', 'G'], "#")
+            );
+            for marker in ['S', 'G'] {
+                let row = lines.iter().position(|line| line.contains(marker)).unwrap();
+                let col = lines[row].find(marker).unwrap();
+                assert!(row == 0 || row == 2 || col == 0 || col == 6);
+            }
+        }
+    }
+
     #[test]
     fn format_with_no_start_and_goal() {
         let mut expected = String::new();
@@ -540,6 +609,56 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn format_open_room() {
+        let mut expected = String::new();
+        expected.push_str("##########\n");
+        expected.push_str("#........#\n");
+        expected.push_str("#........#\n");
+        expected.push_str("#........#\n");
+        expected.push_str("#........#\n");
+        expected.push_str("#........#\n");
+        expected.push_str("##########\n");
+
+        let actual = GameMap::new().format(&generate_room()).0;
+
+        assert_eq!(actual, expected);
+    }
+
+    fn generate_room() -> Grid {
+        let mut grid = Grid::new(3, 2);
+        for x in 0..3 {
+            grid.carve_passage((x, 0), Cell::SOUTH).unwrap();
+        }
+        for y in 0..2 {
+            grid.carve_passage((0, y), Cell::EAST).unwrap();
+            grid.carve_passage((1, y), Cell::EAST).unwrap();
+        }
+
+        grid
+    }
+
+    #[test]
+    fn format_tunnel() {
+        let mut grid = Grid::new(3, 3);
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_tunnel((0, 1), Cell::EAST).unwrap();
+
+        // Both sides of a cell a tunnel runs under are open
+        let mut expected = String::new();
+        expected.push_str("#######\n");
+        expected.push_str("#.#.#.#\n");
+        expected.push_str("###.###\n");
+        expected.push_str("#.....#\n");
+        expected.push_str("###.###\n");
+        expected.push_str("#.#.#.#\n");
+        expected.push_str("#######\n");
+
+        let formatter = GameMap::new().span(1);
+        assert_eq!(expected, formatter.format(&grid).into_inner());
+    }
+
     fn generate_maze() -> Grid {
         let mut grid = Grid::new(4, 4);
 
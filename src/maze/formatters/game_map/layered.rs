@@ -0,0 +1,84 @@
+use super::{GameMap, NoStartGoal};
+use crate::maze::formatters::{stairs_marker, Formatter, StringWrapper};
+use crate::maze::grid::layered::LayeredGrid;
+
+impl GameMap<NoStartGoal> {
+    /// Converts every level of a given layered grid into a separate map, from the ground level up
+    ///
+    /// The stairs are placed in the top left corner of a cell they're carved out of and marked by
+    /// `U` when they lead up, `D` when they lead down and `X` when they lead both ways.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = LayeredMazeBuilder::new().levels(2).build();
+    /// let floors = GameMap::new().format_levels(maze.grid());
+    ///
+    /// assert_eq!(2, floors.len());
+    /// ```
+    pub fn format_levels(&self, grid: &LayeredGrid) -> Vec<StringWrapper> {
+        // Span (width of a passage) + 1 (place for a wall)
+        let span = self.state.span + 1;
+        // Every line of a map ends with a line break
+        let line_len = grid.width() * span + 2;
+
+        (0..grid.levels())
+            .filter_map(|z| {
+                let level = grid.level(z)?;
+                let mut map: Vec<char> = self.format(&level).into_inner().chars().collect();
+
+                for ((x, y), stairs) in grid.stairs(z) {
+                    if let Some(marker) = stairs_marker(stairs) {
+                        map[(y * span + 1) * line_len + x * span + 1] = marker;
+                    }
+                }
+                Some(StringWrapper(map.into_iter().collect()))
+            })
+            .collect()
+    }
+}
+
+/// An implementation of a formatter for layered mazes
+///
+/// Emits the maps of all the levels, see [format_levels](GameMap::format_levels), from the ground
+/// level up with an empty line between each two of them.
+impl Formatter<StringWrapper, LayeredGrid> for GameMap<NoStartGoal> {
+    /// Converts a given layered grid into the map characters and returns a [StringWrapper] over it
+    fn format(&self, grid: &LayeredGrid) -> StringWrapper {
+        let levels: Vec<String> = self
+            .format_levels(grid)
+            .into_iter()
+            .map(StringWrapper::into_inner)
+            .collect();
+
+        StringWrapper(levels.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_levels() {
+        let mut grid = LayeredGrid::new(2, 1, 2);
+        grid.graph_mut().link(0, 1).unwrap();
+        grid.graph_mut().link(1, 3).unwrap();
+        grid.graph_mut().link(3, 2).unwrap();
+
+        let formatter = GameMap::new().span(1);
+        let floors: Vec<String> = formatter
+            .format_levels(&grid)
+            .into_iter()
+            .map(StringWrapper::into_inner)
+            .collect();
+        assert_eq!(
+            vec!["#####\n#..U#\n#####\n", "#####\n#..D#\n#####\n"],
+            floors
+        );
+
+        let map = formatter.format(&grid).into_inner();
+        assert_eq!(format!("{}\n{}", floors[0], floors[1]), map);
+    }
+}
@@ -1,22 +1,72 @@
 use crate::maze::grid::cell::Cell;
-use crate::maze::{formatters::Formatter, grid::Grid};
+use crate::maze::{formatters::Formatter, grid::Grid, statistics::distances};
 use crate::utils::color::Color;
 use crate::utils::types::Coords;
 use image::{ImageBuffer, RgbImage};
 
-use super::ImageWrapper;
+use super::{is_wall, path_step, ImageWrapper};
+
+mod animation;
+mod delta;
+mod draw;
+mod hex;
+mod layered;
+mod polar;
+mod upsilon;
+
+pub use animation::Animation;
+
+/// A rectangle of pixels as its top left corner and size
+type Rect = ((usize, usize), (usize, usize));
 
 /// An Image formatter for a generated maze
 ///
 /// This formatter generates a JPG or PNG image of a maze, designed for use in games,
 /// puzzle design, or visual content creation. It supports customizable maze parameters
 /// and ensures a visually clear output.
+///
+/// Optionally, it draws a path through the maze (e.g. the one found by a
+/// [Solver](crate::maze::Solver)) and highlights its start and goal cells, so the output can be
+/// used as a puzzle answer key.
+///
+/// It can also render a heat map, filling every passage with a color gradient by its distance
+/// from a given cell, which makes the texture of a generation algorithm easy to see.
+///
+/// With an [inset](Image::inset), passages are drawn as corridors narrower than cells, which makes
+/// the crossings of a [weave](crate::maze::OrthogonalMazeBuilder::weave) maze visible.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use knossos::maze::*;
+///
+/// let maze = OrthogonalMazeBuilder::new().build();
+/// let path = maze.solve(BreadthFirstSearch, (0, 0), (9, 9)).unwrap();
+///
+/// let formatter = Image::new().path(path).start((0, 0)).goal((9, 9));
+/// maze.save("output/maze_answer_key.png", formatter).unwrap();
+///
+/// let formatter = Image::new().heat_map((0, 0));
+/// maze.save("output/maze_heat_map.png", formatter).unwrap();
+/// ```
+#[derive(Clone)]
 pub struct Image {
     wall_width: usize,
     passage_width: usize,
     margin: usize,
     background_color: Color,
     foreground_color: Color,
+    path: Vec<Coords>,
+    path_color: Color,
+    path_width: f32,
+    start: Option<Coords>,
+    start_color: Color,
+    goal: Option<Coords>,
+    goal_color: Color,
+    heat_map: Option<Coords>,
+    heat_near_color: Color,
+    heat_far_color: Color,
+    inset: usize,
 }
 
 impl Image {
@@ -28,6 +78,17 @@ impl Image {
             background_color: Color::RGB(250, 250, 250),
             foreground_color: Color::RGB(0, 0, 0),
             margin: 50,
+            path: Vec::new(),
+            path_color: Color::RGB(220, 50, 50),
+            path_width: 0.4,
+            start: None,
+            start_color: Color::RGB(40, 170, 80),
+            goal: None,
+            goal_color: Color::RGB(40, 100, 220),
+            heat_map: None,
+            heat_near_color: Color::RGB(255, 245, 200),
+            heat_far_color: Color::RGB(150, 0, 40),
+            inset: 0,
         }
     }
 
@@ -61,6 +122,87 @@ impl Image {
         self
     }
 
+    /// Sets a path to draw through the maze passages and returns itself
+    ///
+    /// The path is an ordered list of cells, where every two consecutive cells are expected to be
+    /// adjacent, and the steps between the other cells are left out. Cells out of the maze bounds
+    /// are skipped.
+    ///
+    /// A step through a tunnel is drawn over the cell the tunnel runs under, while a step across a
+    /// wrapped border reaches out to both of the opposite edges of a maze.
+    pub fn path(mut self, path: Vec<Coords>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Sets a path color and returns itself
+    pub const fn path_color(mut self, color: Color) -> Self {
+        self.path_color = color;
+        self
+    }
+
+    /// Sets a path stroke width relative to the passage width and returns itself
+    ///
+    /// The value is clamped to the `0.0..=1.0` range, where `1.0` makes the path fill the entire
+    /// passage. The stroke is never thinner than a single pixel.
+    pub const fn path_width(mut self, ratio: f32) -> Self {
+        self.path_width = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets a cell to highlight as the start and returns itself
+    pub const fn start(mut self, coords: Coords) -> Self {
+        self.start = Some(coords);
+        self
+    }
+
+    /// Sets a start cell color and returns itself
+    pub const fn start_color(mut self, color: Color) -> Self {
+        self.start_color = color;
+        self
+    }
+
+    /// Sets a cell to highlight as the goal and returns itself
+    pub const fn goal(mut self, coords: Coords) -> Self {
+        self.goal = Some(coords);
+        self
+    }
+
+    /// Sets a goal cell color and returns itself
+    pub const fn goal_color(mut self, color: Color) -> Self {
+        self.goal_color = color;
+        self
+    }
+
+    /// Sets a cell to measure distances from for rendering a heat map and returns itself
+    ///
+    /// Every passage reachable from the cell is filled with a color between the
+    /// [near and far](Image::heat_map_colors) heat map colors, proportionally to its distance.
+    /// The cells that cannot be reached keep the background color.
+    pub const fn heat_map(mut self, origin: Coords) -> Self {
+        self.heat_map = Some(origin);
+        self
+    }
+
+    /// Sets the colors of the nearest and the farthest passages of a heat map and returns itself
+    pub const fn heat_map_colors(mut self, near: Color, far: Color) -> Self {
+        self.heat_near_color = near;
+        self.heat_far_color = far;
+        self
+    }
+
+    /// Sets an inset of passages from the walls and returns itself
+    ///
+    /// With a nonzero inset, every passage is drawn as a corridor inset by a given number of
+    /// pixels from both sides of a cell, so the walls get thicker and there is room left between
+    /// the corridors. A tunnel of a [weave](crate::maze::OrthogonalMazeBuilder::weave) maze is
+    /// then drawn as two corridors ending at the walls of the corridor running over it. The inset
+    /// is limited so that a corridor stays at least a pixel wide.
+    pub const fn inset(mut self, width: usize) -> Self {
+        self.inset = width;
+        self
+    }
+
     const fn cell_width(&self) -> usize {
         self.wall_width * 2 + self.passage_width
     }
@@ -87,6 +229,221 @@ impl Image {
         }
     }
 
+    /// Returns the pixel coordinates of the top left corner of a cell passage
+    const fn passage_origin(&self, (x, y): Coords) -> (usize, usize) {
+        let step = self.wall_width + self.passage_width;
+        (
+            self.margin + x * step + self.wall_width,
+            self.margin + y * step + self.wall_width,
+        )
+    }
+
+    fn fill_rect(
+        &self,
+        image: &mut RgbImage,
+        (x, y): (usize, usize),
+        (w, h): (usize, usize),
+        color: Color,
+    ) {
+        // The shapes reaching out of a maze, e.g. a path across a wrapped border, are clipped
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        for py in y..(y + h).min(height) {
+            for px in x..(x + w).min(width) {
+                *image.get_pixel_mut(px as u32, py as u32) = rgb(color);
+            }
+        }
+    }
+
+    const fn inset_width(&self) -> usize {
+        let max = self.passage_width.saturating_sub(1) / 2;
+        if self.inset < max {
+            self.inset
+        } else {
+            max
+        }
+    }
+
+    /// Returns a rectangle of the passage inside a cell at the given coordinates
+    const fn body(&self, coords: Coords) -> Rect {
+        let inset = self.inset_width();
+        let (px, py) = self.passage_origin(coords);
+        let side = self.passage_width - inset * 2;
+        ((px + inset, py + inset), (side, side))
+    }
+
+    /// Returns the coordinates of a cell a passage carved out of a cell at the given coordinates
+    /// to East or South leads to, along with the rectangles of the opening between their bodies
+    ///
+    /// The opening of a tunnel is split into two parts that end at the sides of a cell the tunnel
+    /// runs under, so the inset around the corridor over the tunnel separates them. The opening of
+    /// a passage across a wrapped border is split into two parts that end at the opposite edges of
+    /// a maze.
+    fn opening(&self, grid: &Grid, coords: Coords, direction: Cell) -> Option<(Coords, Vec<Rect>)> {
+        let next = grid.get_linked_cell_coords(coords, direction)?;
+
+        let ((bx, by), (side, _)) = self.body(coords);
+        if next.0 < coords.0 || next.1 < coords.1 {
+            let ((nbx, nby), _) = self.body(next);
+            let length = self.wall_width + self.inset_width();
+            let rects = match direction {
+                Cell::EAST => vec![
+                    ((bx + side, by), (length, side)),
+                    ((nbx - length, nby), (length, side)),
+                ],
+                _ => vec![
+                    ((bx, by + side), (side, length)),
+                    ((nbx, nby - length), (side, length)),
+                ],
+            };
+            return Some((next, rects));
+        }
+
+        let reach = self.wall_width + self.inset_width() * 2;
+        let rect = |offset: usize, length: usize| match direction {
+            Cell::EAST => ((bx + offset, by), (length, side)),
+            _ => ((bx, by + offset), (side, length)),
+        };
+
+        let (x, y) = coords;
+        let (nx, ny) = next;
+        let rects = if nx - x + ny - y > 1 {
+            let gap = self.inset_width();
+            vec![
+                rect(side, reach - gap),
+                rect(side * 2 + reach + gap, reach - gap),
+            ]
+        } else {
+            vec![rect(side, reach)]
+        };
+
+        Some((next, rects))
+    }
+
+    fn draw_heat_map(&self, image: &mut RgbImage, grid: &Grid) {
+        let Some(origin) = self.heat_map.filter(|coords| grid.contains(*coords)) else {
+            return;
+        };
+
+        let distances = distances(grid, origin);
+        let max = distances
+            .iter()
+            .flatten()
+            .max()
+            .copied()
+            .unwrap_or(0)
+            .max(1) as f32;
+        let color =
+            |distance: f32| blend(self.heat_near_color, self.heat_far_color, distance / max);
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                let Some(distance) = distances[y * grid.width() + x] else {
+                    continue;
+                };
+
+                let (origin, size) = self.body((x, y));
+                self.fill_rect(image, origin, size, color(distance as f32));
+
+                // Fill the openings to the Eastern and Southern cells with a color in between
+                for dir in [Cell::EAST, Cell::SOUTH] {
+                    if let Some(((nx, ny), rects)) = self.opening(grid, (x, y), dir) {
+                        let next = distances[ny * grid.width() + nx].unwrap_or(distance);
+                        for (origin, size) in rects {
+                            let color = color((distance + next) as f32 / 2.0);
+                            self.fill_rect(image, origin, size, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_path(&self, image: &mut RgbImage, grid: &Grid) {
+        let (_, (side, _)) = self.body((0, 0));
+        // There is no room for a path in the passages with no width
+        if side == 0 {
+            return;
+        }
+        let stroke = ((side as f32 * self.path_width).round() as usize).clamp(1, side);
+        let offset = (self.passage_width - stroke) / 2;
+        let step_width = self.wall_width + self.passage_width;
+
+        let cells: Vec<Coords> = self
+            .path
+            .iter()
+            .copied()
+            .filter(|coords| grid.contains(*coords))
+            .collect();
+
+        for coords in cells.iter() {
+            let (px, py) = self.passage_origin(*coords);
+            let origin = (px + offset, py + offset);
+            self.fill_rect(image, origin, (stroke, stroke), self.path_color);
+        }
+
+        for pair in cells.windows(2) {
+            let Some(step) = path_step(grid, pair[0], pair[1]) else {
+                continue;
+            };
+
+            // Join the strokes of two cells with the halves of a step reaching out of both of
+            // them, which meet on the wall between adjacent cells or in the middle of a cell a
+            // tunnel runs under, and end at the edges of a maze across a wrapped border
+            let reach = (step.cells * step_width).div_ceil(2);
+            for (coords, direction) in [
+                (pair[0], step.direction),
+                (pair[1], step.direction.opposite()),
+            ] {
+                let (px, py) = self.passage_origin(coords);
+                let (ox, oy) = (px + offset, py + offset);
+                let (origin, size) = match direction {
+                    Cell::NORTH => ((ox, oy - reach.min(oy)), (stroke, stroke + reach.min(oy))),
+                    Cell::SOUTH => ((ox, oy), (stroke, stroke + reach)),
+                    Cell::WEST => ((ox - reach.min(ox), oy), (stroke + reach.min(ox), stroke)),
+                    _ => ((ox, oy), (stroke + reach, stroke)),
+                };
+                self.fill_rect(image, origin, size, self.path_color);
+            }
+        }
+    }
+
+    fn draw_markers(&self, image: &mut RgbImage, grid: &Grid) {
+        let markers = [(self.start, self.start_color), (self.goal, self.goal_color)];
+
+        for (coords, color) in markers {
+            if let Some(coords) = coords.filter(|coords| grid.contains(*coords)) {
+                let (origin, size) = self.body(coords);
+                self.fill_rect(image, origin, size, color);
+            }
+        }
+    }
+
+    /// Draws every enabled cell as a solid block with the passages inset into it
+    fn draw_inset_maze(&self, image: &mut RgbImage, grid: &Grid) {
+        let step = self.wall_width + self.passage_width;
+        let size = (self.cell_width(), self.cell_width());
+
+        for (x, y) in grid.enabled_cells() {
+            let origin = (self.margin + x * step, self.margin + y * step);
+            self.fill_rect(image, origin, size, self.foreground_color);
+        }
+
+        for coords in grid.enabled_cells() {
+            let (origin, size) = self.body(coords);
+            self.fill_rect(image, origin, size, self.background_color);
+
+            for dir in [Cell::EAST, Cell::SOUTH] {
+                for (origin, size) in self
+                    .opening(grid, coords, dir)
+                    .into_iter()
+                    .flat_map(|o| o.1)
+                {
+                    self.fill_rect(image, origin, size, self.background_color);
+                }
+            }
+        }
+    }
+
     fn draw_maze(&self, image: &mut RgbImage, grid: &Grid) {
         for y in 0..grid.height() {
             for x in 0..grid.width() {
@@ -95,7 +452,33 @@ impl Image {
         }
     }
 
+    /// Draws the walls a cell disabled by a mask shares with its enabled neighbours and leaves the
+    /// rest of the cell as empty space
+    fn draw_disabled_cell(&self, coords: Coords, grid: &Grid, image: &mut RgbImage) {
+        let (x, y) = coords;
+        let step = self.wall_width + self.passage_width;
+        let (cell_x, cell_y) = (self.margin + x * step, self.margin + y * step);
+        let (wall, side) = (self.wall_width, self.cell_width());
+
+        let walls = [
+            (Cell::NORTH, (cell_x, cell_y), (side, wall)),
+            (Cell::SOUTH, (cell_x, cell_y + side - wall), (side, wall)),
+            (Cell::WEST, (cell_x, cell_y), (wall, side)),
+            (Cell::EAST, (cell_x + side - wall, cell_y), (wall, side)),
+        ];
+        for (dir, origin, size) in walls {
+            if is_wall(grid, coords, dir) {
+                self.fill_rect(image, origin, size, self.foreground_color);
+            }
+        }
+    }
+
     fn draw_cell(&self, coords: Coords, grid: &Grid, image: &mut RgbImage) {
+        if !grid.is_cell_enabled(coords) {
+            self.draw_disabled_cell(coords, grid, image);
+            return;
+        }
+
         let (x, y) = coords;
         let inner_cell_width = self.cell_width() - self.wall_width;
         let cell_x = x * inner_cell_width + self.margin;
@@ -212,16 +595,26 @@ impl Image {
         let is_bottommost_cell = y == grid.height() - 1;
 
         // Ensure the rightmost and bottommost walls are fully drawn to prevent missing edges
-        // at the outer boundary of the grid. This guarantees a closed maze structure.
+        // at the outer boundary of the grid. This guarantees a closed maze structure, apart from
+        // the openings of the passages across the wrapped borders.
         if is_rightmost_cell || is_bottommost_cell {
             let cell_right_x = cell_x + self.cell_width() - 1;
             let cell_bottom_y = cell_y + self.cell_width() - 1;
+            let passage = |p: usize, cell_p: usize| {
+                p >= cell_p + self.wall_width && p <= cell_p + inner_cell_width
+            };
+            let is_east_open = grid.is_carved(coords, Cell::EAST);
+            let is_south_open = grid.is_carved(coords, Cell::SOUTH);
 
             for py in cell_y..=cell_bottom_y {
                 for px in cell_x..=cell_right_x {
-                    if (is_rightmost_cell && px > cell_right_x - self.wall_width)
-                        || (is_bottommost_cell && py > cell_bottom_y - self.wall_width)
-                    {
+                    let is_east_wall = is_rightmost_cell
+                        && px > cell_right_x - self.wall_width
+                        && !(is_east_open && passage(py, cell_y));
+                    let is_south_wall = is_bottommost_cell
+                        && py > cell_bottom_y - self.wall_width
+                        && !(is_south_open && passage(px, cell_x));
+                    if is_east_wall || is_south_wall {
                         *image.get_pixel_mut(px as u32, py as u32) = match self.foreground_color {
                             Color::RGB(r, g, b) => image::Rgb([r, g, b]),
                         }
@@ -232,6 +625,20 @@ impl Image {
     }
 }
 
+/// Returns a color in between two given colors, where the ratio of `0.0` stands for the first
+/// color and `1.0` for the second one
+fn blend(from: Color, to: Color, ratio: f32) -> Color {
+    let (Color::RGB(r1, g1, b1), Color::RGB(r2, g2, b2)) = (from, to);
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio).round() as u8;
+    Color::RGB(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+}
+
+const fn rgb(color: Color) -> image::Rgb<u8> {
+    match color {
+        Color::RGB(r, g, b) => image::Rgb([r, g, b]),
+    }
+}
+
 impl Default for Image {
     fn default() -> Self {
         Self::new()
@@ -246,7 +653,15 @@ impl Formatter<ImageWrapper> for Image {
         let mut image: RgbImage = ImageBuffer::new(width as u32, height as u32);
 
         self.fill_background(&mut image);
-        self.draw_maze(&mut image, grid);
+        if self.inset_width() > 0 {
+            self.draw_inset_maze(&mut image, grid);
+            self.draw_heat_map(&mut image, grid);
+        } else {
+            self.draw_heat_map(&mut image, grid);
+            self.draw_maze(&mut image, grid);
+        }
+        self.draw_markers(&mut image, grid);
+        self.draw_path(&mut image, grid);
 
         ImageWrapper(image)
     }
@@ -256,7 +671,7 @@ impl Formatter<ImageWrapper> for Image {
 mod tests {
     use image::EncodableLayout;
 
-    use crate::maze::grid::cell::Cell;
+    use crate::maze::{grid::cell::Cell, mask::Mask, topology::Topology};
 
     use super::*;
 
@@ -268,6 +683,175 @@ mod tests {
         assert_eq!(Color::RGB(250, 250, 250), image.background_color);
         assert_eq!(Color::RGB(0, 0, 0), image.foreground_color);
         assert_eq!(50, image.margin);
+        assert!(image.path.is_empty());
+        assert_eq!(Color::RGB(220, 50, 50), image.path_color);
+        assert_eq!(0.4, image.path_width);
+        assert_eq!(None, image.start);
+        assert_eq!(Color::RGB(40, 170, 80), image.start_color);
+        assert_eq!(None, image.goal);
+        assert_eq!(Color::RGB(40, 100, 220), image.goal_color);
+        assert_eq!(None, image.heat_map);
+        assert_eq!(Color::RGB(255, 245, 200), image.heat_near_color);
+        assert_eq!(Color::RGB(150, 0, 40), image.heat_far_color);
+    }
+
+    #[test]
+    fn heat_map_params_change() {
+        let image = Image::new()
+            .heat_map((1, 2))
+            .heat_map_colors(Color::RGB(1, 2, 3), Color::RGB(4, 5, 6));
+
+        assert_eq!(Some((1, 2)), image.heat_map);
+        assert_eq!(Color::RGB(1, 2, 3), image.heat_near_color);
+        assert_eq!(Color::RGB(4, 5, 6), image.heat_far_color);
+    }
+
+    #[test]
+    fn format_heat_map() {
+        let (near, far) = (Color::RGB(0, 0, 0), Color::RGB(180, 90, 0));
+        let formatter = Image::new()
+            .wall(1)
+            .passage(1)
+            .margin(0)
+            .foreground(Color::RGB(255, 255, 255))
+            .heat_map((0, 0))
+            .heat_map_colors(near, far);
+        let (grid, _) = crate::maze::solvers::tests::generate_maze();
+
+        let image = formatter.format(&grid).into_inner();
+
+        // The origin, the farthest cell at 9 steps, the cell at 3 steps, and the opening between
+        // the origin and the cell to the South
+        assert_eq!(&rgb(near), image.get_pixel(1, 1));
+        assert_eq!(&rgb(far), image.get_pixel(3, 1));
+        assert_eq!(&rgb(Color::RGB(60, 30, 0)), image.get_pixel(5, 3));
+        assert_eq!(&rgb(Color::RGB(10, 5, 0)), image.get_pixel(1, 2));
+        // A wall
+        assert_eq!(&rgb(Color::RGB(255, 255, 255)), image.get_pixel(2, 1));
+    }
+
+    #[test]
+    fn format_heat_map_unreachable_cells() {
+        let formatter = Image::new().wall(1).passage(1).margin(0).heat_map((0, 0));
+        let grid = Grid::new(2, 1);
+
+        let image = formatter.format(&grid).into_inner();
+
+        assert_eq!(&rgb(Color::RGB(255, 245, 200)), image.get_pixel(1, 1));
+        assert_eq!(&rgb(Color::RGB(250, 250, 250)), image.get_pixel(3, 1));
+    }
+
+    #[test]
+    fn format_masked_grid() {
+        let formatter = Image::new().wall(1).passage(1).margin(0);
+        let mut mask = Mask::new(3, 1);
+        mask.disable((2, 0));
+        let mut grid = Grid::from_mask(&mask);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+
+        let image = formatter.format(&grid).into_inner();
+
+        // The wall between the enabled and disabled cells is drawn, but the disabled cell is empty
+        assert_eq!(&rgb(Color::RGB(0, 0, 0)), image.get_pixel(4, 1));
+        assert_eq!(&rgb(Color::RGB(250, 250, 250)), image.get_pixel(5, 1));
+        assert_eq!(&rgb(Color::RGB(250, 250, 250)), image.get_pixel(5, 0));
+    }
+
+    #[test]
+    fn blend_colors() {
+        let (from, to) = (Color::RGB(0, 100, 255), Color::RGB(100, 0, 255));
+
+        assert_eq!(from, blend(from, to, 0.0));
+        assert_eq!(Color::RGB(50, 50, 255), blend(from, to, 0.5));
+        assert_eq!(to, blend(from, to, 1.0));
+    }
+
+    #[test]
+    fn path_params_change() {
+        let image = Image::new()
+            .path(vec![(0, 0), (0, 1)])
+            .path_color(Color::RGB(1, 2, 3))
+            .path_width(1.5)
+            .start((0, 0))
+            .start_color(Color::RGB(4, 5, 6))
+            .goal((0, 1))
+            .goal_color(Color::RGB(7, 8, 9));
+
+        assert_eq!(vec![(0, 0), (0, 1)], image.path);
+        assert_eq!(Color::RGB(1, 2, 3), image.path_color);
+        assert_eq!(1.0, image.path_width);
+        assert_eq!(Some((0, 0)), image.start);
+        assert_eq!(Color::RGB(4, 5, 6), image.start_color);
+        assert_eq!(Some((0, 1)), image.goal);
+        assert_eq!(Color::RGB(7, 8, 9), image.goal_color);
+    }
+
+    #[test]
+    fn format_path() {
+        let path_color = Color::RGB(255, 0, 0);
+        let formatter = Image::new()
+            .wall(1)
+            .passage(3)
+            .margin(0)
+            .path(vec![(0, 0), (0, 1), (1, 1)])
+            .path_color(path_color)
+            .path_width(0.3);
+        let (grid, _) = crate::maze::solvers::tests::generate_maze();
+
+        let image = formatter.format(&grid).into_inner();
+
+        // The center of the passages walked and the walls crossed
+        for (px, py) in [(2, 2), (2, 4), (2, 6), (4, 6), (6, 6)] {
+            assert_eq!(&rgb(path_color), image.get_pixel(px, py));
+        }
+        // The rest of the passage of a walked cell and the center of a cell not walked
+        for (px, py) in [(1, 1), (3, 3), (6, 2)] {
+            assert_eq!(&rgb(Color::RGB(250, 250, 250)), image.get_pixel(px, py));
+        }
+    }
+
+    #[test]
+    fn format_start_and_goal() {
+        let formatter = Image::new()
+            .wall(1)
+            .passage(3)
+            .margin(0)
+            .start((0, 0))
+            .goal((3, 3))
+            .goal_color(Color::RGB(0, 0, 255));
+        let (grid, _) = crate::maze::solvers::tests::generate_maze();
+
+        let image = formatter.format(&grid).into_inner();
+
+        for (px, py) in [(1, 1), (2, 2), (3, 3)] {
+            assert_eq!(&rgb(Color::RGB(40, 170, 80)), image.get_pixel(px, py));
+        }
+        for (px, py) in [(13, 13), (15, 15)] {
+            assert_eq!(&rgb(Color::RGB(0, 0, 255)), image.get_pixel(px, py));
+        }
+        assert_eq!(&rgb(Color::RGB(0, 0, 0)), image.get_pixel(0, 0));
+    }
+
+    #[test]
+    fn format_path_out_of_bounds() {
+        let formatter = Image::new().wall(1).passage(1).margin(0);
+        let expected = formatter.format(&generate_maze()).into_inner();
+
+        let formatter = formatter.path(vec![(10, 10), (11, 10)]).goal((4, 4));
+        let actual = formatter.format(&generate_maze()).into_inner();
+
+        assert_eq!(expected.as_bytes(), actual.as_bytes());
+    }
+
+    #[test]
+    fn format_path_without_passages() {
+        let formatter = Image::new().wall(2).passage(0).margin(0);
+        let expected = formatter.format(&generate_maze()).into_inner();
+
+        let formatter = formatter.path(vec![(0, 0), (0, 1)]);
+        let actual = formatter.format(&generate_maze()).into_inner();
+
+        assert_eq!(expected.as_bytes(), actual.as_bytes());
     }
 
     #[test]
@@ -307,6 +891,91 @@ mod tests {
         assert_eq!(actual.as_bytes(), expected.as_bytes());
     }
 
+    #[test]
+    fn format_inset_tunnel() {
+        let formatter = Image::new().wall(1).passage(5).margin(0).inset(1);
+        let mut grid = Grid::new(3, 3);
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_tunnel((0, 1), Cell::EAST).unwrap();
+
+        let image = formatter.format(&grid).into_inner();
+        let wall = *image.get_pixel(0, 0);
+        let passage = *image.get_pixel(9, 9);
+        assert_ne!(wall, passage);
+
+        // The tunnel stops a pixel short of both sides of the corridor running over it
+        let row: Vec<_> = (2..17).map(|x| *image.get_pixel(x, 9) == wall).collect();
+        assert_eq!(
+            vec![
+                false, false, false, false, false, true, false, false, false, true, false, false,
+                false, false, false
+            ],
+            row
+        );
+        assert_eq!(formatter.inset_width(), 1);
+        assert_eq!(Image::new().passage(5).inset(10).inset_width(), 2);
+    }
+
+    #[test]
+    fn format_path_through_tunnel_and_wrapped_border() {
+        let mut grid = Grid::new(3, 3);
+        grid.set_topology(Topology::Torus);
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_tunnel((0, 1), Cell::EAST).unwrap();
+        grid.carve_passage((2, 0), Cell::NORTH).unwrap();
+
+        let path = rgb(Color::RGB(220, 50, 50));
+        let image = Image::new()
+            .wall(1)
+            .passage(5)
+            .margin(0)
+            .path_width(1.0)
+            .path(vec![(0, 1), (2, 1), (2, 0), (2, 2)])
+            .format(&grid)
+            .into_inner();
+
+        // The path runs over the cell the tunnel runs under
+        assert!((1..18).all(|x| image.get_pixel(x, 9) == &path));
+        // The path leaves through the top edge and comes back through the bottom one
+        assert_eq!(&path, image.get_pixel(15, 0));
+        assert_eq!(&path, image.get_pixel(15, 18));
+    }
+
+    #[test]
+    fn format_wrapped_passages() {
+        let mut grid = Grid::new(3, 3);
+        grid.set_topology(Topology::Torus);
+        grid.carve_passage((2, 1), Cell::EAST).unwrap();
+        grid.carve_passage((1, 2), Cell::SOUTH).unwrap();
+
+        let background = rgb(Color::RGB(250, 250, 250));
+        let foreground = rgb(Color::RGB(0, 0, 0));
+
+        // The passages across the wrapped borders open both of the opposite borders
+        let image = Image::new()
+            .wall(1)
+            .passage(1)
+            .margin(0)
+            .format(&grid)
+            .into_inner();
+        assert_eq!(&background, image.get_pixel(6, 3));
+        assert_eq!(&background, image.get_pixel(0, 3));
+        assert_eq!(&background, image.get_pixel(3, 6));
+        assert_eq!(&background, image.get_pixel(3, 0));
+        assert_eq!(&foreground, image.get_pixel(6, 2));
+        assert_eq!(&foreground, image.get_pixel(6, 5));
+
+        let formatter = Image::new().wall(1).passage(5).margin(0).inset(1);
+        let image = formatter.format(&grid).into_inner();
+        assert_eq!(&background, image.get_pixel(18, 9));
+        assert_eq!(&background, image.get_pixel(0, 9));
+        assert_eq!(&background, image.get_pixel(9, 18));
+        assert_eq!(&background, image.get_pixel(9, 0));
+        assert_eq!(&foreground, image.get_pixel(18, 1));
+    }
+
     fn generate_maze() -> Grid {
         let mut grid = Grid::new(4, 4);
 
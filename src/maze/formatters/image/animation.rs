@@ -0,0 +1,234 @@
+use super::Image;
+use crate::maze::events::Replay;
+use crate::maze::formatters::{AnimationWrapper, Formatter};
+use crate::maze::grid::Grid;
+use crate::utils::color::Color;
+use crate::utils::types::Coords;
+use image::{Delay, DynamicImage, Frame, RgbImage};
+use std::collections::HashSet;
+use std::iter;
+
+/// The number of frames to render the events into, unless a step is set
+const DEFAULT_FRAMES: usize = 100;
+
+/// An animation formatter for the process of generating a maze
+///
+/// This formatter replays the [Event](crate::maze::Event)s recorded while generating a maze with
+/// [record_events](crate::maze::OrthogonalMazeBuilder::record_events) and renders the maze every
+/// given number of steps with the [Image] styling, so the frames can be saved as an animated GIF.
+/// By default, the step grows with the number of events, so an animation gets about a hundred
+/// frames however long the generation process is.
+/// Each frame highlights the current cell of an algorithm and the cells it keeps track of, such as
+/// the frontier of [Prim](crate::maze::Prim) or the active cells of
+/// [GrowingTree](crate::maze::GrowingTree). The last frame shows the complete maze, which is the
+/// only frame if no events were recorded.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use knossos::maze::*;
+///
+/// let maze = OrthogonalMazeBuilder::new()
+///     .algorithm(Box::new(Prim::new()))
+///     .record_events(true)
+///     .build();
+///
+/// let formatter = Animation::new().step(4).delay(40);
+/// maze.save("output/maze.gif", formatter).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Animation {
+    image: Image,
+    step: Option<usize>,
+    delay: u32,
+    current_color: Color,
+    frontier_color: Color,
+}
+
+impl Animation {
+    /// Returns a new instance of an [Animation] formatter with a default settings, which renders
+    /// about a hundred frames of 50 milliseconds with the smaller walls and passages than an
+    /// [Image] has by default
+    pub const fn new() -> Animation {
+        Animation {
+            image: Image::new().wall(4).passage(16).margin(10),
+            step: None,
+            delay: 50,
+            current_color: Color::RGB(220, 50, 50),
+            frontier_color: Color::RGB(150, 190, 240),
+        }
+    }
+
+    /// Sets an [Image] formatter to render each frame with and returns itself
+    pub fn image(mut self, image: Image) -> Self {
+        self.image = image;
+        self
+    }
+
+    /// Sets a number of steps to replay between the frames and returns itself
+    ///
+    /// Each event counts as a step, so a larger number makes an animation shorter. A step is never
+    /// less than one.
+    pub const fn step(mut self, events: usize) -> Self {
+        self.step = Some(if events > 1 { events } else { 1 });
+        self
+    }
+
+    /// Sets a duration of each frame in milliseconds and returns itself
+    pub const fn delay(mut self, millis: u32) -> Self {
+        self.delay = millis;
+        self
+    }
+
+    /// Sets a color of the current cell of an algorithm and returns itself
+    pub const fn current_color(mut self, color: Color) -> Self {
+        self.current_color = color;
+        self
+    }
+
+    /// Sets a color of the cells an algorithm keeps track of and returns itself
+    pub const fn frontier_color(mut self, color: Color) -> Self {
+        self.frontier_color = color;
+        self
+    }
+
+    /// Returns an iterator rendering a frame of a given grid every step of replaying its events,
+    /// followed by a frame of the complete maze
+    pub(crate) fn frames<'a>(&'a self, grid: &'a Grid) -> impl Iterator<Item = Frame> + 'a {
+        let events = grid.events();
+        let step = self
+            .step
+            .unwrap_or_else(|| events.len().div_ceil(DEFAULT_FRAMES))
+            .max(1);
+
+        // Only the full steps make frames, since the last frame shows the complete maze anyway
+        let mut replay = Replay::new(grid);
+        let steps = iter::from_fn(move || {
+            (replay.by_ref().take(step).count() == step)
+                .then(|| self.frame(replay.grid(), replay.current(), replay.frontier()))
+        });
+
+        steps.chain(iter::once_with(|| self.frame(grid, None, &HashSet::new())))
+    }
+
+    /// Renders a grid with the given cells highlighted as a frame
+    fn frame(&self, grid: &Grid, current: Option<Coords>, frontier: &HashSet<Coords>) -> Frame {
+        let mut image = self.image.format(grid).into_inner();
+
+        for &coords in frontier.iter().filter(|coords| grid.contains(**coords)) {
+            self.highlight(&mut image, coords, self.frontier_color);
+        }
+        if let Some(coords) = current.filter(|coords| grid.contains(*coords)) {
+            self.highlight(&mut image, coords, self.current_color);
+        }
+
+        let delay = Delay::from_numer_denom_ms(self.delay, 1);
+        Frame::from_parts(DynamicImage::from(image).to_rgba8(), 0, 0, delay)
+    }
+
+    /// Fills the passage inside a cell at the given coordinates with a given color
+    fn highlight(&self, image: &mut RgbImage, coords: Coords, color: Color) {
+        let (origin, size) = self.image.body(coords);
+        self.image.fill_rect(image, origin, size, color);
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of a formatter
+impl Formatter<AnimationWrapper> for Animation {
+    /// Returns an [AnimationWrapper] over a copy of a given grid with the recorded events, which
+    /// renders the frames as they are saved
+    fn format(&self, grid: &Grid) -> AnimationWrapper {
+        AnimationWrapper {
+            animation: self.clone(),
+            grid: grid.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::events::Event;
+    use crate::maze::grid::cell::Cell;
+
+    #[test]
+    fn params_change() {
+        let animation = Animation::new()
+            .step(0)
+            .delay(20)
+            .current_color(Color::RGB(1, 2, 3))
+            .frontier_color(Color::RGB(4, 5, 6));
+        assert_eq!(Some(1), animation.step);
+        assert_eq!(20, animation.delay);
+        assert_eq!(Color::RGB(1, 2, 3), animation.current_color);
+        assert_eq!(Color::RGB(4, 5, 6), animation.frontier_color);
+        assert_eq!(Some(10), Animation::new().step(10).step);
+    }
+
+    #[test]
+    fn format_without_events() {
+        let mut grid = Grid::new(2, 1);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+
+        let animation = Animation::new().format(&grid).into_inner();
+        let image = Animation::new().image.format(&grid).into_inner();
+        assert_eq!(1, animation.len());
+        assert_eq!(DynamicImage::from(image).to_rgba8(), *animation[0].buffer());
+    }
+
+    #[test]
+    fn format_with_default_step() {
+        let mut grid = Grid::new(2, 1);
+        grid.record_events();
+        for _ in 0..250 {
+            grid.emit(Event::Visit((0, 0)));
+        }
+
+        // Every 3 events make a frame, and the last frame shows the complete maze
+        assert_eq!(84, Animation::new().format(&grid).frames().count());
+        assert_eq!(11, Animation::new().step(25).format(&grid).frames().count());
+    }
+
+    #[test]
+    fn format_recorded_events() {
+        let mut grid = Grid::new(3, 1);
+        grid.record_events();
+        grid.emit(Event::Visit((0, 0)));
+        grid.emit(Event::Mark((1, 0)));
+        grid.emit(Event::Mark((2, 0)));
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.emit(Event::Backtrack((1, 0)));
+        grid.emit(Event::Visit((1, 0)));
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+
+        let animation = Animation::new().step(2).delay(30);
+        let frames = animation.format(&grid).into_inner();
+        assert_eq!(4, frames.len());
+        assert_eq!((30, 1), frames[0].delay().numer_denom_ms());
+
+        let color = |frame: &Frame, coords: Coords| {
+            let ((x, y), _) = animation.image.body(coords);
+            let image::Rgba([r, g, b, _]) = *frame.buffer().get_pixel(x as u32, y as u32);
+            Color::RGB(r, g, b)
+        };
+        let background = Color::RGB(250, 250, 250);
+
+        assert_eq!(animation.current_color, color(&frames[0], (0, 0)));
+        assert_eq!(animation.frontier_color, color(&frames[0], (1, 0)));
+        assert_eq!(background, color(&frames[0], (2, 0)));
+
+        assert_eq!(animation.current_color, color(&frames[2], (1, 0)));
+        assert_eq!(animation.frontier_color, color(&frames[2], (2, 0)));
+        assert_eq!(background, color(&frames[2], (0, 0)));
+
+        for coords in [(0, 0), (1, 0), (2, 0)] {
+            assert_eq!(background, color(&frames[3], coords));
+        }
+    }
+}
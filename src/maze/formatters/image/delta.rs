@@ -0,0 +1,110 @@
+use super::draw::{draw_line, Point};
+use super::{rgb, Image};
+use crate::maze::formatters::{Formatter, ImageWrapper};
+use crate::maze::grid::delta::{DeltaCell, DeltaGrid};
+use crate::utils::types::Coords;
+use image::{ImageBuffer, RgbImage};
+
+impl Image {
+    /// Returns the side length and the height of the triangles of a triangular maze in pixels
+    ///
+    /// The triangles are sized so that a circle inscribed into each of them is as wide as the sum
+    /// of the wall and passage widths.
+    fn triangle_sizes(&self) -> (f64, f64) {
+        let diameter = (self.wall_width + self.passage_width) as f64;
+        (diameter * 3.0_f64.sqrt(), diameter * 1.5)
+    }
+
+    fn delta_sizes(&self, grid: &DeltaGrid) -> (usize, usize) {
+        let (side, height) = self.triangle_sizes();
+        let frame = (self.wall_width + self.margin * 2) as f64;
+
+        let width = side / 2.0 * (grid.width() + 1) as f64;
+        let height = height * grid.height() as f64;
+        (
+            (width + frame).ceil() as usize,
+            (height + frame).ceil() as usize,
+        )
+    }
+
+    /// Returns the ends of the sides of a triangle at the given coordinates in the Western, Eastern
+    /// and base order
+    fn triangle_sides(&self, grid: &DeltaGrid, coords: Coords) -> [(Point, Point); 3] {
+        let (x, y) = coords;
+        let (side, height) = self.triangle_sizes();
+        let offset = self.margin as f64 + self.wall_width as f64 / 2.0;
+
+        let left = offset + side / 2.0 * x as f64;
+        let (apex, base) = match grid.is_upright(coords) {
+            true => (offset + height * y as f64, offset + height * (y + 1) as f64),
+            false => (offset + height * (y + 1) as f64, offset + height * y as f64),
+        };
+
+        let west = (left, base);
+        let east = (left + side, base);
+        let top = (left + side / 2.0, apex);
+        [(west, top), (top, east), (west, east)]
+    }
+
+    fn draw_delta_maze(&self, image: &mut RgbImage, grid: &DeltaGrid) {
+        let width = self.wall_width as f64;
+        let color = rgb(self.foreground_color);
+        let dirs = [DeltaCell::WEST, DeltaCell::EAST, DeltaCell::BASE];
+
+        for coords in grid.cells() {
+            let sides = self.triangle_sides(grid, coords);
+            for (dir, (from, to)) in dirs.into_iter().zip(sides) {
+                if !grid.is_carved(coords, dir) {
+                    draw_line(image, from, to, width, color);
+                }
+            }
+        }
+    }
+}
+
+/// An implementation of a formatter for triangular mazes
+///
+/// The walls are drawn as the sides of triangles, each as wide as a [wall](Image::wall), with a
+/// circle inscribed into each triangle as wide as the sum of the wall and
+/// [passage](Image::passage) widths. The path, markers, heat map and inset are not supported yet,
+/// so they are ignored.
+impl Formatter<ImageWrapper, DeltaGrid> for Image {
+    /// Converts a given triangular grid into an image and returns an [ImageWrapper] over that image
+    fn format(&self, grid: &DeltaGrid) -> ImageWrapper {
+        let (width, height) = self.delta_sizes(grid);
+        let mut image: RgbImage = ImageBuffer::new(width as u32, height as u32);
+
+        self.fill_background(&mut image);
+        self.draw_delta_maze(&mut image, grid);
+
+        ImageWrapper(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_delta_grid() {
+        let mut grid = DeltaGrid::new(2, 1);
+        let formatter = Image::new().wall(2).passage(18).margin(0);
+
+        let image = formatter.format(&grid).into_inner();
+        // The triangles are about 34.6 pixels wide and 30 pixels high
+        assert_eq!((54, 32), image.dimensions());
+
+        let wall = rgb(formatter.foreground_color);
+        let passage = rgb(formatter.background_color);
+        // The bottom of the first triangle, the top of the second one and their shared side
+        assert_eq!(&wall, image.get_pixel(18, 31));
+        assert_eq!(&wall, image.get_pixel(35, 0));
+        assert_eq!(&wall, image.get_pixel(27, 16));
+        assert_eq!(&passage, image.get_pixel(18, 22));
+
+        grid.graph_mut().link(0, 1).unwrap();
+        let image = formatter.format(&grid).into_inner();
+        assert_eq!(&passage, image.get_pixel(27, 16));
+        assert_eq!(&wall, image.get_pixel(18, 31));
+    }
+}
@@ -0,0 +1,125 @@
+//! Drawing primitives for the mazes whose walls don't follow the pixel grid
+
+use image::{Rgb, RgbImage};
+
+/// A point in the image coordinates, where the pixel at `(x, y)` spans to `(x + 1, y + 1)`
+pub(super) type Point = (f64, f64);
+
+/// The length of the straight segments an arc is approximated with, in pixels
+const ARC_SEGMENT_LENGTH: f64 = 4.0;
+
+/// Fills all the pixels whose centers are within half of a given width from a line segment
+/// between two points, which gives the line round ends
+pub(super) fn draw_line(image: &mut RgbImage, from: Point, to: Point, width: f64, color: Rgb<u8>) {
+    let half = width / 2.0;
+    if half <= 0.0 {
+        return;
+    }
+
+    let (min_x, max_x) = (from.0.min(to.0) - half, from.0.max(to.0) + half);
+    let (min_y, max_y) = (from.1.min(to.1) - half, from.1.max(to.1) + half);
+    let x_range = pixel_range(min_x, max_x, image.width());
+    let y_range = pixel_range(min_y, max_y, image.height());
+
+    for y in y_range {
+        for x in x_range.clone() {
+            let center = (x as f64 + 0.5, y as f64 + 0.5);
+            if distance_to_segment(center, from, to) <= half {
+                image.put_pixel(x, y, color);
+            }
+        }
+    }
+}
+
+/// Draws an arc of a circle with a given center and radius between two angles in radians, where
+/// the angle of zero points up and the angles grow clockwise
+pub(super) fn draw_arc(
+    image: &mut RgbImage,
+    center: Point,
+    radius: f64,
+    (from, to): (f64, f64),
+    width: f64,
+    color: Rgb<u8>,
+) {
+    let length = radius * (to - from).abs();
+    let segments = (length / ARC_SEGMENT_LENGTH).ceil().max(1.0) as usize;
+
+    let mut previous = polar_point(center, radius, from);
+    for step in 1..=segments {
+        let angle = from + (to - from) * step as f64 / segments as f64;
+        let next = polar_point(center, radius, angle);
+        draw_line(image, previous, next, width, color);
+        previous = next;
+    }
+}
+
+/// Returns a point at a given distance from a center in a given direction, where the angle of
+/// zero points up and the angles grow clockwise
+pub(super) fn polar_point((cx, cy): Point, radius: f64, angle: f64) -> Point {
+    (cx + radius * angle.sin(), cy - radius * angle.cos())
+}
+
+/// Returns the range of pixels overlapping with a given span of image coordinates, limited by the
+/// image size
+fn pixel_range(from: f64, to: f64, size: u32) -> std::ops::Range<u32> {
+    let from = from.floor().max(0.0) as u32;
+    let to = (to.ceil().max(0.0) as u32).min(size);
+    from.min(to)..to
+}
+
+fn distance_to_segment((px, py): Point, (ax, ay): Point, (bx, by): Point) -> f64 {
+    let (dx, dy) = (bx - ax, by - ay);
+    let length = dx * dx + dy * dy;
+    let t = if length > 0.0 {
+        (((px - ax) * dx + (py - ay) * dy) / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    let (cx, cy) = (ax + t * dx, ay + t * dy);
+    ((px - cx).powi(2) + (py - cy).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line() {
+        let mut image = RgbImage::new(5, 5);
+        let white = Rgb([255, 255, 255]);
+        draw_line(&mut image, (0.5, 2.5), (4.5, 2.5), 1.0, white);
+
+        for x in 0..5 {
+            assert_eq!(&white, image.get_pixel(x, 2));
+            assert_eq!(&Rgb([0, 0, 0]), image.get_pixel(x, 1));
+        }
+    }
+
+    #[test]
+    fn line_out_of_bounds() {
+        let mut image = RgbImage::new(3, 3);
+        draw_line(&mut image, (-5.0, -5.0), (10.0, 10.0), 2.0, Rgb([1, 1, 1]));
+        assert_eq!(&Rgb([1, 1, 1]), image.get_pixel(1, 1));
+    }
+
+    #[test]
+    fn arc() {
+        let mut image = RgbImage::new(21, 21);
+        let white = Rgb([255, 255, 255]);
+        draw_arc(
+            &mut image,
+            (10.5, 10.5),
+            8.0,
+            (0.0, std::f64::consts::PI),
+            1.0,
+            white,
+        );
+
+        assert_eq!(&white, image.get_pixel(10, 2));
+        assert_eq!(&white, image.get_pixel(18, 10));
+        assert_eq!(&white, image.get_pixel(10, 18));
+        assert_ne!(&white, image.get_pixel(2, 10));
+        assert_ne!(&white, image.get_pixel(10, 10));
+    }
+}
@@ -0,0 +1,112 @@
+use super::draw::{draw_line, Point};
+use super::{rgb, Image};
+use crate::maze::formatters::{Formatter, ImageWrapper};
+use crate::maze::grid::hex::{HexCell, HexGrid};
+use crate::utils::types::Coords;
+use image::{ImageBuffer, RgbImage};
+use std::f64::consts::PI;
+
+impl Image {
+    /// Returns the apothem and the circumradius of the hexagons of a hexagonal maze in pixels
+    ///
+    /// The apothem is a half of the distance between two opposite walls, while the circumradius
+    /// is a distance between the center of a hexagon and its corners.
+    fn hex_radii(&self) -> (f64, f64) {
+        let apothem = (self.wall_width + self.passage_width) as f64 / 2.0;
+        (apothem, apothem * 2.0 / 3.0_f64.sqrt())
+    }
+
+    fn hex_sizes(&self, grid: &HexGrid) -> (usize, usize) {
+        let (apothem, radius) = self.hex_radii();
+        let frame = (self.wall_width + self.margin * 2) as f64;
+        let shift = if grid.width() > 1 { apothem } else { 0.0 };
+
+        let width = radius * 2.0 + radius * 1.5 * grid.width().saturating_sub(1) as f64;
+        let height = apothem * 2.0 * grid.height() as f64 + shift;
+        (
+            (width + frame).ceil() as usize,
+            (height + frame).ceil() as usize,
+        )
+    }
+
+    /// Returns the corners of a hexagon at the given coordinates, clockwise from the Eastern one
+    fn hex_corners(&self, (x, y): Coords) -> [Point; 6] {
+        let (apothem, radius) = self.hex_radii();
+        let offset = self.margin as f64 + self.wall_width as f64 / 2.0;
+        let cx = offset + radius + radius * 1.5 * x as f64;
+        let cy = offset + apothem * (2 * y + 1 + x % 2) as f64;
+
+        std::array::from_fn(|i| {
+            let angle = PI / 3.0 * i as f64;
+            (cx + radius * angle.cos(), cy + radius * angle.sin())
+        })
+    }
+
+    fn draw_hex_maze(&self, image: &mut RgbImage, grid: &HexGrid) {
+        let width = self.wall_width as f64;
+        let color = rgb(self.foreground_color);
+        let sides = [
+            HexCell::SOUTH_EAST,
+            HexCell::SOUTH,
+            HexCell::SOUTH_WEST,
+            HexCell::NORTH_WEST,
+            HexCell::NORTH,
+            HexCell::NORTH_EAST,
+        ];
+
+        for coords in grid.cells() {
+            let corners = self.hex_corners(coords);
+            for (i, dir) in sides.into_iter().enumerate() {
+                if !grid.is_carved(coords, dir) {
+                    draw_line(image, corners[i], corners[(i + 1) % 6], width, color);
+                }
+            }
+        }
+    }
+}
+
+/// An implementation of a formatter for hexagonal mazes
+///
+/// The walls are drawn as the sides of hexagons, each as wide as a [wall](Image::wall), with the
+/// distance between two opposite walls of a hexagon equal to the sum of the wall and
+/// [passage](Image::passage) widths. The path, markers, heat map and inset are not supported yet,
+/// so they are ignored.
+impl Formatter<ImageWrapper, HexGrid> for Image {
+    /// Converts a given hexagonal grid into an image and returns an [ImageWrapper] over that image
+    fn format(&self, grid: &HexGrid) -> ImageWrapper {
+        let (width, height) = self.hex_sizes(grid);
+        let mut image: RgbImage = ImageBuffer::new(width as u32, height as u32);
+
+        self.fill_background(&mut image);
+        self.draw_hex_maze(&mut image, grid);
+
+        ImageWrapper(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_hex_grid() {
+        let mut grid = HexGrid::new(2, 1);
+        let formatter = Image::new().wall(2).passage(18).margin(0);
+
+        let image = formatter.format(&grid).into_inner();
+        // The hexagons are 20 pixels between the opposite walls, i.e. about 23.1 pixels wide
+        assert_eq!((43, 32), image.dimensions());
+
+        let wall = rgb(formatter.foreground_color);
+        let passage = rgb(formatter.background_color);
+        assert_eq!(&wall, image.get_pixel(12, 1));
+        assert_eq!(&passage, image.get_pixel(12, 11));
+        // The wall between the cells
+        assert_eq!(&wall, image.get_pixel(20, 15));
+
+        grid.graph_mut().link(0, 1).unwrap();
+        let image = formatter.format(&grid).into_inner();
+        assert_eq!(&passage, image.get_pixel(20, 15));
+        assert_eq!(&wall, image.get_pixel(12, 1));
+    }
+}
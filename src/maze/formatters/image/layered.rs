@@ -0,0 +1,103 @@
+use super::draw::draw_line;
+use super::{rgb, Image};
+use crate::maze::formatters::{Formatter, ImageWrapper};
+use crate::maze::grid::layered::{LayeredCell, LayeredGrid};
+use crate::utils::types::Coords;
+use image::{imageops, ImageBuffer, RgbImage};
+
+impl Image {
+    /// Returns a formatter with the same sizes and colors but without a path, markers or a heat
+    /// map, whose coordinates are meaningless within a single level
+    const fn level_formatter(&self) -> Image {
+        let mut formatter = Image::new()
+            .wall(self.wall_width)
+            .passage(self.passage_width)
+            .margin(self.margin)
+            .background(self.background_color)
+            .foreground(self.foreground_color);
+        formatter.inset = self.inset;
+        formatter
+    }
+
+    /// Draws the stairs carved out of a cell at the given coordinates as chevrons, one pointing up
+    /// in the upper half of a cell and the other pointing down in the lower half
+    fn draw_stairs(&self, image: &mut RgbImage, coords: Coords, stairs: LayeredCell) {
+        let (px, py) = self.passage_origin(coords);
+        let side = self.passage_width as f64;
+        let point = |dx: f64, dy: f64| (px as f64 + side * dx, py as f64 + side * dy);
+        let width = (side / 8.0).max(1.0);
+        let color = rgb(self.foreground_color);
+
+        let chevrons = [
+            (LayeredCell::UP, (0.45, 0.2)),
+            (LayeredCell::DOWN, (0.55, 0.8)),
+        ];
+        for (dir, (base, tip)) in chevrons {
+            if stairs.contains(dir) {
+                draw_line(image, point(0.25, base), point(0.5, tip), width, color);
+                draw_line(image, point(0.5, tip), point(0.75, base), width, color);
+            }
+        }
+    }
+}
+
+/// An implementation of a formatter for layered mazes
+///
+/// The levels are drawn side by side from the ground one up, each with its own margins and a
+/// passage width apart from each other. The stairs are drawn as chevrons pointing up and down. The
+/// path, markers and heat map are not supported yet, so they are ignored.
+impl Formatter<ImageWrapper, LayeredGrid> for Image {
+    /// Converts a given layered grid into an image and returns an [ImageWrapper] over that image
+    fn format(&self, grid: &LayeredGrid) -> ImageWrapper {
+        let formatter = self.level_formatter();
+        let levels: Vec<RgbImage> = (0..grid.levels())
+            .filter_map(|z| {
+                let mut image = formatter.format(&grid.level(z)?).into_inner();
+                for (coords, stairs) in grid.stairs(z) {
+                    self.draw_stairs(&mut image, coords, stairs);
+                }
+                Some(image)
+            })
+            .collect();
+
+        let gap = self.passage_width as u32;
+        let width = levels.iter().map(|level| level.width() + gap).sum::<u32>();
+        let height = levels.first().map_or(0, RgbImage::height);
+        let mut image: RgbImage = ImageBuffer::new(width.saturating_sub(gap), height);
+
+        self.fill_background(&mut image);
+        let mut left = 0;
+        for level in levels {
+            imageops::replace(&mut image, &level, left.into(), 0);
+            left += level.width() + gap;
+        }
+
+        ImageWrapper(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_layered_grid() {
+        let mut grid = LayeredGrid::new(2, 1, 2);
+        grid.graph_mut().link(1, 3).unwrap();
+
+        let formatter = Image::new().wall(2).passage(16).margin(0);
+        let image = formatter.format(&grid).into_inner();
+        // Two levels of 38x20 pixels with a 16 pixels gap
+        assert_eq!((92, 20), image.dimensions());
+
+        let wall = rgb(formatter.foreground_color);
+        let passage = rgb(formatter.background_color);
+        // The gap between the levels
+        assert_eq!(&passage, image.get_pixel(45, 10));
+        // The tips of the chevrons on both levels
+        assert_eq!(&wall, image.get_pixel(28, 5));
+        assert_eq!(&passage, image.get_pixel(28, 15));
+        assert_eq!(&wall, image.get_pixel(82, 14));
+        assert_eq!(&passage, image.get_pixel(82, 5));
+    }
+}
@@ -0,0 +1,92 @@
+use super::draw::{draw_arc, draw_line, polar_point};
+use super::{rgb, Image};
+use crate::maze::formatters::{Formatter, ImageWrapper};
+use crate::maze::grid::polar::PolarGrid;
+use image::{ImageBuffer, RgbImage};
+use std::f64::consts::TAU;
+
+impl Image {
+    /// Returns the distance between two rings of walls of a polar maze in pixels
+    const fn ring_height(&self) -> usize {
+        self.wall_width + self.passage_width
+    }
+
+    fn draw_polar_maze(&self, image: &mut RgbImage, grid: &PolarGrid, center: (f64, f64)) {
+        let ring_height = self.ring_height() as f64;
+        let width = self.wall_width as f64;
+        let color = rgb(self.foreground_color);
+
+        for coords in grid.cells() {
+            let (ring, index) = coords;
+            let len = grid.ring_len(ring) as f64;
+            let angles = (TAU * index as f64 / len, TAU * (index + 1) as f64 / len);
+            let inner = ring_height * ring as f64;
+
+            if let Some(next) = grid.inward(coords) {
+                if !grid.is_linked(coords, next) {
+                    draw_arc(image, center, inner, angles, width, color);
+                }
+            }
+
+            if let Some(next) = grid.clockwise(coords) {
+                if !grid.is_linked(coords, next) {
+                    let from = polar_point(center, inner, angles.1);
+                    let to = polar_point(center, inner + ring_height, angles.1);
+                    draw_line(image, from, to, width, color);
+                }
+            }
+        }
+
+        let radius = ring_height * grid.rings() as f64;
+        draw_arc(image, center, radius, (0.0, TAU), width, color);
+    }
+}
+
+/// An implementation of a formatter for polar mazes
+///
+/// The walls are drawn as arcs and radial lines, each as wide as a [wall](Image::wall), with the
+/// rings a [passage](Image::passage) wide between them. The path, markers, heat map and inset are
+/// not supported yet, so they are ignored.
+impl Formatter<ImageWrapper, PolarGrid> for Image {
+    /// Converts a given polar grid into an image and returns an [ImageWrapper] over that image
+    fn format(&self, grid: &PolarGrid) -> ImageWrapper {
+        let size = self.ring_height() * grid.rings() * 2 + self.wall_width + self.margin * 2;
+        let mut image: RgbImage = ImageBuffer::new(size as u32, size as u32);
+        let center = (size as f64 / 2.0, size as f64 / 2.0);
+
+        self.fill_background(&mut image);
+        self.draw_polar_maze(&mut image, grid, center);
+
+        ImageWrapper(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_polar_grid() {
+        let mut grid = PolarGrid::new(2);
+        let formatter = Image::new().wall(2).passage(8).margin(5);
+
+        let image = formatter.format(&grid).into_inner();
+        assert_eq!((52, 52), image.dimensions());
+
+        let wall = rgb(formatter.foreground_color);
+        let passage = rgb(formatter.background_color);
+        // The top of the outer wall, the wall between the center and the first cell of the ring
+        // and the wall between the first two cells of the ring
+        assert_eq!(&wall, image.get_pixel(26, 6));
+        assert_eq!(&wall, image.get_pixel(31, 17));
+        assert_eq!(&wall, image.get_pixel(38, 18));
+        assert_eq!(&passage, image.get_pixel(26, 21));
+        assert_eq!(&passage, image.get_pixel(30, 10));
+
+        // Carve a passage from the center to the first cell of the ring
+        grid.graph_mut().link(0, 1).unwrap();
+        let image = formatter.format(&grid).into_inner();
+        assert_eq!(&passage, image.get_pixel(31, 17));
+        assert_eq!(&wall, image.get_pixel(38, 18));
+    }
+}
@@ -0,0 +1,136 @@
+use super::draw::{draw_line, Point};
+use super::{rgb, Image};
+use crate::maze::formatters::{Formatter, ImageWrapper};
+use crate::maze::grid::upsilon::{UpsilonCell, UpsilonGrid};
+use crate::utils::types::Coords;
+use image::{ImageBuffer, RgbImage};
+use std::f64::consts::{FRAC_PI_4, FRAC_PI_8, SQRT_2};
+
+/// The directions of the sides of an octagon, clockwise from the Eastern one
+const OCTAGON_SIDES: [UpsilonCell; 8] = [
+    UpsilonCell::EAST,
+    UpsilonCell::SOUTH_EAST,
+    UpsilonCell::SOUTH,
+    UpsilonCell::SOUTH_WEST,
+    UpsilonCell::WEST,
+    UpsilonCell::NORTH_WEST,
+    UpsilonCell::NORTH,
+    UpsilonCell::NORTH_EAST,
+];
+
+impl Image {
+    /// Returns the side length of the octagons and squares, the width of an octagon and the
+    /// distance between the centers of two adjacent cells of an upsilon maze in pixels
+    ///
+    /// The squares are sized so that they are as wide as the sum of the wall and passage widths.
+    fn upsilon_sizes(&self) -> (f64, f64, f64) {
+        let side = (self.wall_width + self.passage_width) as f64;
+        let octagon = side * (1.0 + SQRT_2);
+        (side, octagon, octagon / SQRT_2)
+    }
+
+    fn upsilon_image_sizes(&self, grid: &UpsilonGrid) -> (usize, usize) {
+        let (_, octagon, step) = self.upsilon_sizes();
+        let frame = (self.wall_width + self.margin * 2) as f64;
+        let size = |cells: usize| (octagon + step * cells.saturating_sub(1) as f64 + frame).ceil();
+
+        (size(grid.width()) as usize, size(grid.height()) as usize)
+    }
+
+    /// Returns the directions and the ends of the sides of a cell at the given coordinates
+    fn upsilon_sides(
+        &self,
+        grid: &UpsilonGrid,
+        coords: Coords,
+    ) -> Vec<(UpsilonCell, Point, Point)> {
+        let (x, y) = coords;
+        let (side, octagon, step) = self.upsilon_sizes();
+        let offset = self.margin as f64 + self.wall_width as f64 / 2.0 + octagon / 2.0;
+        let (cx, cy) = (offset + step * x as f64, offset + step * y as f64);
+
+        if grid.is_octagon(coords) {
+            let radius = side / 2.0 / FRAC_PI_8.sin();
+            let corner = |i: usize| {
+                let angle = FRAC_PI_4 * i as f64 - FRAC_PI_8;
+                (cx + radius * angle.cos(), cy + radius * angle.sin())
+            };
+
+            OCTAGON_SIDES
+                .into_iter()
+                .enumerate()
+                .map(|(i, dir)| (dir, corner(i), corner(i + 1)))
+                .collect()
+        } else {
+            let half = side / 2.0;
+            let (left, right, top, bottom) = (cx - half, cx + half, cy - half, cy + half);
+
+            vec![
+                (UpsilonCell::NORTH, (left, top), (right, top)),
+                (UpsilonCell::EAST, (right, top), (right, bottom)),
+                (UpsilonCell::SOUTH, (left, bottom), (right, bottom)),
+                (UpsilonCell::WEST, (left, top), (left, bottom)),
+            ]
+        }
+    }
+
+    fn draw_upsilon_maze(&self, image: &mut RgbImage, grid: &UpsilonGrid) {
+        let width = self.wall_width as f64;
+        let color = rgb(self.foreground_color);
+
+        for coords in grid.cells() {
+            for (dir, from, to) in self.upsilon_sides(grid, coords) {
+                if !grid.is_carved(coords, dir) {
+                    draw_line(image, from, to, width, color);
+                }
+            }
+        }
+    }
+}
+
+/// An implementation of a formatter for upsilon mazes
+///
+/// The walls are drawn as the sides of octagons and squares, each as wide as a
+/// [wall](Image::wall), with the squares as wide as the sum of the wall and
+/// [passage](Image::passage) widths. The path, markers, heat map and inset are not supported yet,
+/// so they are ignored.
+impl Formatter<ImageWrapper, UpsilonGrid> for Image {
+    /// Converts a given upsilon grid into an image and returns an [ImageWrapper] over that image
+    fn format(&self, grid: &UpsilonGrid) -> ImageWrapper {
+        let (width, height) = self.upsilon_image_sizes(grid);
+        let mut image: RgbImage = ImageBuffer::new(width as u32, height as u32);
+
+        self.fill_background(&mut image);
+        self.draw_upsilon_maze(&mut image, grid);
+
+        ImageWrapper(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_upsilon_grid() {
+        let mut grid = UpsilonGrid::new(2, 1);
+        let formatter = Image::new().wall(2).passage(18).margin(0);
+
+        let image = formatter.format(&grid).into_inner();
+        // The octagon is about 48.3 pixels wide, and the square is about 34.1 pixels from it
+        assert_eq!((85, 51), image.dimensions());
+
+        let wall = rgb(formatter.foreground_color);
+        let passage = rgb(formatter.background_color);
+        // The Northern side of the octagon, the shared side and the Eastern side of the square
+        assert_eq!(&wall, image.get_pixel(25, 1));
+        assert_eq!(&wall, image.get_pixel(49, 25));
+        assert_eq!(&wall, image.get_pixel(69, 25));
+        assert_eq!(&passage, image.get_pixel(25, 25));
+        assert_eq!(&passage, image.get_pixel(60, 25));
+
+        grid.graph_mut().link(0, 1).unwrap();
+        let image = formatter.format(&grid).into_inner();
+        assert_eq!(&passage, image.get_pixel(49, 25));
+        assert_eq!(&wall, image.get_pixel(69, 25));
+    }
+}
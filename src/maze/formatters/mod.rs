@@ -3,23 +3,117 @@
 mod ascii;
 mod game_map;
 mod image;
+mod svg;
 
-use crate::maze::grid::Grid;
-use ::image::RgbImage;
+use crate::maze::grid::{cell::Cell, layered::LayeredCell, Grid};
+use crate::utils::types::Coords;
+use ::image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Frame, RgbImage,
+};
 use std::{fs::File, io::Write};
 
-pub use self::image::Image;
+pub use self::image::{Animation, Image};
 use super::errors::MazeSaveError;
-pub use ascii::{AsciiNarrow, AsciiBroad};
+pub use ascii::{AsciiNarrow, AsciiBroad, AsciiPath};
 pub use game_map::GameMap;
+pub use svg::Svg;
 
 /// A trait for maze formatters
-pub trait Formatter<T>
+///
+/// A formatter converts an orthogonal [Grid] by default. Formatters supporting the grids of other
+/// shapes implement this trait for them too, e.g. `Formatter<ImageWrapper, PolarGrid>`.
+pub trait Formatter<T, G = Grid>
 where
     T: Saveable,
 {
     /// Returns a given grid converted into a given type that implements [Saveable]
-    fn format(&self, grid: &Grid) -> T;
+    fn format(&self, grid: &G) -> T;
+}
+
+/// Returns `true` if there is a wall on a given side of a cell at the given coordinates
+///
+/// A wall separates two enabled cells with no passage carved between them, as well as an enabled
+/// cell and either a cell disabled by a mask or the outside of a grid. There are no walls between
+/// the disabled cells, so they render as empty space.
+fn is_wall(grid: &Grid, coords: Coords, direction: Cell) -> bool {
+    let (x, y) = coords;
+    let next = match direction {
+        Cell::NORTH => y.checked_sub(1).map(|ny| (x, ny)),
+        Cell::SOUTH => Some((x, y + 1)),
+        Cell::WEST => x.checked_sub(1).map(|nx| (nx, y)),
+        Cell::EAST => Some((x + 1, y)),
+        _ => unreachable!(),
+    };
+    let is_next_enabled = next.is_some_and(|next| grid.is_cell_enabled(next));
+
+    (grid.is_cell_enabled(coords) || is_next_enabled) && !is_open(grid, coords, direction)
+}
+
+/// Returns `true` if a passage is carved on a given side of a cell at the given coordinates from
+/// either of the cells on both sides of it. Otherwise, returns `false`
+///
+/// A tunnel of a weave maze runs under a cell with no passages carved out of it towards the
+/// tunnel, so both sides of the cell are open as if the tunnel crossed it.
+fn is_open(grid: &Grid, coords: Coords, direction: Cell) -> bool {
+    grid.is_carved(coords, direction)
+        || grid
+            .get_next_cell_coords(coords, direction)
+            .is_ok_and(|next| grid.is_carved(next, direction.opposite()))
+}
+
+/// A step of a path between two cells next to each other
+struct PathStep {
+    /// The direction of a step
+    direction: Cell,
+    /// The number of cells a step covers, which is two for a step through a tunnel of a weave maze
+    cells: usize,
+    /// Whether a step leaves a grid across a wrapped border and comes back on the opposite side
+    wraps: bool,
+}
+
+/// Returns a step of a path from a cell at the given coordinates to the next one, or `None` if the
+/// cells aren't next to each other
+fn path_step(grid: &Grid, from: Coords, to: Coords) -> Option<PathStep> {
+    [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST]
+        .into_iter()
+        .filter_map(|direction| {
+            let next = grid.get_next_cell_coords(from, direction).ok()?;
+            let cells = if next == to {
+                1
+            } else {
+                let beyond = grid.get_next_cell_coords(next, direction).ok()?;
+                (beyond == to && grid.get_tunnel(next).contains(direction)).then_some(2)?
+            };
+            let wraps = match direction {
+                Cell::NORTH => to.1 > from.1,
+                Cell::SOUTH => to.1 < from.1,
+                Cell::WEST => to.0 > from.0,
+                _ => to.0 < from.0,
+            };
+
+            Some(PathStep {
+                direction,
+                cells,
+                wraps,
+            })
+        })
+        // The cells of a grid two cells wide are next to each other across the border too
+        .min_by_key(|step| step.wraps)
+}
+
+/// Returns a character marking the stairs carved out of a cell of a layered maze, which is `U`
+/// for the stairs leading up, `D` for the ones leading down and `X` for both, if there are any
+const fn stairs_marker(stairs: LayeredCell) -> Option<char> {
+    let up = stairs.contains(LayeredCell::UP);
+    let down = stairs.contains(LayeredCell::DOWN);
+
+    match (up, down) {
+        (true, true) => Some('X'),
+        (true, false) => Some('U'),
+        (false, true) => Some('D'),
+        (false, false) => None,
+    }
 }
 
 /// A trait for data wrappers that must be returned after formatting the grid
@@ -55,6 +149,53 @@ impl Saveable for ImageWrapper {
     }
 }
 
+/// A custom wrapper over a grid with the recorded events and an [Animation] formatter, which
+/// renders the [Frame]s of an animation of generating a maze
+///
+/// The frames are rendered one at a time while they are saved, so a long animation never has to
+/// fit into memory at once.
+pub struct AnimationWrapper {
+    animation: Animation,
+    grid: Grid,
+}
+
+impl AnimationWrapper {
+    /// Returns an iterator rendering the frames of an animation one by one
+    pub fn frames(&self) -> impl Iterator<Item = Frame> + '_ {
+        self.animation.frames(&self.grid)
+    }
+
+    /// Consumes `self` and returns all the frames rendered at once.
+    pub fn into_inner(self) -> Vec<Frame> {
+        self.frames().collect()
+    }
+}
+
+/// An implementation of [Saveable] for saving the frames of an animation into a GIF file
+impl Saveable for AnimationWrapper {
+    /// Encodes the frames as an endlessly looping animated GIF and saves it to a given path
+    fn save(&self, path: &str) -> Result<String, MazeSaveError> {
+        let file = File::create(path).map_err(|why| MazeSaveError {
+            reason: format!("Couldn't create {}: {}", path, why),
+        })?;
+
+        let mut encoder = GifEncoder::new(file);
+        let result = encoder
+            .set_repeat(Repeat::Infinite)
+            .and_then(|_| encoder.encode_frames(self.frames()));
+        if let Err(reason) = result {
+            return Err(MazeSaveError {
+                reason: reason.to_string(),
+            });
+        }
+
+        Ok(format!(
+            "Maze was successfully saved as an animation: {}",
+            path
+        ))
+    }
+}
+
 /// A custom wrapper over [std::string::String] for converting a maze into
 /// string characters
 pub struct StringWrapper(String);
@@ -0,0 +1,512 @@
+use crate::maze::grid::cell::Cell;
+use crate::maze::{formatters::Formatter, grid::Grid};
+use crate::utils::color::Color;
+use crate::utils::types::Coords;
+use std::fmt::Write;
+
+use super::{is_wall, path_step, StringWrapper};
+
+mod polar;
+
+/// An SVG formatter for a generated maze
+///
+/// This formatter generates a scalable vector image of a maze that stays crisp at any size, which
+/// makes it a good fit for print. Walls are merged into as few line segments as possible, so the
+/// output stays small even for large mazes.
+///
+/// Optionally, it draws a path through the maze (e.g. the one found by a
+/// [Solver](crate::maze::Solver)) and highlights its start and goal cells.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use knossos::maze::*;
+///
+/// let maze = OrthogonalMazeBuilder::new().build();
+/// let path = maze.solve(BreadthFirstSearch, (0, 0), (9, 9)).unwrap();
+///
+/// let formatter = Svg::new().cell(30).wall(3).path(path).start((0, 0)).goal((9, 9));
+/// maze.save("output/maze.svg", formatter).unwrap();
+/// ```
+pub struct Svg {
+    cell_size: usize,
+    wall_width: usize,
+    margin: usize,
+    background_color: Color,
+    foreground_color: Color,
+    path: Vec<Coords>,
+    path_color: Color,
+    path_width: f32,
+    start: Option<Coords>,
+    start_color: Color,
+    goal: Option<Coords>,
+    goal_color: Color,
+}
+
+impl Svg {
+    /// Returns a new instance of an [Svg] formatter with a default settings
+    pub const fn new() -> Svg {
+        Svg {
+            cell_size: 20,
+            wall_width: 2,
+            margin: 10,
+            background_color: Color::RGB(250, 250, 250),
+            foreground_color: Color::RGB(0, 0, 0),
+            path: Vec::new(),
+            path_color: Color::RGB(220, 50, 50),
+            path_width: 0.4,
+            start: None,
+            start_color: Color::RGB(40, 170, 80),
+            goal: None,
+            goal_color: Color::RGB(40, 100, 220),
+        }
+    }
+
+    /// Sets a cell size (a distance between the centers of two opposite walls) and returns itself
+    pub const fn cell(mut self, size: usize) -> Self {
+        self.cell_size = size;
+        self
+    }
+
+    /// Sets a wall stroke width and returns itself
+    pub const fn wall(mut self, width: usize) -> Self {
+        self.wall_width = width;
+        self
+    }
+
+    /// Sets a margin (a distance between a maze and the image borders) and returns itself
+    pub const fn margin(mut self, value: usize) -> Self {
+        self.margin = value;
+        self
+    }
+
+    /// Sets a background color and returns itself
+    pub const fn background(mut self, color: Color) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    /// Sets a maze (foreground) color and returns itself
+    pub const fn foreground(mut self, color: Color) -> Self {
+        self.foreground_color = color;
+        self
+    }
+
+    /// Sets a path to draw through the maze passages and returns itself
+    ///
+    /// The path is an ordered list of cells, where every two consecutive cells are expected to be
+    /// adjacent, and the steps between the other cells are left out. Cells out of the maze bounds
+    /// are skipped.
+    ///
+    /// A step through a tunnel runs over the cell the tunnel runs under, while a step across a
+    /// wrapped border leaves a maze on one side and comes back on the opposite one.
+    pub fn path(mut self, path: Vec<Coords>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Sets a path color and returns itself
+    pub const fn path_color(mut self, color: Color) -> Self {
+        self.path_color = color;
+        self
+    }
+
+    /// Sets a path stroke width relative to the cell size and returns itself
+    ///
+    /// The value is clamped to the `0.0..=1.0` range.
+    pub const fn path_width(mut self, ratio: f32) -> Self {
+        self.path_width = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets a cell to highlight as the start and returns itself
+    pub const fn start(mut self, coords: Coords) -> Self {
+        self.start = Some(coords);
+        self
+    }
+
+    /// Sets a start cell color and returns itself
+    pub const fn start_color(mut self, color: Color) -> Self {
+        self.start_color = color;
+        self
+    }
+
+    /// Sets a cell to highlight as the goal and returns itself
+    pub const fn goal(mut self, coords: Coords) -> Self {
+        self.goal = Some(coords);
+        self
+    }
+
+    /// Sets a goal cell color and returns itself
+    pub const fn goal_color(mut self, color: Color) -> Self {
+        self.goal_color = color;
+        self
+    }
+
+    const fn sizes(&self, grid: &Grid) -> (usize, usize) {
+        // Walls are stroked along the cell borders, so half of a wall sticks out of the outermost
+        // cells on each side
+        let width = self.cell_size * grid.width() + self.wall_width + self.margin * 2;
+        let height = self.cell_size * grid.height() + self.wall_width + self.margin * 2;
+        (width, height)
+    }
+
+    /// Returns the image coordinate of a line between cells with a given index
+    fn line(&self, index: usize) -> f64 {
+        (self.margin + index * self.cell_size) as f64 + self.wall_width as f64 / 2.0
+    }
+
+    /// Returns the image coordinate of the center of cells with a given index
+    fn center(&self, index: usize) -> f64 {
+        self.line(index) + self.cell_size as f64 / 2.0
+    }
+
+    /// Writes the opening tag of an SVG document of a given size and its background
+    fn write_header(&self, svg: &mut String, (width, height): (usize, usize)) {
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            width, height
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"  <rect width="100%" height="100%" fill="{}"/>"#,
+            self.background_color
+        )
+        .unwrap();
+    }
+
+    fn write_markers(&self, svg: &mut String, grid: &Grid) {
+        let markers = [(self.start, self.start_color), (self.goal, self.goal_color)];
+        let size = self.cell_size.saturating_sub(self.wall_width);
+
+        for (coords, color) in markers {
+            if let Some((x, y)) = coords.filter(|coords| grid.contains(*coords)) {
+                let offset = self.wall_width as f64 / 2.0;
+                writeln!(
+                    svg,
+                    r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    self.line(x) + offset,
+                    self.line(y) + offset,
+                    size,
+                    size,
+                    color
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    fn write_path(&self, svg: &mut String, grid: &Grid) {
+        let cells: Vec<Coords> = self
+            .path
+            .iter()
+            .copied()
+            .filter(|coords| grid.contains(*coords))
+            .collect();
+
+        if cells.is_empty() {
+            return;
+        }
+
+        let mut data = String::new();
+        for (i, &(x, y)) in cells.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|i| cells[i]);
+            let Some(step) = previous.and_then(|from| path_step(grid, from, (x, y))) else {
+                write!(data, "M{} {}", self.center(x), self.center(y)).unwrap();
+                continue;
+            };
+
+            // A step across a wrapped border goes out to one edge of a maze and comes back from
+            // the opposite one
+            if step.wraps {
+                let (px, py) = previous.unwrap();
+                let (width, height) = (grid.width(), grid.height());
+                let ((ex, ey), (nx, ny)) = match step.direction {
+                    Cell::NORTH => (
+                        (self.center(px), self.line(0)),
+                        (self.center(x), self.line(height)),
+                    ),
+                    Cell::SOUTH => (
+                        (self.center(px), self.line(height)),
+                        (self.center(x), self.line(0)),
+                    ),
+                    Cell::WEST => (
+                        (self.line(0), self.center(py)),
+                        (self.line(width), self.center(y)),
+                    ),
+                    _ => (
+                        (self.line(width), self.center(py)),
+                        (self.line(0), self.center(y)),
+                    ),
+                };
+                write!(data, "L{} {}M{} {}", ex, ey, nx, ny).unwrap();
+            }
+            write!(data, "L{} {}", self.center(x), self.center(y)).unwrap();
+        }
+
+        writeln!(
+            svg,
+            r#"  <path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+            data,
+            self.path_color,
+            self.cell_size as f32 * self.path_width
+        )
+        .unwrap();
+    }
+
+    fn write_walls(&self, svg: &mut String, grid: &Grid) {
+        let mut data = String::new();
+
+        // Horizontal walls along each of the lines between rows, merged into runs
+        for y in 0..=grid.height() {
+            let has_wall = |x: usize| match y {
+                0 => is_wall(grid, (x, 0), Cell::NORTH),
+                _ => is_wall(grid, (x, y - 1), Cell::SOUTH),
+            };
+            for (from, to) in runs(grid.width(), has_wall) {
+                write!(
+                    data,
+                    "M{} {}H{}",
+                    self.line(from),
+                    self.line(y),
+                    self.line(to)
+                )
+                .unwrap();
+            }
+        }
+
+        // Vertical walls along each of the lines between columns, merged into runs
+        for x in 0..=grid.width() {
+            let has_wall = |y: usize| match x {
+                0 => is_wall(grid, (0, y), Cell::WEST),
+                _ => is_wall(grid, (x - 1, y), Cell::EAST),
+            };
+            for (from, to) in runs(grid.height(), has_wall) {
+                write!(
+                    data,
+                    "M{} {}V{}",
+                    self.line(x),
+                    self.line(from),
+                    self.line(to)
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(
+            svg,
+            r#"  <path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="square"/>"#,
+            data, self.foreground_color, self.wall_width
+        )
+        .unwrap();
+    }
+}
+
+/// Returns the ranges of consecutive indices in `0..len` for which a predicate holds, as pairs of
+/// the first index and the index right after the last one
+fn runs<P>(len: usize, predicate: P) -> Vec<(usize, usize)>
+where
+    P: Fn(usize) -> bool,
+{
+    let mut runs = Vec::new();
+    let mut start = None;
+
+    for i in 0..=len {
+        match (start, i < len && predicate(i)) {
+            (None, true) => start = Some(i),
+            (Some(from), false) => {
+                runs.push((from, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    runs
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of a formatter
+impl Formatter<StringWrapper> for Svg {
+    /// Converts a given grid into an SVG document and returns a [StringWrapper] over it
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        let mut svg = String::new();
+
+        self.write_header(&mut svg, self.sizes(grid));
+        self.write_markers(&mut svg, grid);
+        self.write_path(&mut svg, grid);
+        self.write_walls(&mut svg, grid);
+
+        svg.push_str("</svg>\n");
+
+        StringWrapper(svg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{mask::Mask, solvers::tests::generate_maze, Topology};
+
+    #[test]
+    fn new_call_default_params() {
+        let svg = Svg::new();
+        assert_eq!(20, svg.cell_size);
+        assert_eq!(2, svg.wall_width);
+        assert_eq!(10, svg.margin);
+        assert_eq!(Color::RGB(250, 250, 250), svg.background_color);
+        assert_eq!(Color::RGB(0, 0, 0), svg.foreground_color);
+        assert!(svg.path.is_empty());
+        assert_eq!(Color::RGB(220, 50, 50), svg.path_color);
+        assert_eq!(0.4, svg.path_width);
+        assert_eq!(None, svg.start);
+        assert_eq!(Color::RGB(40, 170, 80), svg.start_color);
+        assert_eq!(None, svg.goal);
+        assert_eq!(Color::RGB(40, 100, 220), svg.goal_color);
+    }
+
+    #[test]
+    fn params_change() {
+        let svg = Svg::new()
+            .cell(30)
+            .wall(4)
+            .margin(0)
+            .background(Color::RGB(1, 1, 1))
+            .foreground(Color::RGB(2, 2, 2))
+            .path(vec![(0, 0)])
+            .path_color(Color::RGB(3, 3, 3))
+            .path_width(2.0)
+            .start((0, 0))
+            .start_color(Color::RGB(4, 4, 4))
+            .goal((1, 1))
+            .goal_color(Color::RGB(5, 5, 5));
+
+        assert_eq!(30, svg.cell_size);
+        assert_eq!(4, svg.wall_width);
+        assert_eq!(0, svg.margin);
+        assert_eq!(Color::RGB(1, 1, 1), svg.background_color);
+        assert_eq!(Color::RGB(2, 2, 2), svg.foreground_color);
+        assert_eq!(vec![(0, 0)], svg.path);
+        assert_eq!(Color::RGB(3, 3, 3), svg.path_color);
+        assert_eq!(1.0, svg.path_width);
+        assert_eq!(Some((0, 0)), svg.start);
+        assert_eq!(Color::RGB(4, 4, 4), svg.start_color);
+        assert_eq!(Some((1, 1)), svg.goal);
+        assert_eq!(Color::RGB(5, 5, 5), svg.goal_color);
+    }
+
+    #[test]
+    fn format() {
+        let mut grid = Grid::new(2, 2);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::WEST).unwrap();
+
+        let mut expected = String::new();
+        expected.push_str(r#"<svg xmlns="http://www.w3.org/2000/svg" width="62" height="62" viewBox="0 0 62 62">"#);
+        expected.push('\n');
+        expected.push_str(r#"  <rect width="100%" height="100%" fill="rgb(250, 250, 250)"/>"#);
+        expected.push('\n');
+        expected.push_str(r#"  <path d="M11 11H51M11 31H31M11 51H51M11 11V51M51 11V51" fill="none" stroke="rgb(0, 0, 0)" stroke-width="2" stroke-linecap="square"/>"#);
+        expected.push('\n');
+        expected.push_str("</svg>\n");
+
+        let actual = Svg::new().format(&grid).into_inner();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn format_masked_grid() {
+        let mut mask = Mask::new(2, 2);
+        mask.disable((1, 1));
+        let mut grid = Grid::from_mask(&mask);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+
+        let actual = Svg::new().format(&grid).into_inner();
+
+        assert!(
+            actual.contains(r#"<path d="M11 11H51M31 31H51M11 51H31M11 11V51M31 31V51M51 11V31""#)
+        );
+    }
+
+    #[test]
+    fn format_path_and_markers() {
+        let (grid, path) = generate_maze();
+        let formatter = Svg::new()
+            .cell(10)
+            .wall(2)
+            .margin(0)
+            .path(path)
+            .start((0, 0))
+            .goal((3, 3));
+
+        let actual = formatter.format(&grid).into_inner();
+
+        assert!(
+            actual.contains(r#"<rect x="2" y="2" width="8" height="8" fill="rgb(40, 170, 80)"/>"#)
+        );
+        assert!(actual
+            .contains(r#"<rect x="32" y="32" width="8" height="8" fill="rgb(40, 100, 220)"/>"#));
+        assert!(actual.contains(r#"d="M6 6L6 16L16 16L16 26L6 26L6 36L16 36L26 36L36 36""#));
+        assert!(actual.contains(r#"stroke-width="4""#));
+    }
+
+    #[test]
+    fn format_path_with_gaps() {
+        let grid = Grid::new(3, 1);
+        let formatter = Svg::new()
+            .cell(10)
+            .wall(2)
+            .margin(0)
+            .path(vec![(0, 0), (5, 5), (2, 0)]);
+
+        let actual = formatter.format(&grid).into_inner();
+        assert!(actual.contains(r#"d="M6 6M26 6""#));
+    }
+
+    #[test]
+    fn format_path_through_tunnel_and_wrapped_border() {
+        let mut grid = Grid::new(3, 3);
+        grid.set_topology(Topology::Torus);
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_tunnel((0, 1), Cell::EAST).unwrap();
+        grid.carve_passage((2, 1), Cell::NORTH).unwrap();
+        grid.carve_passage((2, 0), Cell::NORTH).unwrap();
+        let formatter =
+            Svg::new()
+                .cell(10)
+                .wall(2)
+                .margin(0)
+                .path(vec![(0, 1), (2, 1), (2, 0), (2, 2)]);
+
+        let actual = formatter.format(&grid).into_inner();
+        assert!(actual.contains(r#"d="M6 16L26 16L26 6L26 1M26 31L26 26""#));
+        // Both sides of a cell the tunnel runs under are open
+        assert!(actual.contains("M11 1V11M11 21V31M21 1V11M21 21V31"));
+    }
+
+    #[test]
+    fn format_without_path() {
+        let (grid, _) = generate_maze();
+        let actual = Svg::new().format(&grid).into_inner();
+
+        assert_eq!(4, actual.lines().count());
+        assert!(!actual.contains("stroke-linecap=\"round\""));
+    }
+
+    #[test]
+    fn merge_wall_runs() {
+        assert_eq!(vec![(0, 2), (3, 4)], runs(4, |i| i != 2));
+        assert_eq!(vec![(0, 3)], runs(3, |_| true));
+        assert!(runs(3, |_| false).is_empty());
+    }
+}
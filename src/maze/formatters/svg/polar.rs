@@ -0,0 +1,119 @@
+use super::Svg;
+use crate::maze::formatters::{Formatter, StringWrapper};
+use crate::maze::grid::polar::PolarGrid;
+use std::f64::consts::TAU;
+use std::fmt::Write;
+
+/// Returns a point at a given distance from a center in a given direction, where the angle of
+/// zero points up and the angles grow clockwise, rounded to two decimal places
+fn polar_point((cx, cy): (f64, f64), radius: f64, angle: f64) -> (f64, f64) {
+    let round = |value: f64| (value * 100.0).round() / 100.0;
+    (
+        round(cx + radius * angle.sin()),
+        round(cy - radius * angle.cos()),
+    )
+}
+
+impl Svg {
+    fn write_polar_walls(&self, svg: &mut String, grid: &PolarGrid, center: (f64, f64)) {
+        let ring_height = self.cell_size as f64;
+        let mut data = String::new();
+
+        for coords in grid.cells() {
+            let (ring, index) = coords;
+            let len = grid.ring_len(ring) as f64;
+            let (from, to) = (TAU * index as f64 / len, TAU * (index + 1) as f64 / len);
+            let inner = ring_height * ring as f64;
+
+            if let Some(next) = grid.inward(coords) {
+                if !grid.is_linked(coords, next) {
+                    let (x0, y0) = polar_point(center, inner, from);
+                    let (x1, y1) = polar_point(center, inner, to);
+                    write!(data, "M{} {}A{2} {2} 0 0 1 {3} {4}", x0, y0, inner, x1, y1).unwrap();
+                }
+            }
+
+            if let Some(next) = grid.clockwise(coords) {
+                if !grid.is_linked(coords, next) {
+                    let (x0, y0) = polar_point(center, inner, to);
+                    let (x1, y1) = polar_point(center, inner + ring_height, to);
+                    write!(data, "M{} {}L{} {}", x0, y0, x1, y1).unwrap();
+                }
+            }
+        }
+
+        writeln!(
+            svg,
+            r#"  <circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            center.0,
+            center.1,
+            ring_height * grid.rings() as f64,
+            self.foreground_color,
+            self.wall_width
+        )
+        .unwrap();
+
+        if !data.is_empty() {
+            writeln!(
+                svg,
+                r#"  <path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round"/>"#,
+                data, self.foreground_color, self.wall_width
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// An implementation of a formatter for polar mazes
+///
+/// The walls are drawn as arcs and radial lines, with the rings a [cell](Svg::cell) size apart.
+/// The path and markers are not supported yet, so they are ignored.
+impl Formatter<StringWrapper, PolarGrid> for Svg {
+    /// Converts a given polar grid into an SVG document and returns a [StringWrapper] over it
+    fn format(&self, grid: &PolarGrid) -> StringWrapper {
+        let size = self.cell_size * grid.rings() * 2 + self.wall_width + self.margin * 2;
+        let center = (size as f64 / 2.0, size as f64 / 2.0);
+        let mut svg = String::new();
+
+        self.write_header(&mut svg, (size, size));
+        self.write_polar_walls(&mut svg, grid, center);
+
+        svg.push_str("</svg>\n");
+
+        StringWrapper(svg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_polar_grid() {
+        let mut grid = PolarGrid::new(2);
+        for cell in 1..7 {
+            grid.graph_mut().link(0, cell).unwrap();
+        }
+
+        let svg = Svg::new()
+            .cell(10)
+            .wall(2)
+            .margin(4)
+            .format(&grid)
+            .into_inner();
+        let expected = concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="50" height="50" viewBox="0 0 50 50">"#,
+            "\n",
+            r#"  <rect width="100%" height="100%" fill="rgb(250, 250, 250)"/>"#,
+            "\n",
+            r#"  <circle cx="25" cy="25" r="20" fill="none" stroke="rgb(0, 0, 0)" stroke-width="2"/>"#,
+            "\n",
+        );
+
+        assert!(svg.starts_with(expected));
+        assert_eq!(0, svg.matches('A').count());
+        assert_eq!(6, svg.matches('L').count());
+        assert!(svg.contains("M25 15L25 5"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -0,0 +1,180 @@
+use super::errors::LinkError;
+use std::collections::VecDeque;
+
+/// A graph of cells connected by passages
+///
+/// Represents the cells of a maze of any shape, such as a [PolarGrid](crate::maze::PolarGrid), by
+/// their indices. Each cell knows the cells adjacent to it, i.e. the ones a passage can be carved
+/// to, and the cells it's linked with by the carved passages. The algorithms implementing the
+/// [GraphAlgorithm](crate::maze::GraphAlgorithm) trait carve mazes through it.
+#[derive(Clone)]
+pub struct Graph {
+    neighbours: Vec<Vec<usize>>,
+    links: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// Returns a new instance of a graph with no passages carved, where each cell is adjacent to
+    /// the cells at the given indices
+    ///
+    /// The adjacency is expected to be symmetric, i.e. a cell is listed among the neighbours of
+    /// each of its own neighbours.
+    pub(crate) fn new(neighbours: Vec<Vec<usize>>) -> Graph {
+        let links = vec![Vec::new(); neighbours.len()];
+        Graph { neighbours, links }
+    }
+
+    /// Returns the number of cells in a graph
+    pub const fn len(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    /// Returns `true` if a graph has no cells. Otherwise, returns `false`
+    pub const fn is_empty(&self) -> bool {
+        self.neighbours.is_empty()
+    }
+
+    /// Returns the indices of the cells adjacent to a cell with a given index
+    ///
+    /// Returns an empty slice if the index is out of the graph bounds.
+    pub fn neighbours(&self, cell: usize) -> &[usize] {
+        self.neighbours.get(cell).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the indices of the cells a cell with a given index shares a carved passage with
+    ///
+    /// Returns an empty slice if the index is out of the graph bounds.
+    pub fn links(&self, cell: usize) -> &[usize] {
+        self.links.get(cell).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns `true` if there is a passage carved between two cells with the given indices.
+    /// Otherwise, returns `false`
+    pub fn is_linked(&self, cell: usize, other: usize) -> bool {
+        self.links(cell).contains(&other)
+    }
+
+    /// Returns `true` if any passage is carved out of a cell with a given index. Otherwise,
+    /// returns `false`
+    pub fn is_visited(&self, cell: usize) -> bool {
+        !self.links(cell).is_empty()
+    }
+
+    /// Carves a passage between two adjacent cells with the given indices
+    ///
+    /// # Errors
+    /// Returns a [LinkError] if the cells are not adjacent or already linked.
+    pub fn link(&mut self, cell: usize, other: usize) -> Result<(), LinkError> {
+        let error = |reason: &str| LinkError {
+            cells: (cell, other),
+            reason: reason.to_string(),
+        };
+
+        if !self.neighbours(cell).contains(&other) {
+            return Err(error("Cells are not adjacent"));
+        }
+        if self.is_linked(cell, other) {
+            return Err(error("Cells are already linked"));
+        }
+
+        self.links[cell].push(other);
+        self.links[other].push(cell);
+        Ok(())
+    }
+
+    /// Returns the number of passages carved in a graph
+    pub fn passage_count(&self) -> usize {
+        self.links.iter().map(Vec::len).sum::<usize>() / 2
+    }
+
+    /// Returns the number of passages on the shortest path from a cell with a given index to
+    /// every cell of a graph, or `None` for the cells unreachable from it
+    pub fn distances(&self, from: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue = VecDeque::new();
+        if from < self.len() {
+            distances[from] = Some(0);
+            queue.push_back(from);
+        }
+
+        while let Some(cell) = queue.pop_front() {
+            let distance = distances[cell].map(|d| d + 1);
+            for &next in self.links(cell) {
+                if distances[next].is_none() {
+                    distances[next] = distance;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Returns `true` if all the cells of a graph are reachable from each other. Otherwise,
+    /// returns `false`
+    pub fn is_valid(&self) -> bool {
+        !self.is_empty() && self.distances(0).iter().all(Option::is_some)
+    }
+
+    /// Returns `true` if a graph is valid and there is exactly one path between any two cells.
+    /// Otherwise, returns `false`
+    pub fn is_perfect(&self) -> bool {
+        // A connected graph is a tree if and only if it has one edge less than vertices
+        self.is_valid() && self.passage_count() + 1 == self.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_graph() -> Graph {
+        // A square of four cells, each adjacent to the two next to it
+        Graph::new(vec![vec![1, 3], vec![0, 2], vec![1, 3], vec![2, 0]])
+    }
+
+    #[test]
+    fn link() {
+        let mut graph = generate_graph();
+
+        graph.link(0, 1).unwrap();
+        assert!(graph.is_linked(0, 1));
+        assert!(graph.is_linked(1, 0));
+        assert!(graph.is_visited(0));
+        assert!(!graph.is_visited(2));
+        assert_eq!(1, graph.passage_count());
+
+        assert_eq!(
+            "Cannot link cells. Reason: Cells are already linked. Cells: 1 and 0",
+            graph.link(1, 0).unwrap_err().to_string()
+        );
+        assert!(graph.link(0, 2).is_err());
+        assert!(graph.link(0, 4).is_err());
+    }
+
+    #[test]
+    fn distances() {
+        let mut graph = generate_graph();
+        graph.link(0, 1).unwrap();
+        graph.link(1, 2).unwrap();
+
+        assert_eq!(vec![Some(0), Some(1), Some(2), None], graph.distances(0));
+        assert_eq!(vec![None; 4], graph.distances(4));
+    }
+
+    #[test]
+    fn perfect() {
+        let mut graph = generate_graph();
+        graph.link(0, 1).unwrap();
+        graph.link(1, 2).unwrap();
+        assert!(!graph.is_valid());
+
+        graph.link(2, 3).unwrap();
+        assert!(graph.is_perfect());
+
+        graph.link(3, 0).unwrap();
+        assert!(graph.is_valid());
+        assert!(!graph.is_perfect());
+        assert!(!Graph::new(vec![]).is_valid());
+    }
+}
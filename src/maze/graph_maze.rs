@@ -0,0 +1,508 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fmt;
+
+use super::{
+    algorithms::{GraphAlgorithm, RecursiveBacktracking},
+    errors::MazeSaveError,
+    formatters::{AsciiNarrow, Formatter, Saveable, StringWrapper},
+    graph::Graph,
+    grid::{
+        delta::DeltaGrid, hex::HexGrid, layered::LayeredGrid, polar::PolarGrid,
+        upsilon::UpsilonGrid,
+    },
+};
+
+pub(crate) use sealed::GraphGridMut;
+
+mod sealed {
+    use crate::maze::graph::Graph;
+
+    /// Gives the builders mutable access to the [Graph] of a grid, so that only the algorithms
+    /// carve passages through it
+    pub trait GraphGridMut {
+        /// Returns a mutable ref to a graph of the grid cells
+        fn graph_mut(&mut self) -> &mut Graph;
+    }
+}
+
+/// A grid of cells connected through a [Graph], which a [GraphMaze] is made of
+///
+/// The [HexGrid], [DeltaGrid], [UpsilonGrid], [PolarGrid] and [LayeredGrid] implement it, so each
+/// of them can be shaped into a maze by a [GraphMazeBuilder] with any
+/// [GraphAlgorithm](crate::maze::GraphAlgorithm).
+pub trait GraphGrid: GraphGridMut {
+    /// The coordinates of a cell
+    type Coords: Copy;
+
+    /// The dimensions of a grid, such as a width and height or a number of rings
+    type Size: Copy;
+
+    /// The dimensions of a grid a builder starts with
+    const DEFAULT_SIZE: Self::Size;
+
+    /// Returns a new instance of a grid with the given dimensions and no passages carved
+    fn with_size(size: Self::Size) -> Self;
+
+    /// Returns a graph of the grid cells
+    fn graph(&self) -> &Graph;
+
+    /// Returns the coordinates of a cell with a given index in the graph
+    fn coords(&self, cell: usize) -> Self::Coords;
+
+    /// Returns the coordinates of all the cells a cell at the given coordinates shares a carved
+    /// passage with, or an empty list if the coordinates are out of the grid bounds
+    fn links(&self, coords: Self::Coords) -> Vec<Self::Coords>;
+}
+
+/// A [GraphGrid] of rows of cells, where each cell is a set of the directions of the passages
+/// carved out of it
+pub trait CellGrid: GraphGrid {
+    /// The set of directions of a cell
+    type Cell;
+
+    /// Returns a grid width in a number of cells in a row
+    fn width(&self) -> usize;
+
+    /// Returns a grid height in a number of rows
+    fn height(&self) -> usize;
+
+    /// Returns `true` if the coordinates are within the grid bounds. Otherwise, returns `false`
+    fn contains(&self, coords: Self::Coords) -> bool;
+
+    /// Returns the passages carved out of a cell at the given coordinates
+    fn get_cell(&self, coords: Self::Coords) -> Self::Cell;
+
+    /// Returns `true` if a passage is carved out of a cell at the given coordinates in a given
+    /// direction. Otherwise, returns `false`
+    fn is_carved(&self, coords: Self::Coords, direction: Self::Cell) -> bool;
+}
+
+/// A maze of any shape, carved through a [GraphGrid]
+///
+/// The mazes of each shape have their own aliases, such as [HexMaze] or [PolarMaze], and the
+/// details of how the cells are arranged are described by their grids.
+pub struct GraphMaze<G> {
+    grid: G,
+}
+
+/// A hexagonal (sigma) maze, where each cell is a hexagon with up to six passages carved out of it,
+/// see [HexGrid]
+pub type HexMaze = GraphMaze<HexGrid>;
+
+/// A triangular (delta) maze, where each cell is a triangle with up to three passages carved out of
+/// it, see [DeltaGrid]
+///
+/// A maze of more than two rows must be at least two triangles wide, see [DeltaGrid::new].
+pub type DeltaMaze = GraphMaze<DeltaGrid>;
+
+/// An upsilon maze made of octagons with up to eight passages carved out of them and squares with
+/// up to four passages in between, see [UpsilonGrid]
+pub type UpsilonMaze = GraphMaze<UpsilonGrid>;
+
+/// A polar (circular) maze made of concentric rings of cells around a single cell in the center,
+/// see [PolarGrid]
+pub type PolarMaze = GraphMaze<PolarGrid>;
+
+/// A layered (3D) maze of several levels stacked on top of each other and connected by stairs,
+/// see [LayeredGrid]
+pub type LayeredMaze = GraphMaze<LayeredGrid>;
+
+impl<G: GraphGrid> GraphMaze<G> {
+    /// Returns a new instance of a maze with the given dimensions and no passages carved
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let hex = HexMaze::new((4, 3));
+    /// let polar = PolarMaze::new(5);
+    /// let layered = LayeredMaze::new((4, 3, 2));
+    ///
+    /// assert_eq!(12, hex.cells().count());
+    /// assert_eq!(24, layered.cells().count());
+    /// assert!(!polar.is_valid());
+    /// ```
+    pub fn new(size: G::Size) -> GraphMaze<G> {
+        GraphMaze {
+            grid: G::with_size(size),
+        }
+    }
+
+    /// Returns a ref to a grid
+    pub const fn grid(&self) -> &G {
+        &self.grid
+    }
+
+    /// Returns a mutable ref to a grid
+    pub(crate) const fn get_grid_mut(&mut self) -> &mut G {
+        &mut self.grid
+    }
+
+    /// Returns the coordinates of all the cells reachable from a cell at the given coordinates in
+    /// a single step, i.e. the cells it shares a carved passage with, in the order of the
+    /// directions of a grid
+    ///
+    /// The list is empty if the coordinates are out of the maze bounds.
+    pub fn neighbours(&self, coords: G::Coords) -> Vec<G::Coords> {
+        self.grid.links(coords)
+    }
+
+    /// Returns an iterator over the coordinates of all the cells in a maze in the order of their
+    /// indices in the graph, e.g. row by row or ring by ring from the center
+    pub fn cells(&self) -> impl Iterator<Item = G::Coords> + '_ {
+        (0..self.grid.graph().len()).map(|cell| self.grid.coords(cell))
+    }
+
+    /// Returns an iterator over all the passages in a maze
+    ///
+    /// Each passage is yielded once as a pair of the coordinates of the cells it connects, with
+    /// the cell that goes first in the order of [cells](GraphMaze::cells) going first.
+    pub fn passages(&self) -> impl Iterator<Item = (G::Coords, G::Coords)> + '_ {
+        let graph = self.grid.graph();
+        (0..graph.len()).flat_map(move |cell| {
+            graph
+                .links(cell)
+                .iter()
+                .filter(move |next| cell < **next)
+                .map(move |next| (self.grid.coords(cell), self.grid.coords(*next)))
+        })
+    }
+
+    /// Returns `true` if a maze is valid, i.e. all the cells are reachable from each other.
+    /// Otherwise, returns `false`
+    pub fn is_valid(&self) -> bool {
+        self.grid.graph().is_valid()
+    }
+
+    /// Returns `true` if a maze is perfect, i.e. it's valid and there is exactly one path between
+    /// any two cells. Otherwise, returns `false`
+    pub fn is_perfect(&self) -> bool {
+        self.grid.graph().is_perfect()
+    }
+
+    /// Saves the maze to a file at the specified path using the provided formatter
+    ///
+    /// The [Image](crate::maze::Image) formatter supports the mazes of all shapes, while the
+    /// [AsciiNarrow](crate::maze::AsciiNarrow) formatter supports the hexagonal and layered mazes,
+    /// the [AsciiBroad](crate::maze::AsciiBroad) and [GameMap](crate::maze::GameMap) formatters
+    /// support the layered mazes and the [Svg](crate::maze::Svg) formatter supports the polar
+    /// mazes.
+    ///
+    /// # Errors
+    /// This function can return a `MazeSaveError` if the file could not be written.
+    pub fn save<F, T>(&self, path: &str, formatter: F) -> Result<String, MazeSaveError>
+    where
+        F: Formatter<T, G>,
+        T: Saveable,
+    {
+        let data = formatter.format(&self.grid);
+        Saveable::save(&data, path)
+    }
+
+    /// Returns a formatted maze using the provided formatter
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = PolarMazeBuilder::new().rings(5).build();
+    /// let svg = maze.format(Svg::new()).into_inner();
+    ///
+    /// assert!(svg.starts_with("<svg"));
+    /// ```
+    pub fn format<F, T>(&self, formatter: F) -> T
+    where
+        F: Formatter<T, G>,
+        T: Saveable,
+    {
+        formatter.format(&self.grid)
+    }
+}
+
+impl<G: CellGrid> GraphMaze<G> {
+    /// Returns a maze width in a number of cells in a row
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    /// Returns a maze height in a number of rows
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// Returns the passages carved out of a cell at the given coordinates
+    ///
+    /// Returns `None` if the coordinates are out of the maze bounds.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = HexMazeBuilder::new().width(4).height(4).build();
+    /// let cell = maze.cell((1, 1)).unwrap();
+    ///
+    /// assert!(!cell.is_empty());
+    /// assert_eq!(None, maze.cell((4, 4)));
+    /// ```
+    pub fn cell(&self, coords: G::Coords) -> Option<G::Cell> {
+        self.grid
+            .contains(coords)
+            .then(|| self.grid.get_cell(coords))
+    }
+
+    /// Returns `true` if a passage is carved out of a cell at the given coordinates in a given
+    /// direction. Otherwise, returns `false`
+    pub fn is_carved(&self, coords: G::Coords, direction: G::Cell) -> bool {
+        self.grid.is_carved(coords, direction)
+    }
+}
+
+impl PolarMaze {
+    /// Returns a number of rings
+    pub const fn rings(&self) -> usize {
+        self.grid.rings()
+    }
+
+    /// Returns a number of cells in a ring with a given index, or zero if there is no such ring
+    pub fn ring_len(&self, ring: usize) -> usize {
+        self.grid.ring_len(ring)
+    }
+}
+
+impl LayeredMaze {
+    /// Returns a number of maze levels
+    pub const fn levels(&self) -> usize {
+        self.grid.levels()
+    }
+}
+
+/// An implementation of the [Display](fmt::Display) trait for printing a maze as the
+/// [AsciiNarrow] text, for the shapes the formatter supports
+impl<G> fmt::Display for GraphMaze<G>
+where
+    AsciiNarrow: Formatter<StringWrapper, G>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", AsciiNarrow.format(&self.grid).into_inner())
+    }
+}
+
+/// A builder for constructing a maze of any shape step by step
+///
+/// The builders of each shape have their own aliases, such as [HexMazeBuilder] or
+/// [PolarMazeBuilder], with the setters of the dimensions of that shape.
+pub struct GraphMazeBuilder<G: GraphGrid> {
+    size: G::Size,
+    algorithm: Box<dyn GraphAlgorithm>,
+    seed: Option<u64>,
+}
+
+/// A builder of a [HexMaze]
+pub type HexMazeBuilder = GraphMazeBuilder<HexGrid>;
+
+/// A builder of a [DeltaMaze]
+pub type DeltaMazeBuilder = GraphMazeBuilder<DeltaGrid>;
+
+/// A builder of an [UpsilonMaze]
+pub type UpsilonMazeBuilder = GraphMazeBuilder<UpsilonGrid>;
+
+/// A builder of a [PolarMaze]
+pub type PolarMazeBuilder = GraphMazeBuilder<PolarGrid>;
+
+/// A builder of a [LayeredMaze]
+pub type LayeredMazeBuilder = GraphMazeBuilder<LayeredGrid>;
+
+impl<G: GraphGrid> GraphMazeBuilder<G> {
+    /// Returns a new instance of a builder with the default dimensions and algorithm
+    pub fn new() -> Self {
+        GraphMazeBuilder {
+            size: G::DEFAULT_SIZE,
+            algorithm: Box::new(RecursiveBacktracking),
+            seed: None,
+        }
+    }
+
+    /// Sets an algorithm for generating a maze and returns itself
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = PolarMazeBuilder::new()
+    ///     .algorithm(Box::new(Kruskal))
+    ///     .build();
+    ///
+    /// assert!(maze.is_perfect());
+    /// ```
+    pub fn algorithm(mut self, algorithm: Box<dyn GraphAlgorithm>) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Sets a seed value for deterministic generation and returns itself
+    pub const fn seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Builds a maze and returns a resulting object of the generated maze
+    pub fn build(mut self) -> GraphMaze<G> {
+        let mut maze = GraphMaze::<G>::new(self.size);
+        let mut rng = match self.seed {
+            Some(val) => StdRng::seed_from_u64(val),
+            None => StdRng::from_os_rng(),
+        };
+        self.algorithm
+            .carve(maze.get_grid_mut().graph_mut(), &mut rng);
+        maze
+    }
+}
+
+impl<G: GraphGrid<Size = (usize, usize)>> GraphMazeBuilder<G> {
+    /// Sets a maze width and returns itself
+    pub const fn width(mut self, width: usize) -> Self {
+        self.size.0 = width;
+        self
+    }
+
+    /// Sets a maze height and returns itself
+    pub const fn height(mut self, height: usize) -> Self {
+        self.size.1 = height;
+        self
+    }
+}
+
+impl PolarMazeBuilder {
+    /// Sets a number of rings, the center cell included, and returns itself
+    pub const fn rings(mut self, rings: usize) -> Self {
+        self.size = rings;
+        self
+    }
+}
+
+impl LayeredMazeBuilder {
+    /// Sets a maze width and returns itself
+    pub const fn width(mut self, width: usize) -> Self {
+        self.size.0 = width;
+        self
+    }
+
+    /// Sets a maze height and returns itself
+    pub const fn height(mut self, height: usize) -> Self {
+        self.size.1 = height;
+        self
+    }
+
+    /// Sets a number of maze levels and returns itself
+    pub const fn levels(mut self, levels: usize) -> Self {
+        self.size.2 = levels;
+        self
+    }
+}
+
+impl<G: GraphGrid> Default for GraphMazeBuilder<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::grid::{hex::HexCell, layered::LayeredCell};
+    use std::fmt::Debug;
+
+    /// Checks that a maze is perfect and every passage is listed by the cells at both of its ends
+    fn assert_passages<G>(maze: &GraphMaze<G>)
+    where
+        G: GraphGrid,
+        G::Coords: PartialEq + Debug,
+    {
+        assert!(maze.is_perfect());
+        assert_eq!(maze.cells().count() - 1, maze.passages().count());
+        for (from, to) in maze.passages() {
+            assert!(maze.neighbours(from).contains(&to));
+            assert!(maze.neighbours(to).contains(&from));
+        }
+    }
+
+    #[test]
+    fn build() {
+        let hex = HexMazeBuilder::default().build();
+        let delta = DeltaMazeBuilder::default().build();
+        let upsilon = UpsilonMazeBuilder::default().build();
+        let polar = PolarMazeBuilder::default().build();
+        let layered = LayeredMazeBuilder::default().build();
+
+        assert_eq!((10, 10), (hex.width(), hex.height()));
+        assert_eq!((10, 10), (delta.width(), delta.height()));
+        assert_eq!((10, 10), (upsilon.width(), upsilon.height()));
+        assert_eq!(10, polar.rings());
+        assert_eq!(
+            (10, 10, 3),
+            (layered.width(), layered.height(), layered.levels())
+        );
+
+        assert_passages(&hex);
+        assert_passages(&delta);
+        assert_passages(&upsilon);
+        assert_passages(&polar);
+        assert_passages(&layered);
+        // The levels are connected by stairs
+        assert!(layered
+            .cells()
+            .any(|coords| layered.is_carved(coords, LayeredCell::UP)));
+    }
+
+    #[test]
+    fn build_seeded() {
+        let build = || PolarMazeBuilder::new().rings(6).seed(Some(7)).build();
+        let passages: Vec<_> = build().passages().collect();
+
+        assert_eq!(passages, build().passages().collect::<Vec<_>>());
+        assert_passages(&build());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least two triangles wide")]
+    fn build_narrow_delta() {
+        DeltaMazeBuilder::new().width(1).height(5).build();
+    }
+
+    #[test]
+    fn neighbours_out_of_maze() {
+        let hex = HexMazeBuilder::new().width(5).height(4).build();
+        let layered = LayeredMazeBuilder::new()
+            .width(4)
+            .height(3)
+            .levels(2)
+            .build();
+
+        assert!(hex.neighbours((5, 0)).is_empty());
+        assert_eq!(None, hex.cell((5, 0)));
+        assert!(layered.neighbours((0, 0, 2)).is_empty());
+        assert_eq!(None, layered.cell((0, 0, 2)));
+    }
+
+    #[test]
+    fn empty_maze() {
+        let maze = PolarMaze::new(3);
+
+        assert_eq!(6, maze.ring_len(1));
+        assert!(!maze.is_valid());
+        assert_eq!(None, maze.passages().next());
+    }
+
+    #[test]
+    fn display() {
+        let mut maze = HexMaze::new((2, 1));
+        maze.get_grid_mut().graph_mut().link(0, 1).unwrap();
+
+        assert_eq!(" __    \n/  \\__ \n\\__   \\\n   \\__/\n", maze.to_string());
+        assert!(maze.is_carved((0, 0), HexCell::SOUTH_EAST));
+
+        let maze = LayeredMaze::new((2, 1, 2));
+        assert_eq!(" ___     ___ \n|_|_|   |_|_|\n", maze.to_string());
+    }
+}
@@ -1,19 +1,42 @@
 use bitflags::bitflags;
 
 bitflags! {
-    #[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    /// A set of directions of a maze cell
+    ///
+    /// Depending on the context, the set bits are either passages carved out of a cell (see
+    /// [OrthogonalMaze::cell](crate::maze::OrthogonalMaze::cell)) or walls that remain around it
+    /// (see [OrthogonalMaze::walls](crate::maze::OrthogonalMaze::walls))
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
     pub struct Cell: u8 {
+        /// The Northern side of a cell
         const NORTH = 0b0001;
+        /// The Southern side of a cell
         const SOUTH = 0b0010;
+        /// The Eastern side of a cell
         const EAST =  0b0100;
+        /// The Western side of a cell
         const WEST =  0b1000;
     }
 }
 
+impl Cell {
+    /// Returns the opposite direction of a single direction, e.g. South for North
+    pub(crate) const fn opposite(self) -> Cell {
+        match self {
+            Cell::NORTH => Cell::SOUTH,
+            Cell::SOUTH => Cell::NORTH,
+            Cell::EAST => Cell::WEST,
+            Cell::WEST => Cell::EAST,
+            _ => unreachable!(),
+        }
+    }
+}
+
 #[derive(Default, Copy, Clone)]
 pub struct CellStatus {
     visited: bool,
     marked: bool,
+    disabled: bool,
 }
 
 impl CellStatus {
@@ -25,11 +48,23 @@ impl CellStatus {
         self.marked
     }
 
-    pub fn visit(&mut self) {
+    pub const fn disabled(&self) -> bool {
+        self.disabled
+    }
+
+    pub const fn visit(&mut self) {
         self.visited = true;
     }
 
-    pub fn mark(&mut self) {
+    pub const fn mark(&mut self) {
         self.marked = true;
     }
+
+    pub const fn disable(&mut self) {
+        self.disabled = true;
+    }
+
+    pub const fn enable(&mut self) {
+        self.disabled = false;
+    }
 }
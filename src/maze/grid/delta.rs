@@ -0,0 +1,256 @@
+use crate::maze::graph::Graph;
+use crate::maze::graph_maze::{CellGrid, GraphGrid, GraphGridMut};
+use crate::utils::types::Coords;
+use bitflags::bitflags;
+
+bitflags! {
+    /// A set of directions of a triangular maze cell
+    ///
+    /// Each cell is a triangle with a horizontal base and two slanted sides. Depending on the
+    /// context, the set bits are either passages carved out of a cell (see
+    /// [DeltaMaze::cell](crate::maze::DeltaMaze::cell)) or a side to move across.
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct DeltaCell: u8 {
+        /// The Western slanted side of a cell
+        const WEST = 0b001;
+        /// The Eastern slanted side of a cell
+        const EAST = 0b010;
+        /// The horizontal side of a cell, which is at the bottom of a triangle pointing up and at
+        /// the top of a triangle pointing down
+        const BASE = 0b100;
+    }
+}
+
+/// A grid of triangular cells that algorithms carve passages through
+///
+/// The triangles alternate between pointing up and down in every row, starting with the one
+/// pointing up in the top left corner, so a cell at `(x, y)` points up if `x + y` is even. Each
+/// cell is adjacent to the cells to the West and East of it, as well as to the cell below a
+/// triangle pointing up or above a triangle pointing down.
+///
+/// The cells are addressed by [Coords] of their columns and rows, while the passages are carved
+/// through a [Graph] of them, see [GraphAlgorithm](crate::maze::GraphAlgorithm).
+pub struct DeltaGrid {
+    width: usize,
+    height: usize,
+    graph: Graph,
+}
+
+impl DeltaGrid {
+    /// Returns a new instance of a grid with a given width and height and no passages carved
+    ///
+    /// # Panics
+    /// Panics if the grid is a single triangle wide and more than two rows tall, since such a
+    /// grid falls apart into pairs of rows that no passage can join.
+    pub fn new(width: usize, height: usize) -> DeltaGrid {
+        assert!(
+            width != 1 || height <= 2,
+            "a delta grid of more than two rows must be at least two triangles wide"
+        );
+
+        let mut grid = DeltaGrid {
+            width,
+            height,
+            graph: Graph::new(Vec::new()),
+        };
+        let neighbours = grid
+            .cells()
+            .map(|coords| {
+                DeltaCell::all()
+                    .iter()
+                    .filter_map(|dir| grid.neighbour(coords, dir))
+                    .map(|next| grid.index(next))
+                    .collect()
+            })
+            .collect();
+        grid.graph = Graph::new(neighbours);
+        grid
+    }
+
+    /// Returns a grid width in a number of triangles in a row
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns a grid height in a number of rows
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if the coordinates are within the grid bounds. Otherwise, returns `false`
+    pub const fn contains(&self, (x, y): Coords) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Returns `true` if a cell at the given coordinates is a triangle pointing up. Otherwise, if
+    /// it points down, returns `false`
+    pub const fn is_upright(&self, (x, y): Coords) -> bool {
+        (x + y) % 2 == 0
+    }
+
+    /// Returns an iterator over the coordinates of all the cells, row by row
+    pub fn cells(&self) -> impl Iterator<Item = Coords> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Returns the coordinates of a cell adjacent to a cell at the given coordinates across a
+    /// given side, or `None` if there is no such cell within the grid bounds
+    pub fn neighbour(&self, coords: Coords, direction: DeltaCell) -> Option<Coords> {
+        let (x, y) = coords;
+        let next = match direction {
+            DeltaCell::WEST => (x.checked_sub(1)?, y),
+            DeltaCell::EAST => (x + 1, y),
+            DeltaCell::BASE if self.is_upright(coords) => (x, y + 1),
+            DeltaCell::BASE => (x, y.checked_sub(1)?),
+            _ => return None,
+        };
+
+        (self.contains(coords) && self.contains(next)).then_some(next)
+    }
+
+    /// Returns `true` if a passage is carved out of a cell at the given coordinates across a given
+    /// side. Otherwise, returns `false`
+    pub fn is_carved(&self, coords: Coords, direction: DeltaCell) -> bool {
+        self.neighbour(coords, direction)
+            .is_some_and(|next| self.graph.is_linked(self.index(coords), self.index(next)))
+    }
+
+    /// Returns the passages carved out of a cell at the given coordinates
+    pub fn get_cell(&self, coords: Coords) -> DeltaCell {
+        DeltaCell::all()
+            .iter()
+            .filter(|dir| self.is_carved(coords, *dir))
+            .collect()
+    }
+
+    /// Returns the coordinates of all the cells a cell at the given coordinates shares a carved
+    /// passage with, in the West, East and base order
+    pub fn links(&self, coords: Coords) -> Vec<Coords> {
+        DeltaCell::all()
+            .iter()
+            .filter(|dir| self.is_carved(coords, *dir))
+            .filter_map(|dir| self.neighbour(coords, dir))
+            .collect()
+    }
+
+    /// Returns a graph of the grid cells
+    pub const fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns a mutable ref to a graph of the grid cells
+    pub(crate) const fn graph_mut(&mut self) -> &mut Graph {
+        &mut self.graph
+    }
+
+    /// Returns an index of a cell at the given coordinates in the graph
+    pub(crate) const fn index(&self, (x, y): Coords) -> usize {
+        y * self.width + x
+    }
+
+    /// Returns the coordinates of a cell with a given index in the graph
+    pub(crate) const fn coords(&self, cell: usize) -> Coords {
+        (cell % self.width, cell / self.width)
+    }
+}
+
+impl GraphGrid for DeltaGrid {
+    type Coords = Coords;
+    type Size = (usize, usize);
+
+    const DEFAULT_SIZE: Self::Size = (10, 10);
+
+    fn with_size((width, height): Self::Size) -> Self {
+        DeltaGrid::new(width, height)
+    }
+
+    fn graph(&self) -> &Graph {
+        DeltaGrid::graph(self)
+    }
+
+    fn coords(&self, cell: usize) -> Coords {
+        DeltaGrid::coords(self, cell)
+    }
+
+    fn links(&self, coords: Coords) -> Vec<Coords> {
+        DeltaGrid::links(self, coords)
+    }
+}
+
+impl CellGrid for DeltaGrid {
+    type Cell = DeltaCell;
+
+    fn width(&self) -> usize {
+        DeltaGrid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        DeltaGrid::height(self)
+    }
+
+    fn contains(&self, coords: Coords) -> bool {
+        DeltaGrid::contains(self, coords)
+    }
+
+    fn get_cell(&self, coords: Coords) -> DeltaCell {
+        DeltaGrid::get_cell(self, coords)
+    }
+
+    fn is_carved(&self, coords: Coords, direction: DeltaCell) -> bool {
+        DeltaGrid::is_carved(self, coords, direction)
+    }
+}
+
+impl GraphGridMut for DeltaGrid {
+    fn graph_mut(&mut self) -> &mut Graph {
+        DeltaGrid::graph_mut(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours() {
+        let grid = DeltaGrid::new(3, 2);
+
+        assert!(grid.is_upright((0, 0)));
+        assert!(!grid.is_upright((1, 0)));
+        assert_eq!(Some((0, 1)), grid.neighbour((0, 0), DeltaCell::BASE));
+        assert_eq!(None, grid.neighbour((1, 0), DeltaCell::BASE));
+        assert_eq!(None, grid.neighbour((1, 1), DeltaCell::BASE));
+        assert_eq!(Some((0, 0)), grid.neighbour((0, 1), DeltaCell::BASE));
+        assert_eq!(None, grid.neighbour((0, 0), DeltaCell::WEST));
+        assert_eq!(None, grid.neighbour((2, 0), DeltaCell::EAST));
+        assert_eq!(Some((2, 1)), grid.neighbour((1, 1), DeltaCell::EAST));
+        assert_eq!(None, grid.neighbour((1, 1), DeltaCell::all()));
+
+        assert_eq!(2, grid.graph().neighbours(grid.index((1, 1))).len());
+    }
+
+    #[test]
+    fn narrow_grid() {
+        let grid = DeltaGrid::new(1, 2);
+        assert_eq!(Some((0, 1)), grid.neighbour((0, 0), DeltaCell::BASE));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least two triangles wide")]
+    fn narrow_grid_too_tall() {
+        DeltaGrid::new(1, 3);
+    }
+
+    #[test]
+    fn links() {
+        let mut grid = DeltaGrid::new(2, 2);
+        grid.graph_mut().link(0, 1).unwrap();
+        grid.graph_mut().link(0, 2).unwrap();
+
+        assert_eq!(DeltaCell::EAST | DeltaCell::BASE, grid.get_cell((0, 0)));
+        assert_eq!(DeltaCell::WEST, grid.get_cell((1, 0)));
+        assert_eq!(vec![(1, 0), (0, 1)], grid.links((0, 0)));
+        assert_eq!((1, 1), grid.coords(3));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::maze::graph::Graph;
+use crate::maze::graph_maze::{CellGrid, GraphGrid, GraphGridMut};
+use crate::utils::types::Coords;
+use bitflags::bitflags;
+
+bitflags! {
+    /// A set of directions of a hexagonal maze cell
+    ///
+    /// The cells are hexagons with flat tops, so each of them has six sides. Depending on the
+    /// context, the set bits are either passages carved out of a cell (see
+    /// [HexMaze::cell](crate::maze::HexMaze::cell)) or a side to move across.
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct HexCell: u8 {
+        /// The Northern side of a cell
+        const NORTH = 0b000001;
+        /// The North-Eastern side of a cell
+        const NORTH_EAST = 0b000010;
+        /// The South-Eastern side of a cell
+        const SOUTH_EAST = 0b000100;
+        /// The Southern side of a cell
+        const SOUTH = 0b001000;
+        /// The South-Western side of a cell
+        const SOUTH_WEST = 0b010000;
+        /// The North-Western side of a cell
+        const NORTH_WEST = 0b100000;
+    }
+}
+
+/// A grid of hexagonal cells that algorithms carve passages through
+///
+/// The hexagons have flat tops and are arranged in columns, where every odd column is shifted down
+/// by half a cell. So a cell in an even column shares its North-Eastern side with the cell one row
+/// up in the next column, while a cell in an odd column shares it with the cell in the same row.
+///
+/// The cells are addressed by [Coords] of their columns and rows, while the passages are carved
+/// through a [Graph] of them, see [GraphAlgorithm](crate::maze::GraphAlgorithm).
+pub struct HexGrid {
+    width: usize,
+    height: usize,
+    graph: Graph,
+}
+
+impl HexGrid {
+    /// Returns a new instance of a grid with a given width and height and no passages carved
+    pub fn new(width: usize, height: usize) -> HexGrid {
+        let mut grid = HexGrid {
+            width,
+            height,
+            graph: Graph::new(Vec::new()),
+        };
+        let neighbours = grid
+            .cells()
+            .map(|coords| {
+                HexCell::all()
+                    .iter()
+                    .filter_map(|dir| grid.neighbour(coords, dir))
+                    .map(|next| grid.index(next))
+                    .collect()
+            })
+            .collect();
+        grid.graph = Graph::new(neighbours);
+        grid
+    }
+
+    /// Returns a grid width in a number of columns
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns a grid height in a number of rows
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if the coordinates are within the grid bounds. Otherwise, returns `false`
+    pub const fn contains(&self, (x, y): Coords) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Returns an iterator over the coordinates of all the cells, row by row
+    pub fn cells(&self) -> impl Iterator<Item = Coords> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Returns the coordinates of a cell adjacent to a cell at the given coordinates across a
+    /// given side, or `None` if there is no such cell within the grid bounds
+    pub fn neighbour(&self, coords: Coords, direction: HexCell) -> Option<Coords> {
+        let (x, y) = coords;
+        // Every odd column is shifted down, so its diagonal neighbours are a row lower
+        let shift = isize::from(x % 2 == 1);
+        let (dx, dy) = match direction {
+            HexCell::NORTH => (0, -1),
+            HexCell::SOUTH => (0, 1),
+            HexCell::NORTH_EAST => (1, shift - 1),
+            HexCell::SOUTH_EAST => (1, shift),
+            HexCell::NORTH_WEST => (-1, shift - 1),
+            HexCell::SOUTH_WEST => (-1, shift),
+            _ => return None,
+        };
+
+        let next = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        (self.contains(coords) && self.contains(next)).then_some(next)
+    }
+
+    /// Returns `true` if a passage is carved out of a cell at the given coordinates across a given
+    /// side. Otherwise, returns `false`
+    pub fn is_carved(&self, coords: Coords, direction: HexCell) -> bool {
+        self.neighbour(coords, direction)
+            .is_some_and(|next| self.graph.is_linked(self.index(coords), self.index(next)))
+    }
+
+    /// Returns the passages carved out of a cell at the given coordinates
+    pub fn get_cell(&self, coords: Coords) -> HexCell {
+        HexCell::all()
+            .iter()
+            .filter(|dir| self.is_carved(coords, *dir))
+            .collect()
+    }
+
+    /// Returns the coordinates of all the cells a cell at the given coordinates shares a carved
+    /// passage with, clockwise from the North
+    pub fn links(&self, coords: Coords) -> Vec<Coords> {
+        HexCell::all()
+            .iter()
+            .filter(|dir| self.is_carved(coords, *dir))
+            .filter_map(|dir| self.neighbour(coords, dir))
+            .collect()
+    }
+
+    /// Returns a graph of the grid cells
+    pub const fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns a mutable ref to a graph of the grid cells
+    pub(crate) const fn graph_mut(&mut self) -> &mut Graph {
+        &mut self.graph
+    }
+
+    /// Returns an index of a cell at the given coordinates in the graph
+    pub(crate) const fn index(&self, (x, y): Coords) -> usize {
+        y * self.width + x
+    }
+
+    /// Returns the coordinates of a cell with a given index in the graph
+    pub(crate) const fn coords(&self, cell: usize) -> Coords {
+        (cell % self.width, cell / self.width)
+    }
+}
+
+impl GraphGrid for HexGrid {
+    type Coords = Coords;
+    type Size = (usize, usize);
+
+    const DEFAULT_SIZE: Self::Size = (10, 10);
+
+    fn with_size((width, height): Self::Size) -> Self {
+        HexGrid::new(width, height)
+    }
+
+    fn graph(&self) -> &Graph {
+        HexGrid::graph(self)
+    }
+
+    fn coords(&self, cell: usize) -> Coords {
+        HexGrid::coords(self, cell)
+    }
+
+    fn links(&self, coords: Coords) -> Vec<Coords> {
+        HexGrid::links(self, coords)
+    }
+}
+
+impl CellGrid for HexGrid {
+    type Cell = HexCell;
+
+    fn width(&self) -> usize {
+        HexGrid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        HexGrid::height(self)
+    }
+
+    fn contains(&self, coords: Coords) -> bool {
+        HexGrid::contains(self, coords)
+    }
+
+    fn get_cell(&self, coords: Coords) -> HexCell {
+        HexGrid::get_cell(self, coords)
+    }
+
+    fn is_carved(&self, coords: Coords, direction: HexCell) -> bool {
+        HexGrid::is_carved(self, coords, direction)
+    }
+}
+
+impl GraphGridMut for HexGrid {
+    fn graph_mut(&mut self) -> &mut Graph {
+        HexGrid::graph_mut(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours() {
+        let grid = HexGrid::new(3, 3);
+
+        // A cell in an even column
+        assert_eq!(Some((0, 0)), grid.neighbour((0, 1), HexCell::NORTH));
+        assert_eq!(Some((1, 0)), grid.neighbour((0, 1), HexCell::NORTH_EAST));
+        assert_eq!(Some((1, 1)), grid.neighbour((0, 1), HexCell::SOUTH_EAST));
+        assert_eq!(None, grid.neighbour((0, 1), HexCell::SOUTH_WEST));
+
+        // A cell in an odd column
+        assert_eq!(Some((2, 1)), grid.neighbour((1, 1), HexCell::NORTH_EAST));
+        assert_eq!(Some((2, 2)), grid.neighbour((1, 1), HexCell::SOUTH_EAST));
+        assert_eq!(Some((0, 2)), grid.neighbour((1, 1), HexCell::SOUTH_WEST));
+        assert_eq!(Some((0, 1)), grid.neighbour((1, 1), HexCell::NORTH_WEST));
+        assert_eq!(None, grid.neighbour((1, 2), HexCell::SOUTH_EAST));
+        assert_eq!(None, grid.neighbour((3, 0), HexCell::SOUTH));
+        assert_eq!(None, grid.neighbour((1, 1), HexCell::all()));
+
+        assert_eq!(6, grid.graph().neighbours(grid.index((1, 1))).len());
+        assert_eq!(2, grid.graph().neighbours(grid.index((2, 0))).len());
+    }
+
+    #[test]
+    fn links() {
+        let mut grid = HexGrid::new(2, 2);
+        grid.graph_mut().link(0, 1).unwrap();
+        grid.graph_mut().link(0, 2).unwrap();
+
+        assert_eq!(HexCell::SOUTH | HexCell::SOUTH_EAST, grid.get_cell((0, 0)));
+        assert_eq!(HexCell::NORTH_WEST, grid.get_cell((1, 0)));
+        assert_eq!(vec![(1, 0), (0, 1)], grid.links((0, 0)));
+        assert_eq!((1, 1), grid.coords(3));
+    }
+}
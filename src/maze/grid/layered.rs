@@ -0,0 +1,310 @@
+use super::{cell::Cell, Grid};
+use crate::maze::graph::Graph;
+use crate::maze::graph_maze::{CellGrid, GraphGrid, GraphGridMut};
+use crate::utils::types::{Coords, LayeredCoords};
+use bitflags::bitflags;
+
+bitflags! {
+    /// A set of directions of a layered maze cell
+    ///
+    /// Apart from the four sides of an orthogonal [Cell], each cell has a ceiling and a floor that
+    /// stairs can lead through to the levels above and below it. Depending on the context, the set
+    /// bits are either passages carved out of a cell (see
+    /// [LayeredMaze::cell](crate::maze::LayeredMaze::cell)) or a direction to move in.
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct LayeredCell: u8 {
+        /// The Northern side of a cell
+        const NORTH = 0b000001;
+        /// The Southern side of a cell
+        const SOUTH = 0b000010;
+        /// The Eastern side of a cell
+        const EAST = 0b000100;
+        /// The Western side of a cell
+        const WEST = 0b001000;
+        /// The ceiling of a cell, i.e. the stairs to the level above
+        const UP = 0b010000;
+        /// The floor of a cell, i.e. the stairs to the level below
+        const DOWN = 0b100000;
+    }
+}
+
+/// A grid of levels of rectangular cells stacked on top of each other that algorithms carve
+/// passages through
+///
+/// Each level is an orthogonal grid of the same width and height, where the level `0` is the
+/// ground one. Apart from the cells next to it on its own level, each cell is adjacent to the
+/// cells right above and below it, which are connected by stairs once a passage is carved.
+///
+/// The cells are addressed by [LayeredCoords] of their columns, rows and levels, while the
+/// passages are carved through a [Graph] of them, see [GraphAlgorithm](crate::maze::GraphAlgorithm).
+pub struct LayeredGrid {
+    width: usize,
+    height: usize,
+    levels: usize,
+    graph: Graph,
+}
+
+impl LayeredGrid {
+    /// Returns a new instance of a grid with a given width, height and number of levels and no
+    /// passages carved
+    pub fn new(width: usize, height: usize, levels: usize) -> LayeredGrid {
+        let mut grid = LayeredGrid {
+            width,
+            height,
+            levels,
+            graph: Graph::new(Vec::new()),
+        };
+        let neighbours = grid
+            .cells()
+            .map(|coords| {
+                LayeredCell::all()
+                    .iter()
+                    .filter_map(|dir| grid.neighbour(coords, dir))
+                    .map(|next| grid.index(next))
+                    .collect()
+            })
+            .collect();
+        grid.graph = Graph::new(neighbours);
+        grid
+    }
+
+    /// Returns a grid width in a number of cells
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns a grid height in a number of cells
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns a number of grid levels
+    pub const fn levels(&self) -> usize {
+        self.levels
+    }
+
+    /// Returns `true` if the coordinates are within the grid bounds. Otherwise, returns `false`
+    pub const fn contains(&self, (x, y, z): LayeredCoords) -> bool {
+        x < self.width && y < self.height && z < self.levels
+    }
+
+    /// Returns an iterator over the coordinates of all the cells, level by level and row by row
+    pub fn cells(&self) -> impl Iterator<Item = LayeredCoords> {
+        let (width, height) = (self.width, self.height);
+        (0..self.levels)
+            .flat_map(move |z| (0..height).flat_map(move |y| (0..width).map(move |x| (x, y, z))))
+    }
+
+    /// Returns the coordinates of a cell adjacent to a cell at the given coordinates in a given
+    /// direction, or `None` if there is no such cell within the grid bounds
+    pub fn neighbour(
+        &self,
+        coords: LayeredCoords,
+        direction: LayeredCell,
+    ) -> Option<LayeredCoords> {
+        let (x, y, z) = coords;
+        let next = match direction {
+            LayeredCell::NORTH => (x, y.checked_sub(1)?, z),
+            LayeredCell::SOUTH => (x, y + 1, z),
+            LayeredCell::EAST => (x + 1, y, z),
+            LayeredCell::WEST => (x.checked_sub(1)?, y, z),
+            LayeredCell::UP => (x, y, z + 1),
+            LayeredCell::DOWN => (x, y, z.checked_sub(1)?),
+            _ => return None,
+        };
+
+        (self.contains(coords) && self.contains(next)).then_some(next)
+    }
+
+    /// Returns `true` if a passage is carved out of a cell at the given coordinates in a given
+    /// direction. Otherwise, returns `false`
+    pub fn is_carved(&self, coords: LayeredCoords, direction: LayeredCell) -> bool {
+        self.neighbour(coords, direction)
+            .is_some_and(|next| self.graph.is_linked(self.index(coords), self.index(next)))
+    }
+
+    /// Returns the passages carved out of a cell at the given coordinates
+    pub fn get_cell(&self, coords: LayeredCoords) -> LayeredCell {
+        LayeredCell::all()
+            .iter()
+            .filter(|dir| self.is_carved(coords, *dir))
+            .collect()
+    }
+
+    /// Returns the coordinates of all the cells a cell at the given coordinates shares a carved
+    /// passage with, in the North, South, East, West, up and down order
+    pub fn links(&self, coords: LayeredCoords) -> Vec<LayeredCoords> {
+        LayeredCell::all()
+            .iter()
+            .filter(|dir| self.is_carved(coords, *dir))
+            .filter_map(|dir| self.neighbour(coords, dir))
+            .collect()
+    }
+
+    /// Returns an orthogonal grid of a given level with the passages carved within it, or `None`
+    /// if the level is out of the grid bounds
+    ///
+    /// The stairs to the other levels are left out, see [stairs](LayeredGrid::stairs).
+    pub fn level(&self, level: usize) -> Option<Grid> {
+        if level >= self.levels {
+            return None;
+        }
+
+        let mut grid = Grid::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                for (dir, cell) in [
+                    (LayeredCell::EAST, Cell::EAST),
+                    (LayeredCell::SOUTH, Cell::SOUTH),
+                ] {
+                    if self.is_carved((x, y, level), dir) {
+                        grid.carve_passage((x, y), cell).ok()?;
+                    }
+                }
+            }
+        }
+        Some(grid)
+    }
+
+    /// Returns the coordinates of the cells of a given level with stairs carved out of them along
+    /// with the directions of the stairs, i.e. [UP](LayeredCell::UP), [DOWN](LayeredCell::DOWN) or
+    /// both
+    pub fn stairs(&self, level: usize) -> Vec<(Coords, LayeredCell)> {
+        let vertical = LayeredCell::UP | LayeredCell::DOWN;
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|_| level < self.levels)
+            .map(|(x, y)| ((x, y), self.get_cell((x, y, level)) & vertical))
+            .filter(|(_, stairs)| !stairs.is_empty())
+            .collect()
+    }
+
+    /// Returns a graph of the grid cells
+    pub const fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns a mutable ref to a graph of the grid cells
+    pub(crate) const fn graph_mut(&mut self) -> &mut Graph {
+        &mut self.graph
+    }
+
+    /// Returns an index of a cell at the given coordinates in the graph
+    pub(crate) const fn index(&self, (x, y, z): LayeredCoords) -> usize {
+        (z * self.height + y) * self.width + x
+    }
+
+    /// Returns the coordinates of a cell with a given index in the graph
+    pub(crate) const fn coords(&self, cell: usize) -> LayeredCoords {
+        let area = self.width * self.height;
+        (cell % self.width, cell % area / self.width, cell / area)
+    }
+}
+
+impl GraphGrid for LayeredGrid {
+    type Coords = LayeredCoords;
+    type Size = (usize, usize, usize);
+
+    const DEFAULT_SIZE: Self::Size = (10, 10, 3);
+
+    fn with_size((width, height, levels): Self::Size) -> Self {
+        LayeredGrid::new(width, height, levels)
+    }
+
+    fn graph(&self) -> &Graph {
+        LayeredGrid::graph(self)
+    }
+
+    fn coords(&self, cell: usize) -> LayeredCoords {
+        LayeredGrid::coords(self, cell)
+    }
+
+    fn links(&self, coords: LayeredCoords) -> Vec<LayeredCoords> {
+        LayeredGrid::links(self, coords)
+    }
+}
+
+impl CellGrid for LayeredGrid {
+    type Cell = LayeredCell;
+
+    fn width(&self) -> usize {
+        LayeredGrid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        LayeredGrid::height(self)
+    }
+
+    fn contains(&self, coords: LayeredCoords) -> bool {
+        LayeredGrid::contains(self, coords)
+    }
+
+    fn get_cell(&self, coords: LayeredCoords) -> LayeredCell {
+        LayeredGrid::get_cell(self, coords)
+    }
+
+    fn is_carved(&self, coords: LayeredCoords, direction: LayeredCell) -> bool {
+        LayeredGrid::is_carved(self, coords, direction)
+    }
+}
+
+impl GraphGridMut for LayeredGrid {
+    fn graph_mut(&mut self) -> &mut Graph {
+        LayeredGrid::graph_mut(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours() {
+        let grid = LayeredGrid::new(3, 2, 2);
+
+        assert_eq!(Some((1, 0, 1)), grid.neighbour((1, 0, 0), LayeredCell::UP));
+        assert_eq!(None, grid.neighbour((1, 0, 1), LayeredCell::UP));
+        assert_eq!(
+            Some((1, 0, 0)),
+            grid.neighbour((1, 0, 1), LayeredCell::DOWN)
+        );
+        assert_eq!(None, grid.neighbour((1, 0, 0), LayeredCell::DOWN));
+        assert_eq!(
+            Some((2, 1, 1)),
+            grid.neighbour((1, 1, 1), LayeredCell::EAST)
+        );
+        assert_eq!(None, grid.neighbour((0, 0, 0), LayeredCell::NORTH));
+        assert_eq!(None, grid.neighbour((3, 0, 0), LayeredCell::WEST));
+        assert_eq!(None, grid.neighbour((1, 1, 0), LayeredCell::all()));
+
+        assert_eq!(4, grid.graph().neighbours(grid.index((1, 0, 0))).len());
+        assert_eq!(3, grid.graph().neighbours(grid.index((0, 1, 1))).len());
+        assert_eq!((2, 1, 1), grid.coords(grid.index((2, 1, 1))));
+    }
+
+    #[test]
+    fn levels_and_stairs() {
+        let mut grid = LayeredGrid::new(2, 2, 3);
+        grid.graph_mut().link(0, 1).unwrap();
+        grid.graph_mut().link(1, 5).unwrap();
+        grid.graph_mut().link(5, 9).unwrap();
+
+        assert_eq!(
+            LayeredCell::WEST | LayeredCell::UP,
+            grid.get_cell((1, 0, 0))
+        );
+        assert_eq!(vec![(0, 0, 0), (1, 0, 1)], grid.links((1, 0, 0)));
+
+        let level = grid.level(0).unwrap();
+        assert!(level.is_carved((0, 0), Cell::EAST));
+        assert!(!level.is_carved((0, 0), Cell::SOUTH));
+        assert!(grid.level(3).is_none());
+
+        assert_eq!(vec![((1, 0), LayeredCell::UP)], grid.stairs(0));
+        assert_eq!(
+            vec![((1, 0), LayeredCell::UP | LayeredCell::DOWN)],
+            grid.stairs(1)
+        );
+        assert!(grid.stairs(3).is_empty());
+    }
+}
@@ -1,64 +1,297 @@
 pub mod cell;
+pub mod delta;
+pub mod hex;
+pub mod layered;
+pub mod polar;
+pub mod upsilon;
 use self::cell::CellStatus;
 
 use super::errors::TransitError;
+use super::events::Event;
+use super::formatters::{AsciiNarrow, Formatter};
+use super::mask::Mask;
+use super::rooms::Room;
+use super::topology::Topology;
 use crate::utils::types::Coords;
 use cell::Cell;
 use std::fmt;
 
 type TransitResult<T> = Result<T, TransitError>;
 
+/// A rectangular grid of cells that algorithms carve passages through
+///
+/// This is the carving surface handed over to [Algorithm::generate](crate::maze::Algorithm::generate).
+/// Every cell starts with all four walls in place and unvisited. Carving a passage between two
+/// cells removes the wall they share and marks both cells as visited. Apart from that, a cell can
+/// be explicitly "marked", which some algorithms use to keep their own bookkeeping (e.g. the cells
+/// that are already part of a maze).
+///
+/// In a weave maze, a passage can also tunnel under a cell with a straight corridor running across
+/// it, which connects the two cells on both sides of the corridor. The cells the tunnel connects
+/// have the passages carved towards each other, while the cell it runs under keeps the directions
+/// of the tunnel apart from its own passages.
+///
+/// A grid with a wrap-around [Topology] has the cells along its wrapped borders adjacent to the
+/// cells along the opposite ones, so passages can be carved across those borders too.
+///
+/// A grid can record the [Event]s of generating a maze on it, so the generation process can be
+/// replayed step by step.
+///
+/// A grid built from a [Mask] has some of its cells disabled. Disabled cells are not a part of a
+/// maze: no passage can be carved into or out of them, and they are never reported as adjacent to
+/// any other cell.
+#[derive(Clone)]
 pub struct Grid {
     width: usize,
     height: usize,
     cells: Vec<Cell>,
+    tunnels: Vec<Cell>,
     cell_statuses: Vec<CellStatus>,
+    weave: bool,
+    topology: Topology,
+    rooms: Vec<Room>,
+    events: Option<Vec<Event>>,
 }
 
 impl Grid {
+    /// Returns a new instance of a grid with a given width and height and no passages carved
     pub fn new(width: usize, height: usize) -> Grid {
         Grid {
             width,
             height,
             cells: vec![Cell::default(); width * height],
+            tunnels: vec![Cell::default(); width * height],
             cell_statuses: vec![CellStatus::default(); width * height],
+            weave: false,
+            topology: Topology::Plane,
+            rooms: Vec::new(),
+            events: None,
         }
     }
 
+    /// Returns a new instance of a grid with the size of a given mask, the cells disabled by the
+    /// mask and no passages carved
+    pub(crate) fn from_mask(mask: &Mask) -> Grid {
+        let mut grid = Grid::new(mask.width(), mask.height());
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                if !mask.is_enabled((x, y)) {
+                    grid.get_cell_status_mut((x, y)).disable();
+                }
+            }
+        }
+        grid
+    }
+
+    /// Returns a new instance of a grid with the same size, topology and disabled cells and no
+    /// passages carved, e.g. to replay the [Event]s recorded on this grid
+    pub(crate) fn blank(&self) -> Grid {
+        let mut grid = Grid::new(self.width, self.height);
+        for (i, status) in self.cell_statuses.iter().enumerate() {
+            if status.disabled() {
+                grid.cell_statuses[i].disable();
+            }
+        }
+        grid.weave = self.weave;
+        grid.topology = self.topology;
+        grid
+    }
+
+    /// Allows algorithms to carve tunnels through a grid, thus generating a weave maze
+    pub(crate) const fn allow_tunnels(&mut self) {
+        self.weave = true;
+    }
+
+    /// Returns `true` if a grid is meant for a weave maze, so algorithms supporting it may carve
+    /// tunnels with [carve_tunnel](Grid::carve_tunnel). Otherwise, returns `false`
+    pub const fn allows_tunnels(&self) -> bool {
+        self.weave
+    }
+
+    /// Sets a topology of a grid, i.e. which of its borders wrap around to the opposite ones
+    pub(crate) const fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    /// Returns a topology of a grid
+    pub const fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Sets the rooms placed in a grid
+    pub(crate) fn set_rooms(&mut self, rooms: Vec<Room>) {
+        self.rooms = rooms;
+    }
+
+    /// Returns the rooms placed in a grid
+    pub(crate) fn rooms(&self) -> &[Room] {
+        &self.rooms
+    }
+
+    /// Returns `true` if the Western and Eastern borders of a grid wrap around, which needs at
+    /// least three columns. Otherwise, returns `false`
+    pub const fn wraps_horizontally(&self) -> bool {
+        self.topology.wraps_horizontally() && self.width > 2
+    }
+
+    /// Returns `true` if the Northern and Southern borders of a grid wrap around, which needs at
+    /// least three rows. Otherwise, returns `false`
+    pub const fn wraps_vertically(&self) -> bool {
+        self.topology.wraps_vertically() && self.height > 2
+    }
+
+    /// Returns a grid height in a number of cells
     pub const fn height(&self) -> usize {
         self.height
     }
 
+    /// Returns a grid width in a number of cells
     pub const fn width(&self) -> usize {
         self.width
     }
 
+    /// Marks a cell at the given coordinates, unless the coordinates are out of the grid bounds
     pub fn mark_cell(&mut self, coords: Coords) {
-        self.get_cell_status_mut(coords).mark()
+        if self.contains(coords) {
+            self.get_cell_status_mut(coords).mark()
+        }
     }
 
+    /// Returns `true` if a passage has been carved into or out of a cell at the given coordinates.
+    /// Otherwise, including when the coordinates are out of the grid bounds, returns `false`
     pub fn is_cell_visited(&self, coords: Coords) -> bool {
-        self.get_cell_status(coords).visited()
+        self.contains(coords) && self.get_cell_status(coords).visited()
     }
 
+    /// Returns `true` if a cell at the given coordinates has been marked. Otherwise, including
+    /// when the coordinates are out of the grid bounds, returns `false`
     pub fn is_cell_marked(&self, coords: Coords) -> bool {
-        self.get_cell_status(coords).marked()
+        self.contains(coords) && self.get_cell_status(coords).marked()
+    }
+
+    /// Returns `true` if the given coordinates are within the grid bounds and a cell there is not
+    /// disabled by a mask. Otherwise, returns `false`
+    pub fn is_cell_enabled(&self, coords: Coords) -> bool {
+        self.contains(coords) && !self.get_cell_status(coords).disabled()
+    }
+
+    /// Returns an iterator over the coordinates of all the enabled cells, row by row
+    pub fn enabled_cells(&self) -> impl Iterator<Item = Coords> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| (x, y)))
+            .filter(|coords| self.is_cell_enabled(*coords))
+    }
+
+    /// Starts recording the events of generating a maze on a grid
+    pub(crate) fn record_events(&mut self) {
+        self.events = Some(Vec::new());
+    }
+
+    /// Returns the events recorded so far, if a grid records them
+    pub(crate) fn events(&self) -> &[Event] {
+        self.events.as_deref().unwrap_or_default()
     }
 
-    pub fn get_cell_status(&self, coords: Coords) -> CellStatus {
+    /// Records an event of generating a maze, if a grid records them. Otherwise, does nothing
+    ///
+    /// Carving passages and tunnels and building walls record their events on their own, so an
+    /// algorithm only emits the steps that change nothing in a grid, such as moving to a cell.
+    pub fn emit(&mut self, event: Event) {
+        if let Some(events) = &mut self.events {
+            events.push(event);
+        }
+    }
+
+    /// Disables a cell at the given coordinates, so it's no longer a part of a maze
+    pub(crate) fn disable_cell(&mut self, coords: Coords) {
+        self.get_cell_status_mut(coords).disable();
+    }
+
+    /// Enables a cell at the given coordinates, so it's a part of a maze again
+    pub(crate) fn enable_cell(&mut self, coords: Coords) {
+        self.get_cell_status_mut(coords).enable();
+    }
+
+    fn get_cell_status(&self, coords: Coords) -> CellStatus {
         let (x, y) = coords;
         self.cell_statuses[y * self.width + x]
     }
 
-    pub fn is_carved(&self, coords: Coords, direction: Cell) -> bool {
+    /// Returns `true` if the given coordinates are within the grid bounds. Otherwise, returns
+    /// `false`
+    pub const fn contains(&self, coords: Coords) -> bool {
+        let (x, y) = coords;
+        x < self.width && y < self.height
+    }
+
+    /// Returns the passages carved out of a cell at the given coordinates, which are empty if the
+    /// coordinates are out of the grid bounds
+    pub fn get_cell(&self, coords: Coords) -> Cell {
+        if !self.contains(coords) {
+            return Cell::empty();
+        }
+
+        let (x, y) = coords;
+        self.cells[y * self.width + x]
+    }
+
+    /// Returns the directions of a tunnel running under a cell at the given coordinates, which are
+    /// empty if there is no tunnel or the coordinates are out of the grid bounds
+    pub fn get_tunnel(&self, coords: Coords) -> Cell {
+        if !self.contains(coords) {
+            return Cell::empty();
+        }
+
         let (x, y) = coords;
-        self.cells[y * self.width + x].contains(direction)
+        self.tunnels[y * self.width + x]
     }
 
+    /// Returns `true` if a passage is carved out of a cell at the given coordinates in a given
+    /// direction. Otherwise, including when the coordinates are out of the grid bounds, returns
+    /// `false`
+    pub fn is_carved(&self, coords: Coords, direction: Cell) -> bool {
+        self.get_cell(coords).contains(direction)
+    }
+
+    /// Returns the coordinates of a cell reachable through a passage carved out of a cell at the
+    /// given coordinates in a given direction, or `None` if there is a wall
+    ///
+    /// If the passage tunnels under the adjacent cell, the cell on the other side of the tunnel is
+    /// returned.
+    pub fn get_linked_cell_coords(&self, coords: Coords, direction: Cell) -> Option<Coords> {
+        if !self.is_carved(coords, direction) {
+            return None;
+        }
+
+        let next = self.get_next_cell_coords(coords, direction).ok()?;
+        if self.get_tunnel(next).contains(direction) {
+            return self.get_next_cell_coords(next, direction).ok();
+        }
+
+        Some(next)
+    }
+
+    /// Carves a passage out of a cell at the given coordinates in a given direction and returns
+    /// the coordinates of the adjacent cell the passage leads to
+    ///
+    /// Both cells become visited.
+    ///
+    /// # Errors
+    /// Returns a [TransitError] if the passage would lead out of the grid, either of the cells is
+    /// disabled or the passage would cut through a tunnel.
     pub fn carve_passage(&mut self, coords: Coords, direction: Cell) -> TransitResult<Coords> {
         let (x, y) = coords;
         let (nx, ny) = self.get_next_cell_coords(coords, direction)?;
 
+        if self.get_tunnel(coords).contains(direction)
+            || self.get_tunnel((nx, ny)).contains(direction)
+        {
+            return Err(TransitError {
+                coords,
+                reason: String::from("Passage cannot cut through a tunnel"),
+            });
+        }
+
         match direction {
             Cell::NORTH => {
                 self.cells[y * self.width + x] |= Cell::NORTH;
@@ -81,24 +314,140 @@ impl Grid {
 
         self.visit_cell(coords);
         self.visit_cell((nx, ny));
+        self.emit(Event::Carve {
+            from: coords,
+            dir: direction,
+        });
+
+        Ok((nx, ny))
+    }
+
+    /// Carves a tunnel out of a cell at the given coordinates in a given direction under the
+    /// adjacent cell and returns the coordinates of the cell on the other side of the tunnel
+    ///
+    /// The adjacent cell must have a straight corridor running across the tunnel and nothing else,
+    /// e.g. the passages to North and South only for a tunnel to East. Both cells the tunnel
+    /// connects become visited.
+    ///
+    /// # Errors
+    /// Returns a [TransitError] if the tunnel would lead out of the grid or across its wrapped
+    /// border, any of the cells is disabled or the adjacent cell is not a straight corridor across
+    /// the tunnel.
+    pub fn carve_tunnel(&mut self, coords: Coords, direction: Cell) -> TransitResult<Coords> {
+        let (mx, my) = self.get_next_cell_coords(coords, direction)?;
+        let (nx, ny) = self.get_next_cell_coords((mx, my), direction)?;
+
+        let is_step = |(ax, ay): Coords, (bx, by): Coords| ax.abs_diff(bx) + ay.abs_diff(by) == 1;
+        if !is_step(coords, (mx, my)) || !is_step((mx, my), (nx, ny)) {
+            return Err(TransitError {
+                coords,
+                reason: String::from("Tunnel cannot run across a wrapped border"),
+            });
+        }
+
+        let tunnel = direction | direction.opposite();
+        if self.get_cell((mx, my)) != tunnel.complement() || !self.get_tunnel((mx, my)).is_empty() {
+            return Err(TransitError {
+                coords,
+                reason: String::from("Tunnel can only run under a straight corridor across it"),
+            });
+        }
+
+        let (x, y) = coords;
+        self.cells[y * self.width + x] |= direction;
+        self.cells[ny * self.width + nx] |= direction.opposite();
+        self.tunnels[my * self.width + mx] = tunnel;
+
+        self.visit_cell(coords);
+        self.visit_cell((nx, ny));
+        self.emit(Event::Tunnel {
+            from: coords,
+            dir: direction,
+        });
+
+        Ok((nx, ny))
+    }
+
+    /// Builds a wall between a cell at the given coordinates and the adjacent cell in a given
+    /// direction, i.e. fills the passage between them if there is one, and returns the coordinates
+    /// of the adjacent cell
+    ///
+    /// Both cells remain visited.
+    ///
+    /// # Errors
+    /// Returns a [TransitError] if there is no adjacent cell in that direction, either of the
+    /// cells is disabled or a tunnel runs between them.
+    pub fn build_wall(&mut self, coords: Coords, direction: Cell) -> TransitResult<Coords> {
+        let (x, y) = coords;
+        let (nx, ny) = self.get_next_cell_coords(coords, direction)?;
+
+        if self.get_tunnel((nx, ny)).contains(direction) {
+            return Err(TransitError {
+                coords,
+                reason: String::from("Wall cannot block a tunnel"),
+            });
+        }
+
+        self.cells[y * self.width + x] -= direction;
+        self.cells[ny * self.width + nx] -= direction.opposite();
+        self.emit(Event::Wall {
+            from: coords,
+            dir: direction,
+        });
 
         Ok((nx, ny))
     }
 
+    /// Returns the coordinates of a cell adjacent to a cell at the given coordinates in a given
+    /// direction, regardless of whether there is a passage between them
+    ///
+    /// # Errors
+    /// Returns a [TransitError] if there is no adjacent cell in that direction or either of the
+    /// cells is disabled.
     pub fn get_next_cell_coords(&self, coords: Coords, direction: Cell) -> TransitResult<Coords> {
         self.validate_transit(coords, direction)?;
 
         let (x, y) = coords;
+        // The transit is validated, so a step out of the grid wraps around to the opposite border
         let (nx, ny) = match direction {
-            Cell::NORTH => (x, y - 1),
-            Cell::SOUTH => (x, y + 1),
-            Cell::WEST => (x - 1, y),
-            Cell::EAST => (x + 1, y),
+            Cell::NORTH => (x, y.checked_sub(1).unwrap_or(self.height - 1)),
+            Cell::SOUTH => (x, (y + 1) % self.height),
+            Cell::WEST => (x.checked_sub(1).unwrap_or(self.width - 1), y),
+            Cell::EAST => ((x + 1) % self.width, y),
             _ => unreachable!(),
         };
+
+        if !self.is_cell_enabled((nx, ny)) {
+            return Err(TransitError {
+                coords,
+                reason: String::from("Adjacent cell is disabled"),
+            });
+        }
+
         Ok((nx, ny))
     }
 
+    /// Returns an iterator over the coordinates of all the cells reachable through the passages
+    /// carved out of a cell at the given coordinates, in the North, South, West, East order
+    pub fn get_linked_cells(&self, coords: Coords) -> impl Iterator<Item = Coords> + '_ {
+        [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST]
+            .into_iter()
+            .filter_map(move |dir| self.get_linked_cell_coords(coords, dir))
+    }
+
+    /// Returns the directions and coordinates of all the cells adjacent to a cell at the given
+    /// coordinates, regardless of whether there are passages between them
+    pub fn get_adjacent_cells(&self, coords: Coords) -> Vec<(Cell, Coords)> {
+        [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST]
+            .into_iter()
+            .filter_map(|dir| {
+                self.get_next_cell_coords(coords, dir)
+                    .ok()
+                    .map(|next| (dir, next))
+            })
+            .collect()
+    }
+
     fn visit_cell(&mut self, coords: Coords) {
         self.get_cell_status_mut(coords).visit()
     }
@@ -110,11 +459,17 @@ impl Grid {
 
     fn validate_transit(&self, coords: Coords, direction: Cell) -> TransitResult<()> {
         let (x, y) = coords;
+        let (wraps_x, wraps_y) = (self.wraps_horizontally(), self.wraps_vertically());
         let reason = match direction {
-            Cell::NORTH if y < 1 => Some("First row in the grid cannot go North"),
-            Cell::SOUTH if y + 1 == self.height => Some("Last row in the grid cannot go South"),
-            Cell::WEST if x < 1 => Some("First cell in a row cannot go West"),
-            Cell::EAST if x + 1 == self.width => Some("Last column in the grid cannot go East"),
+            Cell::NORTH if y < 1 && !wraps_y => Some("First row in the grid cannot go North"),
+            Cell::SOUTH if y + 1 == self.height && !wraps_y => {
+                Some("Last row in the grid cannot go South")
+            }
+            Cell::WEST if x < 1 && !wraps_x => Some("First cell in a row cannot go West"),
+            Cell::EAST if x + 1 == self.width && !wraps_x => {
+                Some("Last column in the grid cannot go East")
+            }
+            _ if !self.is_cell_enabled(coords) => Some("Disabled cell cannot go anywhere"),
             _ => None,
         };
 
@@ -131,36 +486,231 @@ impl Grid {
 
 impl fmt::Display for Grid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let top_border = "_".repeat(self.width * 2 - 1);
+        write!(f, "{}", AsciiNarrow.format(self).into_inner())
+    }
+}
 
-        writeln!(f, " {} ", top_border)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        for y in 0..self.height {
-            write!(f, "|")?; // display left border
+    #[test]
+    fn carve_passage() {
+        let mut grid = Grid::new(2, 2);
 
-            for x in 0..self.width {
-                if self.is_carved((x, y), Cell::SOUTH) {
-                    write!(f, " ")?;
-                } else {
-                    write!(f, "_")?;
-                }
+        assert_eq!((1, 0), grid.carve_passage((0, 0), Cell::EAST).unwrap());
+        assert!(grid.is_carved((0, 0), Cell::EAST));
+        assert!(grid.is_carved((1, 0), Cell::WEST));
+        assert!(grid.is_cell_visited((0, 0)));
+        assert!(grid.is_cell_visited((1, 0)));
+        assert!(!grid.is_cell_visited((0, 1)));
+    }
 
-                if self.is_carved((x, y), Cell::EAST) {
-                    if self.is_carved((x, y), Cell::SOUTH)
-                        || self.is_carved((x + 1, y), Cell::SOUTH)
-                    {
-                        write!(f, " ")?;
-                    } else {
-                        write!(f, "_")?;
-                    }
-                } else {
-                    write!(f, "|")?;
-                }
-            }
+    #[test]
+    fn carve_passage_out_of_grid() {
+        let mut grid = Grid::new(2, 2);
 
-            writeln!(f)?; // goto next line
-        }
+        let error = grid.carve_passage((0, 0), Cell::NORTH).unwrap_err();
+        assert_eq!((0, 0), error.coords);
+        assert!(!grid.is_cell_visited((0, 0)));
+    }
+
+    #[test]
+    fn cells_out_of_grid() {
+        let mut grid = Grid::new(3, 2);
+        grid.carve_passage((2, 0), Cell::SOUTH).unwrap();
+        grid.mark_cell((5, 0));
+
+        assert!(!grid.is_cell_marked((2, 1)));
+        assert!(!grid.is_cell_marked((5, 0)));
+        assert!(!grid.is_cell_visited((5, 0)));
+        assert!(!grid.is_carved((5, 0), Cell::NORTH));
+        assert_eq!(Cell::empty(), grid.get_cell((3, 0)));
+        assert_eq!(Cell::empty(), grid.get_tunnel((0, 7)));
+    }
+
+    #[test]
+    fn linked_cell_coords() {
+        let mut grid = Grid::new(2, 2);
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+
+        assert_eq!(
+            Some((0, 1)),
+            grid.get_linked_cell_coords((0, 0), Cell::SOUTH)
+        );
+        assert_eq!(None, grid.get_linked_cell_coords((0, 0), Cell::EAST));
+        assert_eq!(None, grid.get_linked_cell_coords((0, 0), Cell::NORTH));
+    }
+
+    #[test]
+    fn adjacent_cells() {
+        let grid = Grid::new(3, 3);
+
+        assert_eq!(
+            vec![(Cell::SOUTH, (0, 1)), (Cell::EAST, (1, 0))],
+            grid.get_adjacent_cells((0, 0))
+        );
+        assert_eq!(4, grid.get_adjacent_cells((1, 1)).len());
+    }
+
+    #[test]
+    fn disabled_cells() {
+        let mut mask = Mask::new(3, 1);
+        mask.disable((1, 0));
+        let mut grid = Grid::from_mask(&mask);
+
+        assert!(grid.is_cell_enabled((0, 0)));
+        assert!(!grid.is_cell_enabled((1, 0)));
+        assert!(!grid.is_cell_enabled((3, 0)));
+        assert_eq!(
+            vec![(0, 0), (2, 0)],
+            grid.enabled_cells().collect::<Vec<_>>()
+        );
+
+        assert!(grid.carve_passage((0, 0), Cell::EAST).is_err());
+        assert!(grid.carve_passage((1, 0), Cell::WEST).is_err());
+        assert!(grid.get_adjacent_cells((2, 0)).is_empty());
+        assert!(!grid.is_cell_visited((0, 0)));
+    }
+
+    #[test]
+    fn carve_tunnel() {
+        let mut grid = Grid::new(3, 3);
+        assert!(grid.carve_tunnel((0, 1), Cell::EAST).is_err());
+
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        assert_eq!((2, 1), grid.carve_tunnel((0, 1), Cell::EAST).unwrap());
+
+        assert_eq!(Cell::EAST | Cell::WEST, grid.get_tunnel((1, 1)));
+        assert_eq!(Cell::NORTH | Cell::SOUTH, grid.get_cell((1, 1)));
+        assert_eq!(
+            Some((2, 1)),
+            grid.get_linked_cell_coords((0, 1), Cell::EAST)
+        );
+        assert_eq!(
+            Some((0, 1)),
+            grid.get_linked_cell_coords((2, 1), Cell::WEST)
+        );
+        assert!(grid.is_cell_visited((0, 1)));
+        assert!(grid.is_cell_visited((2, 1)));
+
+        assert!(grid.carve_passage((0, 1), Cell::EAST).is_err());
+        assert!(grid.carve_tunnel((0, 1), Cell::EAST).is_err());
+    }
+
+    #[test]
+    fn build_wall() {
+        let mut grid = Grid::new(3, 3);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+
+        assert_eq!((1, 0), grid.build_wall((0, 0), Cell::EAST).unwrap());
+        assert!(!grid.is_carved((0, 0), Cell::EAST));
+        assert!(!grid.is_carved((1, 0), Cell::WEST));
+        assert!(grid.is_carved((0, 0), Cell::SOUTH));
+        assert!(grid.is_cell_visited((1, 0)));
+
+        // A wall where there is no passage changes nothing
+        assert_eq!((1, 0), grid.build_wall((0, 0), Cell::EAST).unwrap());
+        assert_eq!(Cell::SOUTH, grid.get_cell((0, 0)));
+        assert!(grid.build_wall((0, 0), Cell::NORTH).is_err());
+
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_tunnel((0, 1), Cell::EAST).unwrap();
+        assert!(grid.build_wall((0, 1), Cell::EAST).is_err());
+    }
+
+    #[test]
+    fn record_events() {
+        let mut grid = Grid::new(3, 3);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.emit(Event::Visit((1, 0)));
+        assert!(grid.events().is_empty());
+
+        grid.record_events();
+        grid.emit(Event::Visit((1, 0)));
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_tunnel((0, 1), Cell::EAST).unwrap();
+        grid.carve_passage((0, 1), Cell::WEST).ok();
+        grid.build_wall((1, 0), Cell::WEST).unwrap();
+
+        assert_eq!(
+            vec![
+                Event::Visit((1, 0)),
+                Event::Carve {
+                    from: (1, 0),
+                    dir: Cell::SOUTH
+                },
+                Event::Carve {
+                    from: (1, 1),
+                    dir: Cell::SOUTH
+                },
+                Event::Tunnel {
+                    from: (0, 1),
+                    dir: Cell::EAST
+                },
+                Event::Wall {
+                    from: (1, 0),
+                    dir: Cell::WEST
+                },
+            ],
+            grid.events()
+        );
+    }
+
+    #[test]
+    fn wrapped_borders() {
+        let mut grid = Grid::new(3, 3);
+        grid.set_topology(Topology::Cylinder);
+
+        assert_eq!(
+            Some((0, 1)),
+            grid.get_next_cell_coords((2, 1), Cell::EAST).ok()
+        );
+        assert_eq!(
+            Some((2, 0)),
+            grid.get_next_cell_coords((0, 0), Cell::WEST).ok()
+        );
+        assert!(grid.get_next_cell_coords((0, 0), Cell::NORTH).is_err());
+        assert_eq!(4, grid.get_adjacent_cells((0, 1)).len());
+
+        grid.set_topology(Topology::Torus);
+        assert_eq!(
+            Some((0, 2)),
+            grid.get_next_cell_coords((0, 0), Cell::NORTH).ok()
+        );
+        assert_eq!((0, 0), grid.carve_passage((0, 2), Cell::SOUTH).unwrap());
+        assert!(grid.is_carved((0, 0), Cell::NORTH));
+        assert_eq!(
+            Some((0, 2)),
+            grid.get_linked_cell_coords((0, 0), Cell::NORTH)
+        );
+
+        // A tunnel is drawn under a corridor within a grid, so it cannot run across the border
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 1), Cell::SOUTH).unwrap();
+        assert!(grid.carve_tunnel((2, 1), Cell::EAST).is_err());
+
+        // Two cells would be adjacent across both of their sides
+        let mut grid = Grid::new(2, 3);
+        grid.set_topology(Topology::Torus);
+        assert!(!grid.wraps_horizontally());
+        assert!(grid.get_next_cell_coords((1, 0), Cell::EAST).is_err());
+        assert_eq!(
+            Some((1, 2)),
+            grid.get_next_cell_coords((1, 0), Cell::NORTH).ok()
+        );
+    }
+
+    #[test]
+    fn mark_cell() {
+        let mut grid = Grid::new(2, 2);
+        grid.mark_cell((1, 1));
 
-        Ok(())
+        assert!(grid.is_cell_marked((1, 1)));
+        assert!(!grid.is_cell_visited((1, 1)));
     }
 }
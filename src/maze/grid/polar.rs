@@ -0,0 +1,268 @@
+use crate::maze::graph::Graph;
+use crate::maze::graph_maze::{GraphGrid, GraphGridMut};
+use crate::utils::types::PolarCoords;
+use std::f64::consts::PI;
+
+/// A circular grid of cells arranged in concentric rings that algorithms carve passages through
+///
+/// The innermost ring is a single cell in the center. Every next ring is split into as many cells
+/// as needed to keep them about as wide as they are tall, so each cell of a ring is adjacent to
+/// one or more cells of the ring outside it, along with the two cells next to it in its own ring
+/// and the cell of the ring inside it.
+///
+/// The cells are addressed by [PolarCoords], while the passages are carved through a [Graph] of
+/// them, see [GraphAlgorithm](crate::maze::GraphAlgorithm).
+pub struct PolarGrid {
+    ring_lens: Vec<usize>,
+    offsets: Vec<usize>,
+    graph: Graph,
+}
+
+impl PolarGrid {
+    /// Returns a new instance of a grid with a given number of rings and no passages carved
+    pub fn new(rings: usize) -> PolarGrid {
+        let mut ring_lens: Vec<usize> = Vec::with_capacity(rings);
+        for ring in 0..rings {
+            let len = match ring.checked_sub(1).map(|inner| ring_lens[inner]) {
+                None => 1,
+                Some(inner_len) => {
+                    // The length of the inner border of a ring split between the cells of the
+                    // ring inside it, in the units of the ring height
+                    let width = 2.0 * PI * ring as f64 / inner_len as f64;
+                    inner_len * (width.round() as usize).max(1)
+                }
+            };
+            ring_lens.push(len);
+        }
+
+        let offsets = ring_lens
+            .iter()
+            .scan(0, |offset, len| {
+                let start = *offset;
+                *offset += len;
+                Some(start)
+            })
+            .collect();
+
+        let mut grid = PolarGrid {
+            ring_lens,
+            offsets,
+            graph: Graph::new(Vec::new()),
+        };
+        let neighbours = grid
+            .cells()
+            .map(|coords| grid.adjacent_cells(coords))
+            .map(|cells| cells.into_iter().filter_map(|c| grid.index(c)).collect())
+            .collect();
+        grid.graph = Graph::new(neighbours);
+        grid
+    }
+
+    /// Returns the number of rings
+    pub const fn rings(&self) -> usize {
+        self.ring_lens.len()
+    }
+
+    /// Returns the number of cells in a ring with a given index, or zero if there is no such ring
+    pub fn ring_len(&self, ring: usize) -> usize {
+        self.ring_lens.get(ring).copied().unwrap_or(0)
+    }
+
+    /// Returns `true` if the coordinates are within the grid bounds. Otherwise, returns `false`
+    pub fn contains(&self, (ring, index): PolarCoords) -> bool {
+        index < self.ring_len(ring)
+    }
+
+    /// Returns an iterator over the coordinates of all the cells, ring by ring from the center
+    pub fn cells(&self) -> impl Iterator<Item = PolarCoords> + '_ {
+        self.ring_lens
+            .iter()
+            .enumerate()
+            .flat_map(|(ring, len)| (0..*len).map(move |index| (ring, index)))
+    }
+
+    /// Returns the coordinates of the cell of the ring inside a cell at the given coordinates it's
+    /// adjacent to, or `None` for the center cell or the coordinates out of the grid bounds
+    pub fn inward(&self, (ring, index): PolarCoords) -> Option<PolarCoords> {
+        if ring == 0 || !self.contains((ring, index)) {
+            return None;
+        }
+
+        let ratio = self.ring_len(ring) / self.ring_len(ring - 1);
+        Some((ring - 1, index / ratio))
+    }
+
+    /// Returns the coordinates of the cells of the ring outside a cell at the given coordinates
+    /// it's adjacent to, which is empty for the cells of the outermost ring
+    pub fn outward(&self, (ring, index): PolarCoords) -> Vec<PolarCoords> {
+        if !self.contains((ring, index)) || ring + 1 >= self.rings() {
+            return Vec::new();
+        }
+
+        let ratio = self.ring_len(ring + 1) / self.ring_len(ring);
+        (index * ratio..(index + 1) * ratio)
+            .map(|outer| (ring + 1, outer))
+            .collect()
+    }
+
+    /// Returns the coordinates of the next cell clockwise in the same ring, or `None` for the
+    /// center cell or the coordinates out of the grid bounds
+    pub fn clockwise(&self, (ring, index): PolarCoords) -> Option<PolarCoords> {
+        let len = self.ring_len(ring);
+        (len > 1 && index < len).then(|| (ring, (index + 1) % len))
+    }
+
+    /// Returns the coordinates of the next cell counter-clockwise in the same ring, or `None` for
+    /// the center cell or the coordinates out of the grid bounds
+    pub fn counter_clockwise(&self, (ring, index): PolarCoords) -> Option<PolarCoords> {
+        let len = self.ring_len(ring);
+        (len > 1 && index < len).then(|| (ring, (index + len - 1) % len))
+    }
+
+    /// Returns `true` if there is a passage carved between cells at the given coordinates.
+    /// Otherwise, returns `false`
+    pub fn is_linked(&self, coords: PolarCoords, other: PolarCoords) -> bool {
+        match (self.index(coords), self.index(other)) {
+            (Some(cell), Some(other)) => self.graph.is_linked(cell, other),
+            _ => false,
+        }
+    }
+
+    /// Returns the coordinates of all the cells a cell at the given coordinates shares a carved
+    /// passage with
+    pub fn links(&self, coords: PolarCoords) -> Vec<PolarCoords> {
+        self.index(coords)
+            .map(|cell| self.graph.links(cell))
+            .unwrap_or_default()
+            .iter()
+            .map(|cell| self.coords(*cell))
+            .collect()
+    }
+
+    /// Returns a graph of the grid cells
+    pub const fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns a mutable ref to a graph of the grid cells
+    pub(crate) const fn graph_mut(&mut self) -> &mut Graph {
+        &mut self.graph
+    }
+
+    /// Returns an index of a cell at the given coordinates in the graph, or `None` if the
+    /// coordinates are out of the grid bounds
+    pub(crate) fn index(&self, (ring, index): PolarCoords) -> Option<usize> {
+        self.contains((ring, index))
+            .then(|| self.offsets[ring] + index)
+    }
+
+    /// Returns the coordinates of a cell with a given index in the graph
+    pub(crate) fn coords(&self, cell: usize) -> PolarCoords {
+        let ring = self.offsets.partition_point(|offset| *offset <= cell) - 1;
+        (ring, cell - self.offsets[ring])
+    }
+
+    fn adjacent_cells(&self, coords: PolarCoords) -> Vec<PolarCoords> {
+        let mut cells = Vec::new();
+        let around = [
+            self.inward(coords),
+            self.clockwise(coords),
+            self.counter_clockwise(coords),
+        ];
+
+        for next in around.into_iter().flatten().chain(self.outward(coords)) {
+            if !cells.contains(&next) {
+                cells.push(next);
+            }
+        }
+
+        cells
+    }
+}
+
+impl GraphGrid for PolarGrid {
+    type Coords = PolarCoords;
+    type Size = usize;
+
+    const DEFAULT_SIZE: Self::Size = 10;
+
+    fn with_size(rings: Self::Size) -> Self {
+        PolarGrid::new(rings)
+    }
+
+    fn graph(&self) -> &Graph {
+        PolarGrid::graph(self)
+    }
+
+    fn coords(&self, cell: usize) -> PolarCoords {
+        PolarGrid::coords(self, cell)
+    }
+
+    fn links(&self, coords: PolarCoords) -> Vec<PolarCoords> {
+        PolarGrid::links(self, coords)
+    }
+}
+
+impl GraphGridMut for PolarGrid {
+    fn graph_mut(&mut self) -> &mut Graph {
+        PolarGrid::graph_mut(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_lens() {
+        let grid = PolarGrid::new(6);
+
+        assert_eq!(6, grid.rings());
+        assert_eq!(
+            vec![1, 6, 12, 24, 24, 24],
+            (0..6).map(|ring| grid.ring_len(ring)).collect::<Vec<_>>()
+        );
+        assert_eq!(0, grid.ring_len(6));
+        assert_eq!(91, grid.graph().len());
+        assert_eq!(91, grid.cells().count());
+    }
+
+    #[test]
+    fn adjacent_cells() {
+        let grid = PolarGrid::new(4);
+
+        assert_eq!(None, grid.inward((0, 0)));
+        assert_eq!(None, grid.clockwise((0, 0)));
+        assert_eq!(6, grid.outward((0, 0)).len());
+
+        assert_eq!(Some((1, 2)), grid.inward((2, 5)));
+        assert_eq!(vec![(3, 10), (3, 11)], grid.outward((2, 5)));
+        assert_eq!(Some((2, 0)), grid.clockwise((2, 11)));
+        assert_eq!(Some((2, 11)), grid.counter_clockwise((2, 0)));
+        assert!(grid.outward((3, 0)).is_empty());
+        assert!(grid.outward((4, 0)).is_empty());
+    }
+
+    #[test]
+    fn graph_indices() {
+        let grid = PolarGrid::new(3);
+
+        for (cell, coords) in grid.cells().enumerate() {
+            assert_eq!(Some(cell), grid.index(coords));
+            assert_eq!(coords, grid.coords(cell));
+        }
+        assert_eq!(None, grid.index((1, 6)));
+        assert_eq!(vec![0, 2, 6, 7, 8], grid.graph().neighbours(1));
+    }
+
+    #[test]
+    fn links() {
+        let mut grid = PolarGrid::new(3);
+        grid.graph_mut().link(0, 1).unwrap();
+
+        assert!(grid.is_linked((0, 0), (1, 0)));
+        assert!(!grid.is_linked((0, 0), (1, 1)));
+        assert!(!grid.is_linked((0, 0), (9, 0)));
+        assert_eq!(vec![(1, 0)], grid.links((0, 0)));
+    }
+}
@@ -0,0 +1,267 @@
+use crate::maze::graph::Graph;
+use crate::maze::graph_maze::{CellGrid, GraphGrid, GraphGridMut};
+use crate::utils::types::Coords;
+use bitflags::bitflags;
+
+bitflags! {
+    /// A set of directions of an upsilon maze cell
+    ///
+    /// An octagon has all eight sides, while a square only has the Northern, Southern, Eastern and
+    /// Western ones. Depending on the context, the set bits are either passages carved out of a
+    /// cell (see [UpsilonMaze::cell](crate::maze::UpsilonMaze::cell)) or a side to move across.
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct UpsilonCell: u8 {
+        /// The Northern side of a cell
+        const NORTH = 0b00000001;
+        /// The North-Eastern side of an octagon
+        const NORTH_EAST = 0b00000010;
+        /// The Eastern side of a cell
+        const EAST = 0b00000100;
+        /// The South-Eastern side of an octagon
+        const SOUTH_EAST = 0b00001000;
+        /// The Southern side of a cell
+        const SOUTH = 0b00010000;
+        /// The South-Western side of an octagon
+        const SOUTH_WEST = 0b00100000;
+        /// The Western side of a cell
+        const WEST = 0b01000000;
+        /// The North-Western side of an octagon
+        const NORTH_WEST = 0b10000000;
+    }
+}
+
+/// A grid of octagonal and square cells that algorithms carve passages through
+///
+/// The octagons and squares alternate like the fields of a chessboard, starting with an octagon
+/// in the top left corner, so a cell at `(x, y)` is an octagon if `x + y` is even. Each cell is
+/// adjacent to the cells to the North, South, East and West of it, and an octagon is also adjacent
+/// to the octagons diagonally next to it.
+///
+/// The cells are addressed by [Coords] of their columns and rows, while the passages are carved
+/// through a [Graph] of them, see [GraphAlgorithm](crate::maze::GraphAlgorithm).
+pub struct UpsilonGrid {
+    width: usize,
+    height: usize,
+    graph: Graph,
+}
+
+impl UpsilonGrid {
+    /// Returns a new instance of a grid with a given width and height and no passages carved
+    pub fn new(width: usize, height: usize) -> UpsilonGrid {
+        let mut grid = UpsilonGrid {
+            width,
+            height,
+            graph: Graph::new(Vec::new()),
+        };
+        let neighbours = grid
+            .cells()
+            .map(|coords| {
+                UpsilonCell::all()
+                    .iter()
+                    .filter_map(|dir| grid.neighbour(coords, dir))
+                    .map(|next| grid.index(next))
+                    .collect()
+            })
+            .collect();
+        grid.graph = Graph::new(neighbours);
+        grid
+    }
+
+    /// Returns a grid width in a number of columns
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns a grid height in a number of rows
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if the coordinates are within the grid bounds. Otherwise, returns `false`
+    pub const fn contains(&self, (x, y): Coords) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Returns `true` if a cell at the given coordinates is an octagon. Otherwise, if it's a
+    /// square, returns `false`
+    pub const fn is_octagon(&self, (x, y): Coords) -> bool {
+        (x + y) % 2 == 0
+    }
+
+    /// Returns an iterator over the coordinates of all the cells, row by row
+    pub fn cells(&self) -> impl Iterator<Item = Coords> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Returns the coordinates of a cell adjacent to a cell at the given coordinates across a
+    /// given side, or `None` if the cell has no such side or there is no such cell within the grid
+    /// bounds
+    pub fn neighbour(&self, coords: Coords, direction: UpsilonCell) -> Option<Coords> {
+        let (x, y) = coords;
+        let diagonal = UpsilonCell::NORTH_EAST
+            | UpsilonCell::SOUTH_EAST
+            | UpsilonCell::SOUTH_WEST
+            | UpsilonCell::NORTH_WEST;
+        if diagonal.contains(direction) && !self.is_octagon(coords) {
+            return None;
+        }
+
+        let (dx, dy) = match direction {
+            UpsilonCell::NORTH => (0, -1),
+            UpsilonCell::NORTH_EAST => (1, -1),
+            UpsilonCell::EAST => (1, 0),
+            UpsilonCell::SOUTH_EAST => (1, 1),
+            UpsilonCell::SOUTH => (0, 1),
+            UpsilonCell::SOUTH_WEST => (-1, 1),
+            UpsilonCell::WEST => (-1, 0),
+            UpsilonCell::NORTH_WEST => (-1, -1),
+            _ => return None,
+        };
+
+        let next = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        (self.contains(coords) && self.contains(next)).then_some(next)
+    }
+
+    /// Returns `true` if a passage is carved out of a cell at the given coordinates across a given
+    /// side. Otherwise, returns `false`
+    pub fn is_carved(&self, coords: Coords, direction: UpsilonCell) -> bool {
+        self.neighbour(coords, direction)
+            .is_some_and(|next| self.graph.is_linked(self.index(coords), self.index(next)))
+    }
+
+    /// Returns the passages carved out of a cell at the given coordinates
+    pub fn get_cell(&self, coords: Coords) -> UpsilonCell {
+        UpsilonCell::all()
+            .iter()
+            .filter(|dir| self.is_carved(coords, *dir))
+            .collect()
+    }
+
+    /// Returns the coordinates of all the cells a cell at the given coordinates shares a carved
+    /// passage with, clockwise from the North
+    pub fn links(&self, coords: Coords) -> Vec<Coords> {
+        UpsilonCell::all()
+            .iter()
+            .filter(|dir| self.is_carved(coords, *dir))
+            .filter_map(|dir| self.neighbour(coords, dir))
+            .collect()
+    }
+
+    /// Returns a graph of the grid cells
+    pub const fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns a mutable ref to a graph of the grid cells
+    pub(crate) const fn graph_mut(&mut self) -> &mut Graph {
+        &mut self.graph
+    }
+
+    /// Returns an index of a cell at the given coordinates in the graph
+    pub(crate) const fn index(&self, (x, y): Coords) -> usize {
+        y * self.width + x
+    }
+
+    /// Returns the coordinates of a cell with a given index in the graph
+    pub(crate) const fn coords(&self, cell: usize) -> Coords {
+        (cell % self.width, cell / self.width)
+    }
+}
+
+impl GraphGrid for UpsilonGrid {
+    type Coords = Coords;
+    type Size = (usize, usize);
+
+    const DEFAULT_SIZE: Self::Size = (10, 10);
+
+    fn with_size((width, height): Self::Size) -> Self {
+        UpsilonGrid::new(width, height)
+    }
+
+    fn graph(&self) -> &Graph {
+        UpsilonGrid::graph(self)
+    }
+
+    fn coords(&self, cell: usize) -> Coords {
+        UpsilonGrid::coords(self, cell)
+    }
+
+    fn links(&self, coords: Coords) -> Vec<Coords> {
+        UpsilonGrid::links(self, coords)
+    }
+}
+
+impl CellGrid for UpsilonGrid {
+    type Cell = UpsilonCell;
+
+    fn width(&self) -> usize {
+        UpsilonGrid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        UpsilonGrid::height(self)
+    }
+
+    fn contains(&self, coords: Coords) -> bool {
+        UpsilonGrid::contains(self, coords)
+    }
+
+    fn get_cell(&self, coords: Coords) -> UpsilonCell {
+        UpsilonGrid::get_cell(self, coords)
+    }
+
+    fn is_carved(&self, coords: Coords, direction: UpsilonCell) -> bool {
+        UpsilonGrid::is_carved(self, coords, direction)
+    }
+}
+
+impl GraphGridMut for UpsilonGrid {
+    fn graph_mut(&mut self) -> &mut Graph {
+        UpsilonGrid::graph_mut(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours() {
+        let grid = UpsilonGrid::new(3, 3);
+
+        assert!(grid.is_octagon((1, 1)));
+        assert!(!grid.is_octagon((1, 0)));
+        assert_eq!(
+            Some((2, 2)),
+            grid.neighbour((1, 1), UpsilonCell::SOUTH_EAST)
+        );
+        assert_eq!(
+            Some((0, 0)),
+            grid.neighbour((1, 1), UpsilonCell::NORTH_WEST)
+        );
+        assert_eq!(None, grid.neighbour((1, 0), UpsilonCell::SOUTH_EAST));
+        assert_eq!(Some((1, 1)), grid.neighbour((1, 0), UpsilonCell::SOUTH));
+        assert_eq!(None, grid.neighbour((0, 0), UpsilonCell::NORTH_WEST));
+        assert_eq!(None, grid.neighbour((2, 2), UpsilonCell::EAST));
+
+        assert_eq!(8, grid.graph().neighbours(grid.index((1, 1))).len());
+        assert_eq!(3, grid.graph().neighbours(grid.index((1, 0))).len());
+        assert_eq!(3, grid.graph().neighbours(grid.index((0, 0))).len());
+    }
+
+    #[test]
+    fn links() {
+        let mut grid = UpsilonGrid::new(2, 2);
+        grid.graph_mut().link(0, 1).unwrap();
+        grid.graph_mut().link(0, 3).unwrap();
+
+        assert_eq!(
+            UpsilonCell::EAST | UpsilonCell::SOUTH_EAST,
+            grid.get_cell((0, 0))
+        );
+        assert_eq!(UpsilonCell::NORTH_WEST, grid.get_cell((1, 1)));
+        assert_eq!(vec![(1, 0), (1, 1)], grid.links((0, 0)));
+        assert_eq!((1, 1), grid.coords(3));
+    }
+}
@@ -0,0 +1,197 @@
+use super::errors::{MaskError, MazeParseError};
+use super::parsers;
+use crate::utils::types::Coords;
+
+/// A mask of the cells that make up a maze
+///
+/// A maze is a full rectangle of cells unless it's built with a mask. Every cell a mask disables
+/// is cut out of a maze, so mazes can take the shape of letters, circles or level silhouettes. All
+/// the algorithms respect a mask and never carve a passage into or out of a disabled cell.
+///
+/// The enabled cells of a mask must be connected with each other for a maze to be valid. Some
+/// algorithms, such as [BinaryTree](crate::maze::BinaryTree), [Sidewinder](crate::maze::Sidewinder)
+/// and [Eller](crate::maze::Eller), rely on the full rows and columns of a grid and may cut off some
+/// regions around the disabled cells. A builder joins such regions with a single passage between
+/// every two of them after generating a maze, so every algorithm builds a valid maze on a mask.
+///
+/// # Example
+/// ```rust
+/// use knossos::maze::*;
+///
+/// let mask = Mask::from_ascii(
+///     "..XX..\n\
+///      ......\n\
+///      X....X\n\
+///      XX..XX\n",
+/// )
+/// .unwrap();
+/// let maze = OrthogonalMazeBuilder::new().mask(mask).build();
+///
+/// assert!(maze.is_valid());
+/// assert!(!maze.is_enabled((0, 2)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    width: usize,
+    height: usize,
+    enabled: Vec<bool>,
+}
+
+impl Mask {
+    /// Returns a new instance of a mask with a given width and height and all the cells enabled
+    pub fn new(width: usize, height: usize) -> Mask {
+        Mask {
+            width,
+            height,
+            enabled: vec![true; width * height],
+        }
+    }
+
+    /// Parses a mask from text, where every character stands for a cell
+    ///
+    /// The `X` character disables a cell and any other character enables it. All the lines must
+    /// be equally long.
+    ///
+    /// # Errors
+    /// Returns a [MazeParseError] pointing to the line and column of malformed input, or if all the
+    /// cells are disabled.
+    pub fn from_ascii(text: &str) -> Result<Mask, MazeParseError> {
+        parsers::parse_mask(text)
+    }
+
+    /// Loads a mask from an image at the specified path, where every pixel stands for a cell
+    ///
+    /// Dark pixels disable cells and light pixels enable them, so a black and white image works
+    /// best.
+    ///
+    /// # Errors
+    /// Returns a [MaskError] if the image cannot be opened or decoded, or if all the cells are
+    /// disabled.
+    pub fn from_image(path: &str) -> Result<Mask, MaskError> {
+        let image = image::open(path)
+            .map_err(|err| MaskError {
+                reason: err.to_string(),
+            })?
+            .to_luma8();
+
+        let mut mask = Mask::new(image.width() as usize, image.height() as usize);
+        for (x, y, pixel) in image.enumerate_pixels() {
+            if pixel.0[0] < 128 {
+                mask.disable((x as usize, y as usize));
+            }
+        }
+
+        if mask.count() == 0 {
+            return Err(MaskError {
+                reason: String::from("All the cells are disabled"),
+            });
+        }
+
+        Ok(mask)
+    }
+
+    /// Returns a mask width in a number of cells
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns a mask height in a number of cells
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if a cell at the given coordinates is enabled. Otherwise, including the
+    /// coordinates out of the mask bounds, returns `false`
+    pub fn is_enabled(&self, (x, y): Coords) -> bool {
+        x < self.width && y < self.height && self.enabled[y * self.width + x]
+    }
+
+    /// Enables a cell at the given coordinates
+    ///
+    /// # Panics
+    /// Panics if the coordinates are out of the mask bounds.
+    pub fn enable(&mut self, coords: Coords) {
+        let index = self.index(coords);
+        self.enabled[index] = true;
+    }
+
+    /// Disables a cell at the given coordinates
+    ///
+    /// # Panics
+    /// Panics if the coordinates are out of the mask bounds.
+    pub fn disable(&mut self, coords: Coords) {
+        let index = self.index(coords);
+        self.enabled[index] = false;
+    }
+
+    /// Returns the number of enabled cells
+    pub fn count(&self) -> usize {
+        self.enabled.iter().filter(|enabled| **enabled).count()
+    }
+
+    fn index(&self, (x, y): Coords) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "Coords ({}, {}) are out of the mask bounds",
+            x,
+            y
+        );
+        y * self.width + x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enable_and_disable() {
+        let mut mask = Mask::new(3, 2);
+        assert_eq!(6, mask.count());
+
+        mask.disable((2, 1));
+        assert!(!mask.is_enabled((2, 1)));
+        assert_eq!(5, mask.count());
+
+        mask.enable((2, 1));
+        assert!(mask.is_enabled((2, 1)));
+        assert!(!mask.is_enabled((3, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of the mask bounds")]
+    fn disable_out_of_bounds() {
+        Mask::new(2, 2).disable((2, 0));
+    }
+
+    #[test]
+    fn from_ascii() {
+        let mask = Mask::from_ascii("X.X\n...\n").unwrap();
+
+        assert_eq!((3, 2), (mask.width(), mask.height()));
+        assert_eq!(4, mask.count());
+        assert!(!mask.is_enabled((0, 0)));
+        assert!(mask.is_enabled((1, 0)));
+    }
+
+    #[test]
+    fn from_image() {
+        let path = std::env::temp_dir().join("knossos_mask.png");
+        let path = path.to_str().unwrap();
+
+        let mut image = image::GrayImage::from_pixel(3, 2, image::Luma([255]));
+        image.put_pixel(1, 1, image::Luma([0]));
+        image.save(path).unwrap();
+
+        let mask = Mask::from_image(path).unwrap();
+        assert_eq!((3, 2), (mask.width(), mask.height()));
+        assert!(!mask.is_enabled((1, 1)));
+        assert_eq!(5, mask.count());
+
+        image::GrayImage::new(2, 2).save(path).unwrap();
+        let error = Mask::from_image(path).err().unwrap();
+        assert_eq!("All the cells are disabled", error.reason);
+
+        assert!(Mask::from_image("missing_mask.png").is_err());
+    }
+}
@@ -1,9 +1,17 @@
 use super::{
-    errors::MazeSaveError,
+    errors::{MazeParseError, MazeSaveError},
+    events::{Event, Replay},
     formatters::{Formatter, Saveable},
-    grid::Grid,
+    grid::{cell::Cell, Grid},
+    mask::Mask,
+    parsers,
+    rooms::Room,
+    solvers::Solver,
+    statistics::{self, MazeStatistics},
+    topology::Topology,
     validate::validate,
 };
+use crate::utils::types::Coords;
 use std::fmt;
 
 /// An orthogonal maze
@@ -22,16 +30,238 @@ impl OrthogonalMaze {
         }
     }
 
+    /// Returns a new instance of an orthogonal maze with the size of a given mask and the cells
+    /// disabled by the mask
+    pub fn with_mask(mask: &Mask) -> OrthogonalMaze {
+        OrthogonalMaze {
+            grid: Grid::from_mask(mask),
+        }
+    }
+
+    /// Parses a maze from the text in the [AsciiNarrow](crate::maze::AsciiNarrow) format
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMaze::from_ascii_narrow(" ___ \n|  _|\n|_|_|\n").unwrap();
+    ///
+    /// assert!(maze.is_carved((0, 0), Cell::EAST));
+    /// assert!(maze.is_carved((0, 0), Cell::SOUTH));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [MazeParseError] pointing to the line and column of malformed input.
+    pub fn from_ascii_narrow(text: &str) -> Result<OrthogonalMaze, MazeParseError> {
+        let grid = parsers::parse_ascii_narrow(text)?;
+        Ok(OrthogonalMaze { grid })
+    }
+
+    /// Parses a maze from the text in the [AsciiBroad](crate::maze::AsciiBroad) format
+    ///
+    /// The characters inside the cells and passages are ignored, so the output of
+    /// [AsciiBroad::with_path](crate::maze::AsciiBroad::with_path) can be parsed too.
+    ///
+    /// # Errors
+    /// Returns a [MazeParseError] pointing to the line and column of malformed input.
+    pub fn from_ascii_broad(text: &str) -> Result<OrthogonalMaze, MazeParseError> {
+        let grid = parsers::parse_ascii_broad(text)?;
+        Ok(OrthogonalMaze { grid })
+    }
+
+    /// Parses a maze from the text in the [GameMap](crate::maze::GameMap) format with a given
+    /// span, wall and passage characters
+    ///
+    /// Any characters other than a passage are allowed on the map borders, so the map with start
+    /// and goal characters can be parsed too.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new().build();
+    /// let map = maze.format(GameMap::new().span(3)).into_inner();
+    ///
+    /// let parsed = OrthogonalMaze::from_game_map(&map, 3, '#', '.').unwrap();
+    /// assert_eq!(maze.to_string(), parsed.to_string());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [MazeParseError] pointing to the line and column of malformed input.
+    pub fn from_game_map(
+        text: &str,
+        span: usize,
+        wall: char,
+        passage: char,
+    ) -> Result<OrthogonalMaze, MazeParseError> {
+        let grid = parsers::parse_game_map(text, span, wall, passage)?;
+        Ok(OrthogonalMaze { grid })
+    }
+
+    /// Returns a ref to a grid
+    pub(crate) const fn get_grid(&self) -> &Grid {
+        &self.grid
+    }
+
     /// Returns a mutable ref to a grid
-    pub(crate) fn get_grid_mut(&mut self) -> &mut Grid {
+    pub(crate) const fn get_grid_mut(&mut self) -> &mut Grid {
         &mut self.grid
     }
 
-    /// Returns `true` if a maze is valid. Otherwise, returns `false`
+    /// Returns a maze width in a number of cells
+    pub const fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    /// Returns a maze height in a number of cells
+    pub const fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// Returns a maze [Topology], i.e. which of its borders wrap around to the opposite ones
+    pub const fn topology(&self) -> Topology {
+        self.grid.topology()
+    }
+
+    /// Returns the rooms placed in a maze by
+    /// [OrthogonalMazeBuilder::rooms](crate::maze::OrthogonalMazeBuilder::rooms), if any
+    pub fn rooms(&self) -> &[Room] {
+        self.grid.rooms()
+    }
+
+    /// Returns the events of generating a maze recorded by
+    /// [OrthogonalMazeBuilder::record_events](crate::maze::OrthogonalMazeBuilder::record_events),
+    /// if any
+    ///
+    /// The events keep being recorded as a maze changes, e.g. by an
+    /// [OriginShifter](crate::maze::OriginShifter).
+    pub fn events(&self) -> &[Event] {
+        self.grid.events()
+    }
+
+    /// Returns an iterator replaying the recorded [events](OrthogonalMaze::events) onto a blank
+    /// copy of a maze, e.g. to animate the generation process step by step
+    pub fn replay(&self) -> Replay<'_> {
+        Replay::new(&self.grid)
+    }
+
+    /// Returns the passages carved out of a cell at the given coordinates
+    ///
+    /// Returns `None` if the coordinates are out of the maze bounds.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new().build();
+    /// let passages = maze.cell((0, 0)).unwrap();
+    /// assert!(!passages.contains(Cell::NORTH));
+    /// ```
+    pub fn cell(&self, coords: Coords) -> Option<Cell> {
+        if !self.grid.contains(coords) {
+            return None;
+        }
+
+        Some(self.grid.get_cell(coords))
+    }
+
+    /// Returns the walls that remain around a cell at the given coordinates
+    ///
+    /// This is a complement to [cell](OrthogonalMaze::cell). Returns `None` if the coordinates are
+    /// out of the maze bounds.
+    pub fn walls(&self, coords: Coords) -> Option<Cell> {
+        self.cell(coords).map(|passages| passages.complement())
+    }
+
+    /// Returns the directions of a tunnel running under a cell at the given coordinates in a
+    /// [weave](crate::maze::OrthogonalMazeBuilder::weave) maze, which are empty if there is no
+    /// tunnel
+    ///
+    /// Returns `None` if the coordinates are out of the maze bounds.
+    pub fn tunnel(&self, coords: Coords) -> Option<Cell> {
+        if !self.grid.contains(coords) {
+            return None;
+        }
+
+        Some(self.grid.get_tunnel(coords))
+    }
+
+    /// Returns `true` if a passage is carved from a cell at the given coordinates in a given
+    /// direction. Otherwise, returns `false`
+    pub fn is_carved(&self, coords: Coords, direction: Cell) -> bool {
+        self.grid.contains(coords) && self.grid.is_carved(coords, direction)
+    }
+
+    /// Returns `true` if a cell at the given coordinates is a part of a maze, i.e. it's within the
+    /// maze bounds and not disabled by a [Mask]. Otherwise, returns `false`
+    pub fn is_enabled(&self, coords: Coords) -> bool {
+        self.grid.is_cell_enabled(coords)
+    }
+
+    /// Returns an iterator over the coordinates of all the cells reachable from a cell at the given
+    /// coordinates in a single step, i.e. the cells it shares a carved passage with
+    ///
+    /// The neighbours are yielded in the North, South, West, East order. The iterator is empty if
+    /// the coordinates are out of the maze bounds.
+    pub fn neighbours(&self, coords: Coords) -> impl Iterator<Item = Coords> + '_ {
+        self.grid
+            .contains(coords)
+            .then(|| self.grid.get_linked_cells(coords))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Returns an iterator over the coordinates of all the cells in a maze, row by row
+    ///
+    /// The cells disabled by a [Mask] are included too, see [is_enabled](OrthogonalMaze::is_enabled).
+    pub fn cells(&self) -> impl Iterator<Item = Coords> {
+        let width = self.grid.width();
+        (0..self.grid.height()).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Returns an iterator over all the passages in a maze
+    ///
+    /// Each passage is yielded once as a pair of the coordinates of the cells it connects, with
+    /// the cell it leads East or South from going first. That's the Western or Northern cell,
+    /// unless the passage runs across a wrapped border.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new().width(5).height(5).build();
+    ///
+    /// // A perfect maze is a spanning tree, so it has exactly one passage less than cells
+    /// assert_eq!(maze.passages().count(), 24);
+    /// ```
+    pub fn passages(&self) -> impl Iterator<Item = (Coords, Coords)> + '_ {
+        self.cells().flat_map(move |coords| {
+            [Cell::SOUTH, Cell::EAST]
+                .into_iter()
+                .filter_map(move |dir| self.grid.get_linked_cell_coords(coords, dir))
+                .map(move |next| (coords, next))
+        })
+    }
+
+    /// Returns `true` if a maze is valid, i.e. all the enabled cells are reachable from each
+    /// other. Otherwise, returns `false`
     pub fn is_valid(&self) -> bool {
         validate(&self.grid)
     }
 
+    /// Returns `true` if a maze is perfect, i.e. it's valid and there is exactly one path between
+    /// any two cells. Otherwise, if a maze has loops or unreachable cells, returns `false`
+    ///
+    /// All the algorithms generate perfect mazes, unless the maze is
+    /// [braided](crate::maze::OrthogonalMazeBuilder::braid), has the
+    /// [rooms](crate::maze::OrthogonalMazeBuilder::rooms) placed in it, which are open inside and
+    /// may have several doors, or has the open rooms left by the
+    /// [ChamberDivision](crate::maze::ChamberDivision) algorithm.
+    pub fn is_perfect(&self) -> bool {
+        // A connected graph is a tree if and only if it has one edge less than vertices
+        self.is_valid() && self.passages().count() + 1 == self.grid.enabled_cells().count()
+    }
+
     /// Saves the maze data to a file at the specified path using the provided formatter.
     ///
     /// This method converts the internal grid representation of the maze into a format
@@ -82,6 +312,91 @@ impl OrthogonalMaze {
     {
         formatter.format(&self.grid)
     }
+
+    /// Returns the ordered list of cells leading from the start to the goal found by the provided
+    /// solver, both ends included
+    ///
+    /// Returns `None` if either of the cells is out of the maze bounds or the goal cannot be
+    /// reached from the start.
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new().width(8).height(8).build();
+    /// let path = maze.solve(AStar, (0, 0), (7, 7)).unwrap();
+    ///
+    /// assert_eq!(Some(&(0, 0)), path.first());
+    /// assert_eq!(Some(&(7, 7)), path.last());
+    /// ```
+    pub fn solve<S>(&self, solver: S, start: Coords, goal: Coords) -> Option<Vec<Coords>>
+    where
+        S: Solver,
+    {
+        if !self.grid.contains(start) || !self.grid.contains(goal) {
+            return None;
+        }
+
+        solver.solve(&self.grid, start, goal)
+    }
+
+    /// Returns statistics and difficulty metrics of the maze, such as the number of dead ends,
+    /// the longest path or the directional bias of passages
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new()
+    ///     .algorithm(Box::new(GrowingTree::new(Method::Newest)))
+    ///     .build();
+    /// let stats = maze.statistics();
+    ///
+    /// println!("{} dead ends, river factor {:.2}", stats.dead_ends, stats.river_factor);
+    /// ```
+    pub fn statistics(&self) -> MazeStatistics {
+        statistics::statistics(&self.grid)
+    }
+
+    /// Returns the number of steps along the shortest path between two cells
+    ///
+    /// Returns `None` if either of the cells is out of the maze bounds or the goal cannot be
+    /// reached from the start.
+    pub fn solution_length(&self, start: Coords, goal: Coords) -> Option<usize> {
+        if !self.grid.contains(start) || !self.grid.contains(goal) {
+            return None;
+        }
+
+        let (x, y) = goal;
+        statistics::distances(&self.grid, start)[y * self.grid.width() + x]
+    }
+
+    /// Returns the number of steps along the shortest path from a cell at the given coordinates to
+    /// every cell in a maze, row by row
+    ///
+    /// The distance to the cells that cannot be reached, or to every cell if the coordinates are
+    /// out of the maze bounds, is [usize::MAX].
+    ///
+    /// # Example
+    /// ```rust
+    /// use knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMazeBuilder::new().width(5).height(5).build();
+    /// let distances = maze.distances((0, 0));
+    ///
+    /// assert_eq!(25, distances.len());
+    /// assert_eq!(0, distances[0]);
+    /// ```
+    pub fn distances(&self, from: Coords) -> Vec<usize> {
+        if !self.grid.contains(from) {
+            return vec![usize::MAX; self.grid.width() * self.grid.height()];
+        }
+
+        statistics::distances(&self.grid, from)
+            .into_iter()
+            .map(|distance| distance.unwrap_or(usize::MAX))
+            .collect()
+    }
 }
 
 impl fmt::Display for OrthogonalMaze {
@@ -114,6 +429,94 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn dimensions() {
+        let maze = OrthogonalMaze::new(3, 5);
+        assert_eq!(3, maze.width());
+        assert_eq!(5, maze.height());
+    }
+
+    #[test]
+    fn cell_passages_and_walls() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+
+        assert_eq!(
+            Some(Cell::SOUTH | Cell::EAST | Cell::WEST),
+            maze.cell((1, 1))
+        );
+        assert_eq!(Some(Cell::NORTH), maze.walls((1, 1)));
+        assert_eq!(None, maze.cell((4, 0)));
+        assert_eq!(None, maze.walls((0, 4)));
+
+        assert!(maze.is_carved((1, 1), Cell::EAST));
+        assert!(!maze.is_carved((1, 1), Cell::NORTH));
+        assert!(!maze.is_carved((4, 4), Cell::NORTH));
+    }
+
+    #[test]
+    fn neighbours() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+
+        let neighbours: Vec<Coords> = maze.neighbours((3, 1)).collect();
+        assert_eq!(vec![(3, 0), (3, 2)], neighbours);
+        assert_eq!(0, maze.neighbours((10, 10)).count());
+    }
+
+    #[test]
+    fn cells() {
+        let maze = OrthogonalMaze::new(2, 2);
+        let cells: Vec<Coords> = maze.cells().collect();
+        assert_eq!(vec![(0, 0), (1, 0), (0, 1), (1, 1)], cells);
+    }
+
+    #[test]
+    fn passages() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+
+        let passages: Vec<(Coords, Coords)> = maze.passages().collect();
+        assert_eq!(15, passages.len());
+        assert_eq!(((0, 0), (0, 1)), passages[0]);
+        assert!(passages.contains(&((3, 1), (3, 2))));
+        assert!(!passages.contains(&((2, 1), (3, 1))));
+    }
+
+    #[test]
+    fn solution_length() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+
+        assert_eq!(Some(8), maze.solution_length((0, 0), (3, 3)));
+        assert_eq!(Some(0), maze.solution_length((2, 2), (2, 2)));
+        assert_eq!(None, maze.solution_length((0, 0), (4, 4)));
+        assert_eq!(
+            None,
+            OrthogonalMaze::new(2, 2).solution_length((0, 0), (1, 1))
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+
+        assert_eq!(
+            vec![0, 9, 8, 7, 1, 2, 3, 6, 4, 3, 4, 5, 5, 6, 7, 8],
+            maze.distances((0, 0))
+        );
+        assert_eq!(vec![usize::MAX; 16], maze.distances((4, 0)));
+
+        let maze = OrthogonalMaze::new(2, 1);
+        assert_eq!(vec![0, usize::MAX], maze.distances((0, 0)));
+    }
+
     #[test]
     fn valid_maze() {
         let grid = generate_valid_maze();
@@ -121,6 +524,44 @@ mod tests {
         assert!(maze.is_valid());
     }
 
+    #[test]
+    fn perfect_maze() {
+        let mut maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        assert!(maze.is_perfect());
+
+        maze.grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        assert!(maze.is_valid());
+        assert!(!maze.is_perfect());
+
+        let maze = OrthogonalMaze {
+            grid: generate_invalid_maze(),
+        };
+        assert!(!maze.is_perfect());
+    }
+
+    #[test]
+    fn masked_maze() {
+        let mut mask = Mask::new(2, 2);
+        mask.disable((0, 0));
+        let mut maze = OrthogonalMaze::with_mask(&mask);
+        assert!(!maze.is_enabled((0, 0)));
+        assert!(maze.is_enabled((1, 0)));
+        assert!(!maze.is_valid());
+
+        maze.grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        maze.grid.carve_passage((1, 1), Cell::WEST).unwrap();
+        assert!(maze.is_valid());
+        assert!(maze.is_perfect());
+        assert_eq!(2, maze.statistics().longest_path);
+
+        mask.disable((1, 0));
+        mask.disable((0, 1));
+        mask.disable((1, 1));
+        assert!(!OrthogonalMaze::with_mask(&mask).is_valid());
+    }
+
     #[test]
     fn invalid_maze() {
         let grid = generate_invalid_maze();
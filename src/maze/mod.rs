@@ -1,20 +1,56 @@
-//! Maze representations, builders, formatters, and supported algorithms for generating mazes
+//! Maze representations, builders, formatters, solvers, and supported algorithms for generating
+//! mazes
 //!
 //! Acts as a prelude module with all the imports that are necessary for generating and saving
 //! mazes.
 
+mod braid;
 mod builder;
+mod graph;
+mod graph_maze;
 mod grid;
+mod mask;
 #[allow(clippy::module_inception)]
 mod maze;
 mod errors;
+mod events;
+mod parsers;
+mod regions;
+mod rooms;
+#[cfg(feature = "serde")]
+mod serialization;
+mod statistics;
+mod topology;
 mod validate;
 
 pub mod algorithms;
 pub mod formatters;
+pub mod solvers;
 
 pub use algorithms::*;
 pub use builder::OrthogonalMazeBuilder;
-pub use formatters::{AsciiNarrow, AsciiBroad, GameMap, Image};
-pub use errors::MazeSaveError;
+pub use formatters::{Animation, AsciiNarrow, AsciiBroad, AsciiPath, GameMap, Image, Svg};
+pub use graph::Graph;
+pub use graph_maze::{
+    CellGrid, DeltaMaze, DeltaMazeBuilder, GraphGrid, GraphMaze, GraphMazeBuilder, HexMaze,
+    HexMazeBuilder, LayeredMaze, LayeredMazeBuilder, PolarMaze, PolarMazeBuilder, UpsilonMaze,
+    UpsilonMazeBuilder,
+};
+pub use grid::{
+    cell::Cell,
+    delta::{DeltaCell, DeltaGrid},
+    hex::{HexCell, HexGrid},
+    layered::{LayeredCell, LayeredGrid},
+    polar::PolarGrid,
+    upsilon::{UpsilonCell, UpsilonGrid},
+    Grid,
+};
+pub use events::{Event, Replay};
+pub use errors::{LinkError, MaskError, MazeParseError, MazeSaveError, MutationError, TransitError};
+pub use mask::Mask;
 pub use maze::OrthogonalMaze;
+pub use rooms::{Room, Rooms};
+pub use solvers::*;
+pub use statistics::MazeStatistics;
+pub use topology::Topology;
+pub use crate::utils::types::{Coords, LayeredCoords, PolarCoords};
@@ -0,0 +1,249 @@
+use super::{error, Input, ParseResult};
+use crate::maze::grid::{cell::Cell, Grid};
+
+/// The characters that draw walls in the [AsciiBroad](crate::maze::AsciiBroad) output and thus
+/// cannot be used as markers of a path
+const BROAD_WALLS: [char; 3] = ['|', '-', '+'];
+
+/// Parses a maze in the [AsciiNarrow](crate::maze::AsciiNarrow) format into a grid
+pub fn parse_ascii_narrow(text: &str) -> ParseResult<Grid> {
+    let input = Input::new(text)?;
+
+    if input.width() < 3 || input.width() % 2 == 0 {
+        return Err(error(
+            (0, 0),
+            "Expected lines of an odd number of characters, at least 3",
+        ));
+    }
+    if input.height() < 2 {
+        return Err(error((0, 1), "Expected at least one row of cells"));
+    }
+
+    let width = (input.width() - 1) / 2;
+    let height = input.height() - 1;
+    let mut grid = Grid::new(width, height);
+
+    input.expect((0, 0), &format!(" {} ", "_".repeat(width * 2 - 1)))?;
+
+    for y in 0..height {
+        let line = y + 1;
+        input.expect((0, line), "|")?;
+
+        for x in 0..width {
+            let floor = (x * 2 + 1, line);
+            match input.char_at(floor) {
+                '_' => {}
+                ' ' if y + 1 < height => carve(&mut grid, (x, y), Cell::SOUTH),
+                ' ' => return Err(error(floor, "Expected the Southern border '_'")),
+                ch => return Err(error(floor, format!("Unexpected character '{}'", ch))),
+            }
+
+            let boundary = (x * 2 + 2, line);
+            match input.char_at(boundary) {
+                '|' => {}
+                ' ' | '_' if x + 1 < width => carve(&mut grid, (x, y), Cell::EAST),
+                ' ' | '_' => return Err(error(boundary, "Expected the Eastern border '|'")),
+                ch => return Err(error(boundary, format!("Unexpected character '{}'", ch))),
+            }
+        }
+    }
+
+    Ok(grid)
+}
+
+/// Parses a maze in the [AsciiBroad](crate::maze::AsciiBroad) format into a grid
+///
+/// The characters inside the cells and passages are ignored, so the output with a path drawn is
+/// parsed too. The corners between cells are drawn as `+`, except the blank ones inside the maze
+/// with no walls meeting at them.
+pub fn parse_ascii_broad(text: &str) -> ParseResult<Grid> {
+    let input = Input::new(text)?;
+
+    if input.width() < 5 || (input.width() - 1) % 4 != 0 {
+        return Err(error(
+            (0, 0),
+            "Expected lines of 4 characters per cell plus 1, at least 5",
+        ));
+    }
+    if input.height() < 3 || input.height() % 2 == 0 {
+        return Err(error(
+            (0, input.height()),
+            "Expected 2 lines per row of cells plus 1, at least 3",
+        ));
+    }
+
+    let width = (input.width() - 1) / 4;
+    let height = (input.height() - 1) / 2;
+    let mut grid = Grid::new(width, height);
+
+    input.expect((0, 0), &format!("+{}", "---+".repeat(width)))?;
+
+    for y in 0..height {
+        let (top, bottom) = (y * 2 + 1, y * 2 + 2);
+        input.expect((0, top), "|")?;
+        input.expect((0, bottom), "+")?;
+
+        for x in 0..width {
+            for i in 1..4 {
+                expect_no_wall(&input, (x * 4 + i, top))?;
+            }
+
+            let east = (x * 4 + 4, top);
+            if input.char_at(east) != '|' {
+                if x + 1 == width {
+                    return Err(error(east, "Expected the Eastern border '|'"));
+                }
+                expect_no_wall(&input, east)?;
+                carve(&mut grid, (x, y), Cell::EAST);
+            }
+
+            let south = (x * 4 + 1, bottom);
+            if y + 1 == height || input.char_at(south) == '-' {
+                input.expect(south, "---")?;
+            } else {
+                for i in 0..3 {
+                    expect_no_wall(&input, (x * 4 + 1 + i, bottom))?;
+                }
+                carve(&mut grid, (x, y), Cell::SOUTH);
+            }
+
+            if x + 1 == width || y + 1 == height {
+                input.expect((x * 4 + 4, bottom), "+")?;
+            }
+        }
+    }
+
+    // A corner inside the maze is blank when no walls meet at it, as in the middle of an open
+    // chamber, which is only known once the passages of all four cells around it are parsed
+    for y in 1..height {
+        for x in 1..width {
+            let is_open = grid.is_carved((x - 1, y - 1), Cell::EAST)
+                && grid.is_carved((x - 1, y - 1), Cell::SOUTH)
+                && grid.is_carved((x, y), Cell::WEST)
+                && grid.is_carved((x, y), Cell::NORTH);
+            input.expect((x * 4, y * 2), if is_open { " " } else { "+" })?;
+        }
+    }
+
+    Ok(grid)
+}
+
+fn expect_no_wall(input: &Input, pos: (usize, usize)) -> ParseResult<()> {
+    let ch = input.char_at(pos);
+    if BROAD_WALLS.contains(&ch) {
+        return Err(error(pos, format!("Unexpected character '{}'", ch)));
+    }
+
+    Ok(())
+}
+
+fn carve(grid: &mut Grid, coords: (usize, usize), direction: Cell) {
+    // The bounds are checked while parsing, so the passage never leads out of the grid
+    grid.carve_passage(coords, direction).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{formatters::Formatter, solvers::tests::generate_maze, AsciiBroad, AsciiNarrow};
+
+    #[test]
+    fn parse_narrow() {
+        let (expected, _) = generate_maze();
+        let text = AsciiNarrow.format(&expected).into_inner();
+
+        let grid = parse_ascii_narrow(&text).unwrap();
+        assert_eq!(expected.to_string(), grid.to_string());
+    }
+
+    #[test]
+    fn parse_narrow_single_cell() {
+        let grid = parse_ascii_narrow(" _ \n|_|\n").unwrap();
+        assert_eq!((1, 1), (grid.width(), grid.height()));
+        assert_eq!(Cell::empty(), grid.get_cell((0, 0)));
+    }
+
+    #[test]
+    fn parse_narrow_malformed() {
+        let error = parse_ascii_narrow("").err().unwrap();
+        assert_eq!(
+            (1, 1, "Input is empty"),
+            (error.line, error.column, &*error.reason)
+        );
+
+        let error = parse_ascii_narrow(" _ \n|_|\n|_\n").err().unwrap();
+        assert_eq!((3, 3), (error.line, error.column));
+        assert_eq!("Expected a line of 3 characters, found 2", error.reason);
+
+        let error = parse_ascii_narrow(" ___ \n|_ _|\n|_x_|\n").err().unwrap();
+        assert_eq!((3, 3), (error.line, error.column));
+        assert_eq!("Unexpected character 'x'", error.reason);
+
+        let error = parse_ascii_narrow(" ___ \n|_ _ \n").err().unwrap();
+        assert_eq!((2, 5), (error.line, error.column));
+        assert_eq!("Expected the Eastern border '|'", error.reason);
+
+        let error = parse_ascii_narrow(" ___ \n|_  |\n").err().unwrap();
+        assert_eq!((2, 4), (error.line, error.column));
+        assert_eq!("Expected the Southern border '_'", error.reason);
+    }
+
+    #[test]
+    fn parse_broad() {
+        let (expected, path) = generate_maze();
+
+        let text = AsciiBroad.format(&expected).into_inner();
+        let grid = parse_ascii_broad(&text).unwrap();
+        assert_eq!(expected.to_string(), grid.to_string());
+
+        let text = AsciiBroad
+            .with_path(path)
+            .start((0, 0))
+            .goal((3, 3))
+            .format(&expected)
+            .into_inner();
+        let grid = parse_ascii_broad(&text).unwrap();
+        assert_eq!(expected.to_string(), grid.to_string());
+    }
+
+    #[test]
+    fn parse_broad_blank_corner() {
+        let grid =
+            parse_ascii_broad("+---+---+\n|       |\n+       +\n|       |\n+---+---+\n").unwrap();
+        for coords in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            assert_eq!(2, grid.get_linked_cells(coords).count());
+        }
+    }
+
+    #[test]
+    fn parse_broad_malformed() {
+        let error = parse_ascii_broad("+---+\n|   |\n").err().unwrap();
+        assert_eq!((3, 1), (error.line, error.column));
+
+        let error = parse_ascii_broad("+---+\n|   |\n+- -+\n").err().unwrap();
+        assert_eq!((3, 3), (error.line, error.column));
+        assert_eq!("Expected '---', found '- -'", error.reason);
+
+        let error = parse_ascii_broad("+---+---+\n| | |   |\n+---+---+\n")
+            .err()
+            .unwrap();
+        assert_eq!((2, 3), (error.line, error.column));
+        assert_eq!("Unexpected character '|'", error.reason);
+
+        let error = parse_ascii_broad("+---+\n|    \n+---+\n").err().unwrap();
+        assert_eq!((2, 5), (error.line, error.column));
+        assert_eq!("Expected the Eastern border '|'", error.reason);
+
+        let error = parse_ascii_broad("+---+\n|   |\n+   +\n|   |\n*---+\n")
+            .err()
+            .unwrap();
+        assert_eq!((5, 1), (error.line, error.column));
+        assert_eq!("Expected '+', found '*'", error.reason);
+
+        let error = parse_ascii_broad("+---+---+\n|   |   |\n+       +\n|   |   |\n+---+---+\n")
+            .err()
+            .unwrap();
+        assert_eq!((3, 5), (error.line, error.column));
+        assert_eq!("Expected '+', found ' '", error.reason);
+    }
+}
@@ -0,0 +1,173 @@
+use super::{error, Input, ParseResult};
+use crate::maze::grid::{cell::Cell, Grid};
+
+/// Parses a maze in the [GameMap](crate::maze::GameMap) format with a given span, wall and
+/// passage characters into a grid
+///
+/// Any characters other than a passage are allowed on the map borders, so the output with the
+/// start and goal characters is parsed too.
+pub fn parse_game_map(text: &str, span: usize, wall: char, passage: char) -> ParseResult<Grid> {
+    if span == 0 {
+        return Err(error((0, 0), "Span must be at least 1"));
+    }
+
+    let input = Input::new(text)?;
+    let step = span + 1;
+
+    if input.width() < step + 1 || (input.width() - 1) % step != 0 {
+        return Err(error(
+            (0, 0),
+            format!("Expected lines of {} characters per cell plus 1", step),
+        ));
+    }
+    if input.height() < step + 1 || (input.height() - 1) % step != 0 {
+        return Err(error(
+            (0, input.height()),
+            format!("Expected {} lines per row of cells plus 1", step),
+        ));
+    }
+
+    for y in 0..input.height() {
+        for x in 0..input.width() {
+            let ch = input.char_at((x, y));
+            let is_border = y == 0 || y + 1 == input.height() || x == 0 || x + 1 == input.width();
+
+            if is_border && ch == passage {
+                return Err(error((x, y), "Unexpected passage on the border"));
+            }
+            if !is_border && ch != wall && ch != passage {
+                return Err(error((x, y), format!("Unexpected character '{}'", ch)));
+            }
+        }
+    }
+
+    let width = (input.width() - 1) / step;
+    let height = (input.height() - 1) / step;
+    let mut grid = Grid::new(width, height);
+
+    for cy in 0..height {
+        for cx in 0..width {
+            let (left, top) = (cx * step + 1, cy * step + 1);
+
+            for y in top..top + span {
+                for x in left..left + span {
+                    if input.char_at((x, y)) != passage {
+                        return Err(error((x, y), format!("Expected a passage '{}'", passage)));
+                    }
+                }
+            }
+
+            if cx + 1 < width {
+                let side: Vec<_> = (top..top + span).map(|y| (left + span, y)).collect();
+                if is_open(&input, &side, passage)? {
+                    grid.carve_passage((cx, cy), Cell::EAST).unwrap();
+                }
+            }
+
+            if cy + 1 < height {
+                let side: Vec<_> = (left..left + span).map(|x| (x, top + span)).collect();
+                if is_open(&input, &side, passage)? {
+                    grid.carve_passage((cx, cy), Cell::SOUTH).unwrap();
+                }
+            }
+        }
+    }
+
+    Ok(grid)
+}
+
+/// Returns `true` if a side shared by two cells is entirely made of passages and `false` if it is
+/// entirely made of walls
+fn is_open(input: &Input, side: &[(usize, usize)], passage: char) -> ParseResult<bool> {
+    let open = input.char_at(side[0]) == passage;
+
+    match side
+        .iter()
+        .find(|pos| (input.char_at(**pos) == passage) != open)
+    {
+        Some(pos) => Err(error(
+            *pos,
+            "Expected a side between two cells to be either a solid wall or an open passage",
+        )),
+        None => Ok(open),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{formatters::Formatter, solvers::tests::generate_maze, GameMap};
+
+    #[test]
+    fn parse() {
+        let (expected, _) = generate_maze();
+
+        for span in 1..4 {
+            let text = GameMap::new().span(span).format(&expected).into_inner();
+            let grid = parse_game_map(&text, span, '#', '.').unwrap();
+            assert_eq!(expected.to_string(), grid.to_string());
+        }
+    }
+
+    #[test]
+    fn parse_with_start_goal() {
+        let (expected, _) = generate_maze();
+        let text = GameMap::new()
+            .span(1)
+            .wall('X')
+            .passage(' ')
+            .with_start_goal()
+            .seed(Some(1))
+            .format(&expected)
+            .into_inner();
+
+        let grid = parse_game_map(&text, 1, 'X', ' ').unwrap();
+        assert_eq!(expected.to_string(), grid.to_string());
+    }
+
+    #[test]
+    fn parse_malformed() {
+        let error = parse_game_map("###\n#.#\n###\n", 0, '#', '.')
+            .err()
+            .unwrap();
+        assert_eq!("Span must be at least 1", error.reason);
+
+        let error = parse_game_map("####\n#..#\n####\n", 1, '#', '.')
+            .err()
+            .unwrap();
+        assert_eq!((1, 1), (error.line, error.column));
+        assert_eq!(
+            "Expected lines of 2 characters per cell plus 1",
+            error.reason
+        );
+
+        let error = parse_game_map("###\n#.#\n###\n#.#\n", 1, '#', '.')
+            .err()
+            .unwrap();
+        assert_eq!((5, 1), (error.line, error.column));
+        assert_eq!("Expected 2 lines per row of cells plus 1", error.reason);
+
+        let error = parse_game_map("#.#\n#.#\n###\n", 1, '#', '.')
+            .err()
+            .unwrap();
+        assert_eq!((1, 2), (error.line, error.column));
+        assert_eq!("Unexpected passage on the border", error.reason);
+
+        let error = parse_game_map("#####\n#.?.#\n#####\n", 1, '#', '.')
+            .err()
+            .unwrap();
+        assert_eq!((2, 3), (error.line, error.column));
+        assert_eq!("Unexpected character '?'", error.reason);
+
+        let error = parse_game_map("###\n###\n###\n", 1, '#', '.')
+            .err()
+            .unwrap();
+        assert_eq!((2, 2), (error.line, error.column));
+        assert_eq!("Expected a passage '.'", error.reason);
+
+        let error = parse_game_map("#######\n#..#..#\n#.....#\n#######\n", 2, '#', '.')
+            .err()
+            .unwrap();
+        assert_eq!((3, 4), (error.line, error.column));
+    }
+}
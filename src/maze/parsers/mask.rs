@@ -0,0 +1,55 @@
+use super::{error, Input, ParseResult};
+use crate::maze::mask::Mask;
+
+/// The character that disables a cell of a mask
+const DISABLED: char = 'X';
+
+/// Parses a mask from text, where the [DISABLED] character stands for a disabled cell and any
+/// other character stands for an enabled one
+pub fn parse_mask(text: &str) -> ParseResult<Mask> {
+    let input = Input::new(text)?;
+    let mut mask = Mask::new(input.width(), input.height());
+
+    for y in 0..input.height() {
+        for x in 0..input.width() {
+            if input.char_at((x, y)) == DISABLED {
+                mask.disable((x, y));
+            }
+        }
+    }
+
+    if mask.count() == 0 {
+        return Err(error((0, 0), "Expected at least one enabled cell"));
+    }
+
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let mask = parse_mask(" X \n#X.\n").unwrap();
+
+        assert_eq!((3, 2), (mask.width(), mask.height()));
+        assert!(mask.is_enabled((0, 0)));
+        assert!(!mask.is_enabled((1, 0)));
+        assert!(!mask.is_enabled((1, 1)));
+        assert!(mask.is_enabled((2, 1)));
+    }
+
+    #[test]
+    fn parse_malformed() {
+        let error = parse_mask("").err().unwrap();
+        assert_eq!("Input is empty", error.reason);
+
+        let error = parse_mask("...\n..\n").err().unwrap();
+        assert_eq!((2, 3), (error.line, error.column));
+
+        let error = parse_mask("XX\nXX\n").err().unwrap();
+        assert_eq!((1, 1), (error.line, error.column));
+        assert_eq!("Expected at least one enabled cell", error.reason);
+    }
+}
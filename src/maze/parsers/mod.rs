@@ -0,0 +1,88 @@
+//! Parsers for converting the text output of the formatters back into a grid, and the text masks
+//! into a mask
+
+mod ascii;
+mod game_map;
+mod mask;
+
+use super::errors::MazeParseError;
+
+pub use ascii::{parse_ascii_broad, parse_ascii_narrow};
+pub use game_map::parse_game_map;
+pub use mask::parse_mask;
+
+type ParseResult<T> = Result<T, MazeParseError>;
+
+/// A text split into lines of characters, which keeps track of the positions for error reporting
+struct Input {
+    lines: Vec<Vec<char>>,
+}
+
+impl Input {
+    /// Splits a given text into lines, making sure there is at least one line and all of them are
+    /// equally long
+    fn new(text: &str) -> ParseResult<Input> {
+        let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+
+        let Some(first) = lines.first().filter(|line| !line.is_empty()) else {
+            return Err(error((0, 0), "Input is empty"));
+        };
+
+        let width = first.len();
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                return Err(error(
+                    (line.len().min(width), y),
+                    format!(
+                        "Expected a line of {} characters, found {}",
+                        width,
+                        line.len()
+                    ),
+                ));
+            }
+        }
+
+        Ok(Input { lines })
+    }
+
+    fn width(&self) -> usize {
+        self.lines[0].len()
+    }
+
+    const fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn char_at(&self, (x, y): (usize, usize)) -> char {
+        self.lines[y][x]
+    }
+
+    /// Checks that the characters at the given position match an expected string
+    fn expect(&self, (x, y): (usize, usize), expected: &str) -> ParseResult<()> {
+        for (i, ch) in expected.chars().enumerate() {
+            if self.char_at((x + i, y)) != ch {
+                return Err(error(
+                    (x + i, y),
+                    format!(
+                        "Expected '{}', found '{}'",
+                        expected,
+                        self.lines[y][x..x + expected.chars().count()]
+                            .iter()
+                            .collect::<String>()
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns a parse error at a given zero-based position of a character in the input
+fn error((x, y): (usize, usize), reason: impl Into<String>) -> MazeParseError {
+    MazeParseError {
+        line: y + 1,
+        column: x + 1,
+        reason: reason.into(),
+    }
+}
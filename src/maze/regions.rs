@@ -0,0 +1,128 @@
+use super::grid::{cell::Cell, Grid};
+use crate::utils::types::Coords;
+use rand::prelude::*;
+use std::collections::VecDeque;
+
+/// Joins the regions of a grid that are unreachable from each other by carving a single passage
+/// between every two adjacent ones, so a perfect maze stays perfect
+///
+/// Some algorithms rely on the full rows, columns or borders of a grid to reach every cell, so the
+/// cells disabled by a mask or reserved for the rooms may cut off some regions of a maze. The
+/// regions are joined through random walls between them, the same way the Kruskal's algorithm joins
+/// its sets. A grid with all the enabled cells reachable is left as is, and so are the regions a
+/// mask keeps apart with no wall between them.
+pub fn connect_regions(grid: &mut Grid, rng: &mut StdRng) {
+    let regions = regions(grid);
+    let count = regions.iter().flatten().max().map_or(0, |max| max + 1);
+    if count < 2 {
+        return;
+    }
+
+    let width = grid.width();
+    let region = |(x, y): Coords| regions[y * width + x];
+    let mut walls: Vec<(Coords, Cell, Coords)> = grid
+        .enabled_cells()
+        .flat_map(|coords| [Cell::EAST, Cell::SOUTH].map(|dir| (coords, dir)))
+        .filter_map(|(coords, dir)| {
+            let next = grid.get_next_cell_coords(coords, dir).ok()?;
+            (region(coords) != region(next)).then_some((coords, dir, next))
+        })
+        .collect();
+    walls.shuffle(rng);
+
+    let mut sets: Vec<usize> = (0..count).collect();
+    for (coords, dir, next) in walls {
+        let set = find(&mut sets, region(coords).unwrap());
+        let next_set = find(&mut sets, region(next).unwrap());
+
+        // A passage cannot cut through a tunnel of a weave maze, so another wall is used instead
+        if set != next_set && grid.carve_passage(coords, dir).is_ok() {
+            sets[set] = next_set;
+        }
+    }
+}
+
+/// Returns the number of the region of every cell row by row, where the cells of a region are all
+/// reachable from each other, or `None` for the disabled cells
+fn regions(grid: &Grid) -> Vec<Option<usize>> {
+    let mut regions = vec![None; grid.width() * grid.height()];
+    let index = |(x, y): Coords| y * grid.width() + x;
+    let mut count = 0;
+
+    for start in grid.enabled_cells() {
+        if regions[index(start)].is_some() {
+            continue;
+        }
+
+        regions[index(start)] = Some(count);
+        let mut queue = VecDeque::from([start]);
+        while let Some(coords) = queue.pop_front() {
+            for next in grid.get_linked_cells(coords) {
+                if regions[index(next)].is_none() {
+                    regions[index(next)] = Some(count);
+                    queue.push_back(next);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    regions
+}
+
+/// Returns the representative of a set in a disjoint set forest, compressing the path to it
+fn find(sets: &mut [usize], set: usize) -> usize {
+    if sets[set] != set {
+        sets[set] = find(sets, sets[set]);
+    }
+    sets[set]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connect_cut_off_regions() {
+        let mut grid = Grid::new(3, 3);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((2, 2), Cell::NORTH).unwrap();
+        assert_eq!(Some(6), regions(&grid).into_iter().flatten().max());
+
+        connect_regions(&mut grid, &mut StdRng::seed_from_u64(3));
+
+        assert!(regions(&grid).iter().all(|region| *region == Some(0)));
+        let passages: usize = grid
+            .enabled_cells()
+            .map(|coords| grid.get_linked_cells(coords).count())
+            .sum();
+        assert_eq!(2 * 8, passages);
+    }
+
+    #[test]
+    fn keep_connected_grid() {
+        let mut grid = Grid::new(3, 1);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+        let expected = grid.to_string();
+
+        let mut rng = StdRng::seed_from_u64(5);
+        connect_regions(&mut grid, &mut rng);
+
+        assert_eq!(expected, grid.to_string());
+        assert_eq!(
+            StdRng::seed_from_u64(5).random::<u64>(),
+            rng.random::<u64>()
+        );
+    }
+
+    #[test]
+    fn keep_regions_apart_with_no_wall_between() {
+        let mut grid = Grid::new(3, 1);
+        grid.disable_cell((1, 0));
+
+        connect_regions(&mut grid, &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(vec![Some(0), None, Some(1)], regions(&grid));
+    }
+}
@@ -0,0 +1,310 @@
+use super::grid::{cell::Cell, Grid};
+use crate::utils::types::Coords;
+use rand::prelude::*;
+use std::ops::RangeInclusive;
+
+/// The number of attempts to place each room before giving up on it
+const ATTEMPTS_PER_ROOM: usize = 50;
+
+/// A layout of the rectangular rooms to place in a maze before it's generated
+///
+/// The rooms are placed at random, so that they neither overlap nor touch each other or the
+/// borders of a maze, and the rest of the space is left to an algorithm to fill with a maze.
+/// After that, each room is opened up and connected with the maze through a given number of
+/// doors. A room that doesn't fit into a maze after a number of attempts is skipped, so a maze may
+/// get fewer rooms than requested.
+///
+/// The rooms work like a [Mask](crate::maze::Mask) for an algorithm, so the regions an algorithm
+/// cuts off around the rooms are joined the same way as around the disabled cells of a mask. When
+/// a room splits the space a mask leaves around it, the regions are joined through the room, which
+/// may give it more doors than requested.
+///
+/// # Example
+/// ```rust
+/// use knossos::maze::*;
+///
+/// let maze = OrthogonalMazeBuilder::new()
+///     .width(20)
+///     .height(20)
+///     .rooms(Rooms::new(3).width(3..=5).height(3..=5).doors(1..=2))
+///     .build();
+///
+/// assert!(maze.is_valid());
+/// for room in maze.rooms() {
+///     assert!((1..=2).contains(&room.doors.len()));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rooms {
+    count: usize,
+    width: RangeInclusive<usize>,
+    height: RangeInclusive<usize>,
+    doors: RangeInclusive<usize>,
+}
+
+impl Rooms {
+    /// Returns a new layout of a given number of rooms from 3 to 5 cells wide and tall with one
+    /// or two doors each
+    pub const fn new(count: usize) -> Rooms {
+        Rooms {
+            count,
+            width: 3..=5,
+            height: 3..=5,
+            doors: 1..=2,
+        }
+    }
+
+    /// Sets a range of the room widths in a number of cells and returns itself
+    pub fn width(mut self, width: RangeInclusive<usize>) -> Self {
+        self.width = non_empty(width, 1);
+        self
+    }
+
+    /// Sets a range of the room heights in a number of cells and returns itself
+    pub fn height(mut self, height: RangeInclusive<usize>) -> Self {
+        self.height = non_empty(height, 1);
+        self
+    }
+
+    /// Sets a range of the number of doors in each room and returns itself
+    ///
+    /// Every room has at least one door, so it's reachable from the rest of a maze.
+    pub fn doors(mut self, doors: RangeInclusive<usize>) -> Self {
+        self.doors = non_empty(doors, 1);
+        self
+    }
+}
+
+/// A room placed in a maze
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Room {
+    /// The coordinates of the top left cell of a room
+    pub position: Coords,
+
+    /// The width of a room in a number of cells
+    pub width: usize,
+
+    /// The height of a room in a number of cells
+    pub height: usize,
+
+    /// The doors of a room as the coordinates of a cell along its edge and the direction of the
+    /// passage out of the room
+    pub doors: Vec<(Coords, Cell)>,
+}
+
+impl Room {
+    /// Returns `true` if a room covers a cell at the given coordinates. Otherwise, returns `false`
+    pub const fn contains(&self, (x, y): Coords) -> bool {
+        let (rx, ry) = self.position;
+        x >= rx && x < rx + self.width && y >= ry && y < ry + self.height
+    }
+
+    /// Returns an iterator over the coordinates of all the cells of a room, row by row
+    fn cells(&self) -> impl Iterator<Item = Coords> + '_ {
+        let (rx, ry) = self.position;
+        (ry..ry + self.height).flat_map(move |y| (rx..rx + self.width).map(move |x| (x, y)))
+    }
+
+    /// Returns an iterator over the cells along the edges of a room and the directions out of it
+    fn edges(&self) -> impl Iterator<Item = (Coords, Cell)> + '_ {
+        self.cells().flat_map(move |(x, y)| {
+            [
+                (y == self.position.1, Cell::NORTH),
+                (y + 1 == self.position.1 + self.height, Cell::SOUTH),
+                (x == self.position.0, Cell::WEST),
+                (x + 1 == self.position.0 + self.width, Cell::EAST),
+            ]
+            .into_iter()
+            .filter(|(is_edge, _)| *is_edge)
+            .map(move |(_, dir)| ((x, y), dir))
+        })
+    }
+
+    /// Returns `true` if a room overlaps or touches another one, even diagonally. Otherwise,
+    /// returns `false`
+    const fn touches(&self, other: &Room) -> bool {
+        let ((ax, ay), (bx, by)) = (self.position, other.position);
+        ax <= bx + other.width
+            && bx <= ax + self.width
+            && ay <= by + other.height
+            && by <= ay + self.height
+    }
+}
+
+/// Places the rooms of a given layout in a grid at random and disables their cells, so an
+/// algorithm generates a maze around them
+///
+/// Each room is kept at least one cell away from the others and from the borders of a grid, so the
+/// space around the rooms stays connected. A room is only placed next to at least one enabled cell
+/// outside of it, so it can get a door.
+pub fn place_rooms(grid: &mut Grid, layout: &Rooms, rng: &mut StdRng) -> Vec<Room> {
+    let mut rooms: Vec<Room> = Vec::new();
+
+    for _ in 0..layout.count * ATTEMPTS_PER_ROOM {
+        if rooms.len() == layout.count {
+            break;
+        }
+
+        let width = rng.random_range(layout.width.clone());
+        let height = rng.random_range(layout.height.clone());
+        if width + 2 > grid.width() || height + 2 > grid.height() {
+            continue;
+        }
+
+        let room = Room {
+            position: (
+                rng.random_range(1..grid.width() - width),
+                rng.random_range(1..grid.height() - height),
+            ),
+            width,
+            height,
+            doors: Vec::new(),
+        };
+        if rooms.iter().any(|other| room.touches(other))
+            || room.cells().any(|coords| !grid.is_cell_enabled(coords))
+            || room
+                .edges()
+                .all(|(coords, dir)| grid.get_next_cell_coords(coords, dir).is_err())
+        {
+            continue;
+        }
+
+        rooms.push(room);
+    }
+
+    for coords in rooms.iter().flat_map(|room| room.cells()) {
+        grid.disable_cell(coords);
+    }
+
+    rooms
+}
+
+/// Enables the cells of the given rooms, carves the passages between them, so each room is open,
+/// and connects it with the rest of a maze through a number of doors of a given layout
+pub fn open_rooms(grid: &mut Grid, rooms: &mut [Room], layout: &Rooms, rng: &mut StdRng) {
+    for room in rooms.iter_mut() {
+        let cells: Vec<Coords> = room.cells().collect();
+        for &coords in &cells {
+            grid.enable_cell(coords);
+        }
+
+        let mut exits = Vec::new();
+        for &coords in &cells {
+            for dir in [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST] {
+                let Ok(next) = grid.get_next_cell_coords(coords, dir) else {
+                    continue;
+                };
+
+                if room.contains(next) {
+                    grid.carve_passage(coords, dir).unwrap();
+                } else {
+                    exits.push((coords, dir));
+                }
+            }
+        }
+
+        let count = rng.random_range(layout.doors.clone());
+        room.doors = exits.choose_multiple(rng, count).copied().collect();
+        for &(coords, dir) in &room.doors {
+            grid.carve_passage(coords, dir).unwrap();
+        }
+    }
+}
+
+/// Adds the passages out of the given rooms carved after they were opened to their doors, e.g.
+/// the ones joining the regions a room cuts off from each other
+pub fn add_doors(grid: &Grid, rooms: &mut [Room]) {
+    for room in rooms.iter_mut() {
+        let doors: Vec<(Coords, Cell)> = room
+            .edges()
+            .filter(|(coords, dir)| grid.is_carved(*coords, *dir))
+            .filter(|door| !room.doors.contains(door))
+            .collect();
+        room.doors.extend(doors);
+    }
+}
+
+/// Returns a given range, or a single value range of its start if it's empty, with the start of
+/// at least a given minimum
+fn non_empty(range: RangeInclusive<usize>, min: usize) -> RangeInclusive<usize> {
+    let start = (*range.start()).max(min);
+    start..=(*range.end()).max(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_change() {
+        let (start, end) = (4, 2);
+        let rooms = Rooms::new(2).width(start..=end).height(0..=3).doors(0..=0);
+        assert_eq!(4..=4, rooms.width);
+        assert_eq!(1..=3, rooms.height);
+        assert_eq!(1..=1, rooms.doors);
+    }
+
+    #[test]
+    fn place_separate_rooms() {
+        let mut grid = Grid::new(12, 10);
+        let mut rng = StdRng::seed_from_u64(4);
+        let rooms = place_rooms(&mut grid, &Rooms::new(4).width(2..=3), &mut rng);
+
+        assert!(!rooms.is_empty());
+        for (i, room) in rooms.iter().enumerate() {
+            assert!(rooms[i + 1..].iter().all(|other| !room.touches(other)));
+            assert!(room.cells().all(|coords| !grid.is_cell_enabled(coords)));
+
+            let (x, y) = room.position;
+            assert!(x > 0 && y > 0);
+            assert!(x + room.width < 12 && y + room.height < 10);
+        }
+        assert_eq!(
+            12 * 10
+                - rooms
+                    .iter()
+                    .map(|room| room.width * room.height)
+                    .sum::<usize>(),
+            grid.enabled_cells().count()
+        );
+    }
+
+    #[test]
+    fn skip_rooms_that_do_not_fit() {
+        let mut grid = Grid::new(4, 4);
+        let mut rng = StdRng::seed_from_u64(1);
+        assert!(place_rooms(&mut grid, &Rooms::new(1).width(3..=3), &mut rng).is_empty());
+
+        let rooms = place_rooms(
+            &mut grid,
+            &Rooms::new(3).width(2..=2).height(2..=2),
+            &mut rng,
+        );
+        assert_eq!(1, rooms.len());
+    }
+
+    #[test]
+    fn open_rooms_with_doors() {
+        let mut grid = Grid::new(7, 6);
+        let mut rng = StdRng::seed_from_u64(2);
+        let layout = Rooms::new(1).width(3..=3).height(2..=2).doors(2..=2);
+        let mut rooms = place_rooms(&mut grid, &layout, &mut rng);
+        open_rooms(&mut grid, &mut rooms, &layout, &mut rng);
+
+        let room = &rooms[0];
+        assert_eq!(2, room.doors.len());
+        assert!(room.cells().all(|coords| grid.is_cell_enabled(coords)));
+        for coords in room.cells() {
+            for (dir, next) in grid.get_adjacent_cells(coords) {
+                if room.contains(next) {
+                    assert!(grid.is_carved(coords, dir));
+                }
+            }
+        }
+        for &(coords, dir) in &room.doors {
+            assert!(room.contains(coords));
+            assert!(grid.is_carved(coords, dir));
+            assert!(!room.contains(grid.get_next_cell_coords(coords, dir).unwrap()));
+        }
+    }
+}
@@ -0,0 +1,430 @@
+//! Serialization and deserialization of mazes, available with the `serde` feature
+//!
+//! A maze is stored as its dimensions and a flat list of cells, row by row, where each cell is the
+//! bits of the [Cell] passages carved out of it. A weave maze also lists the coordinates of the
+//! cells with tunnels running under them, a masked maze lists the coordinates of the disabled
+//! cells, a maze with rooms lists their rectangles and doors, and a wrap-around maze stores its
+//! [Topology]. On load,
+//! the data is validated so that only a maze that could have been carved on a grid of the given
+//! dimensions and topology is accepted.
+
+use super::{grid::cell::Cell, mask::Mask, maze::OrthogonalMaze, rooms::Room, topology::Topology};
+use crate::utils::types::Coords;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "OrthogonalMaze")]
+struct MazeData {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tunnels: Vec<Coords>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    disabled: Vec<Coords>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rooms: Vec<RoomData>,
+    #[serde(default, skip_serializing_if = "is_plane")]
+    topology: Topology,
+}
+
+/// A room stored as its rectangle and its doors, where each door is the coordinates of a cell and
+/// the bits of the [Cell] direction of the passage out of the room
+#[derive(Serialize, Deserialize)]
+struct RoomData {
+    position: Coords,
+    width: usize,
+    height: usize,
+    doors: Vec<(Coords, u8)>,
+}
+
+fn is_plane(topology: &Topology) -> bool {
+    *topology == Topology::Plane
+}
+
+impl From<&OrthogonalMaze> for MazeData {
+    fn from(maze: &OrthogonalMaze) -> Self {
+        MazeData {
+            width: maze.width(),
+            height: maze.height(),
+            cells: maze
+                .cells()
+                .map(|coords| maze.cell(coords).unwrap().bits())
+                .collect(),
+            tunnels: maze
+                .cells()
+                .filter(|coords| !maze.tunnel(*coords).unwrap().is_empty())
+                .collect(),
+            disabled: maze
+                .cells()
+                .filter(|coords| !maze.is_enabled(*coords))
+                .collect(),
+            rooms: maze
+                .rooms()
+                .iter()
+                .map(|room| RoomData {
+                    position: room.position,
+                    width: room.width,
+                    height: room.height,
+                    doors: room
+                        .doors
+                        .iter()
+                        .map(|&(coords, dir)| (coords, dir.bits()))
+                        .collect(),
+                })
+                .collect(),
+            topology: maze.topology(),
+        }
+    }
+}
+
+impl TryFrom<MazeData> for OrthogonalMaze {
+    type Error = String;
+
+    fn try_from(data: MazeData) -> Result<Self, Self::Error> {
+        let MazeData {
+            width,
+            height,
+            cells,
+            tunnels,
+            disabled,
+            rooms,
+            topology,
+        } = data;
+
+        if width == 0 || height == 0 {
+            return Err(format!("Invalid maze size {}x{}", width, height));
+        }
+
+        if width.checked_mul(height) != Some(cells.len()) {
+            return Err(format!(
+                "Expected {} cells for a {}x{} maze, found {}",
+                width.saturating_mul(height),
+                width,
+                height,
+                cells.len()
+            ));
+        }
+
+        // A tunnel runs across a straight corridor, from the cell to the West or North of it
+        let mut entrances = Vec::new();
+        for (x, y) in tunnels {
+            let corridor = (x < width && y < height)
+                .then(|| Cell::from_bits(cells[y * width + x]))
+                .flatten();
+            let entrance = match corridor {
+                Some(cell) if cell == Cell::NORTH | Cell::SOUTH && x > 0 => {
+                    ((x - 1, y), Cell::EAST)
+                }
+                Some(cell) if cell == Cell::EAST | Cell::WEST && y > 0 => ((x, y - 1), Cell::SOUTH),
+                _ => return Err(format!("Invalid tunnel at {:?}", (x, y))),
+            };
+            entrances.push(entrance);
+        }
+
+        let mut mask = Mask::new(width, height);
+        for (x, y) in disabled {
+            if x >= width || y >= height {
+                return Err(format!("Invalid disabled cell at {:?}", (x, y)));
+            }
+            mask.disable((x, y));
+        }
+
+        let mut maze = OrthogonalMaze::with_mask(&mask);
+        maze.get_grid_mut().set_topology(topology);
+
+        for (i, bits) in cells.iter().enumerate() {
+            let coords = (i % width, i / width);
+            let cell = Cell::from_bits(*bits)
+                .ok_or_else(|| format!("Invalid passages {:#06b} at {:?}", bits, coords))?;
+
+            // Carving passages to the East and South from every cell restores all of them exactly
+            // once, so that the passages leading out of the maze or having no counterpart in the
+            // adjacent cell can be detected below
+            for dir in [Cell::EAST, Cell::SOUTH] {
+                if cell.contains(dir) && !entrances.contains(&(coords, dir)) {
+                    maze.get_grid_mut()
+                        .carve_passage(coords, dir)
+                        .map_err(|err| format!("Invalid passage at {:?}: {}", coords, err))?;
+                }
+            }
+        }
+
+        // A tunnel runs only under a corridor with all its passages restored, but the corridor of
+        // one crossing may lead into or out of the tunnel under an adjacent one. The tunnels are
+        // rebuilt in passes, so each of them waits for the tunnels that complete its corridor
+        let mut pending = entrances;
+        while let Some(&(coords, dir)) = pending.first() {
+            let count = pending.len();
+            pending.retain(|&(coords, dir)| maze.get_grid_mut().carve_tunnel(coords, dir).is_err());
+            if pending.len() == count {
+                let err = maze.get_grid_mut().carve_tunnel(coords, dir).unwrap_err();
+                return Err(format!("Invalid tunnel at {:?}: {}", coords, err));
+            }
+        }
+
+        for (coords, bits) in maze.cells().zip(cells.iter()) {
+            if maze.cell(coords).unwrap().bits() != *bits {
+                return Err(format!("Mismatched passages at {:?}", coords));
+            }
+        }
+
+        let rooms = rooms
+            .into_iter()
+            .map(|room| room_from_data(&maze, room))
+            .collect::<Result<_, _>>()?;
+        maze.get_grid_mut().set_rooms(rooms);
+
+        Ok(maze)
+    }
+}
+
+/// Restores a room of a loaded maze, so that it lies within the maze and each door leads through a
+/// passage out of the room
+fn room_from_data(maze: &OrthogonalMaze, data: RoomData) -> Result<Room, String> {
+    let RoomData {
+        position: (x, y),
+        width,
+        height,
+        doors,
+    } = data;
+
+    let fits = |size: usize, start: usize, limit: usize| {
+        size > 0 && start.checked_add(size).is_some_and(|end| end <= limit)
+    };
+    if !fits(width, x, maze.width()) || !fits(height, y, maze.height()) {
+        return Err(format!("Invalid room at {:?}", (x, y)));
+    }
+
+    let mut room = Room {
+        position: (x, y),
+        width,
+        height,
+        doors: Vec::new(),
+    };
+    for (coords, bits) in doors {
+        let dir = Cell::from_bits(bits)
+            .filter(|dir| [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST].contains(dir))
+            .ok_or_else(|| format!("Invalid door {:#06b} at {:?}", bits, coords))?;
+        let exit = maze
+            .is_carved(coords, dir)
+            .then(|| maze.get_grid().get_next_cell_coords(coords, dir).ok())
+            .flatten();
+        if !room.contains(coords) || exit.is_none_or(|exit| room.contains(exit)) {
+            return Err(format!("Invalid door at {:?}", coords));
+        }
+        room.doors.push((coords, dir));
+    }
+
+    Ok(room)
+}
+
+impl Serialize for OrthogonalMaze {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        MazeData::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OrthogonalMaze {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        OrthogonalMaze::try_from(MazeData::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() {
+        let mut maze = OrthogonalMaze::new(2, 2);
+        maze.get_grid_mut()
+            .carve_passage((0, 0), Cell::EAST)
+            .unwrap();
+        maze.get_grid_mut()
+            .carve_passage((1, 0), Cell::SOUTH)
+            .unwrap();
+
+        let json = serde_json::to_string(&maze).unwrap();
+        assert_eq!(r#"{"width":2,"height":2,"cells":[4,10,0,1]}"#, json);
+    }
+
+    #[test]
+    fn deserialize() {
+        let json = r#"{"width":2,"height":2,"cells":[4,10,0,1]}"#;
+        let maze: OrthogonalMaze = serde_json::from_str(json).unwrap();
+
+        assert_eq!(2, maze.width());
+        assert_eq!(2, maze.height());
+        assert!(maze.is_carved((0, 0), Cell::EAST));
+        assert!(maze.is_carved((1, 1), Cell::NORTH));
+        assert!(!maze.is_carved((0, 1), Cell::NORTH));
+    }
+
+    #[test]
+    fn serialize_weave_maze() {
+        let mut maze = OrthogonalMaze::new(3, 3);
+        let grid = maze.get_grid_mut();
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_tunnel((0, 1), Cell::EAST).unwrap();
+
+        let json = serde_json::to_string(&maze).unwrap();
+        assert_eq!(
+            r#"{"width":3,"height":3,"cells":[0,2,0,4,3,8,0,1,0],"tunnels":[[1,1]]}"#,
+            json
+        );
+
+        let loaded: OrthogonalMaze = serde_json::from_str(&json).unwrap();
+        assert_eq!(Some(Cell::EAST | Cell::WEST), loaded.tunnel((1, 1)));
+        assert_eq!(vec![(2, 1)], loaded.neighbours((0, 1)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn serialize_wrapped_maze() {
+        let mut maze = OrthogonalMaze::new(3, 1);
+        let grid = maze.get_grid_mut();
+        grid.set_topology(Topology::Cylinder);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((2, 0), Cell::EAST).unwrap();
+
+        let json = serde_json::to_string(&maze).unwrap();
+        assert_eq!(
+            r#"{"width":3,"height":1,"cells":[12,8,4],"topology":"Cylinder"}"#,
+            json
+        );
+
+        let loaded: OrthogonalMaze = serde_json::from_str(&json).unwrap();
+        assert_eq!(Topology::Cylinder, loaded.topology());
+        assert_eq!(
+            vec![(2, 0), (1, 0)],
+            loaded.neighbours((0, 0)).collect::<Vec<_>>()
+        );
+
+        let json = r#"{"width":3,"height":1,"cells":[12,8,4]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error.to_string().starts_with("Invalid passage at (2, 0)"));
+    }
+
+    #[test]
+    fn serialize_masked_maze() {
+        let mut mask = Mask::new(3, 1);
+        mask.disable((2, 0));
+        let mut maze = OrthogonalMaze::with_mask(&mask);
+        maze.get_grid_mut()
+            .carve_passage((0, 0), Cell::EAST)
+            .unwrap();
+
+        let json = serde_json::to_string(&maze).unwrap();
+        assert_eq!(
+            r#"{"width":3,"height":1,"cells":[4,8,0],"disabled":[[2,0]]}"#,
+            json
+        );
+
+        let loaded: OrthogonalMaze = serde_json::from_str(&json).unwrap();
+        assert!(loaded.is_enabled((1, 0)));
+        assert!(!loaded.is_enabled((2, 0)));
+
+        let json = r#"{"width":3,"height":1,"cells":[4,12,8],"disabled":[[2,0]]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error.to_string().starts_with("Invalid passage at (1, 0)"));
+
+        let json = r#"{"width":3,"height":1,"cells":[4,8,0],"disabled":[[3,0]]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("Invalid disabled cell at (3, 0)"));
+    }
+
+    #[test]
+    fn serialize_maze_with_rooms() {
+        let mut maze = OrthogonalMaze::new(3, 1);
+        let grid = maze.get_grid_mut();
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+        grid.set_rooms(vec![Room {
+            position: (1, 0),
+            width: 2,
+            height: 1,
+            doors: vec![((1, 0), Cell::WEST)],
+        }]);
+
+        let json = serde_json::to_string(&maze).unwrap();
+        assert_eq!(
+            r#"{"width":3,"height":1,"cells":[4,12,8],"rooms":[{"position":[1,0],"width":2,"height":1,"doors":[[[1,0],8]]}]}"#,
+            json
+        );
+
+        let loaded: OrthogonalMaze = serde_json::from_str(&json).unwrap();
+        assert_eq!(maze.rooms(), loaded.rooms());
+
+        let json = r#"{"width":3,"height":1,"cells":[4,12,8],"rooms":[{"position":[2,0],"width":2,"height":1,"doors":[]}]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error.to_string().starts_with("Invalid room at (2, 0)"));
+
+        let json = r#"{"width":3,"height":1,"cells":[4,12,8],"rooms":[{"position":[1,0],"width":2,"height":1,"doors":[[[1,0],4]]}]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error.to_string().starts_with("Invalid door at (1, 0)"));
+
+        let json = r#"{"width":3,"height":1,"cells":[4,12,8],"rooms":[{"position":[1,0],"width":2,"height":1,"doors":[[[1,0],12]]}]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("Invalid door 0b1100 at (1, 0)"));
+    }
+
+    #[test]
+    fn deserialize_invalid_tunnel() {
+        let json = r#"{"width":3,"height":3,"cells":[0,2,0,4,3,8,0,1,0],"tunnels":[[1,2]]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error.to_string().starts_with("Invalid tunnel at (1, 2)"));
+
+        let json = r#"{"width":3,"height":3,"cells":[0,2,0,4,3,8,0,1,0],"tunnels":[[1,1],[1,1]]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error.to_string().starts_with("Invalid tunnel at (0, 1)"));
+    }
+
+    #[test]
+    fn deserialize_invalid_size() {
+        let json = r#"{"width":0,"height":2,"cells":[]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error.to_string().starts_with("Invalid maze size 0x2"));
+
+        let json = r#"{"width":2,"height":2,"cells":[0,0,0]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("Expected 4 cells for a 2x2 maze, found 3"));
+    }
+
+    #[test]
+    fn deserialize_invalid_passages() {
+        let json = r#"{"width":2,"height":2,"cells":[4,10,0,17]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("Invalid passages 0b10001 at (1, 1)"));
+    }
+
+    #[test]
+    fn deserialize_passage_out_of_maze() {
+        let json = r#"{"width":2,"height":2,"cells":[0,4,0,0]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error.to_string().starts_with("Invalid passage at (1, 0)"));
+    }
+
+    #[test]
+    fn deserialize_mismatched_passages() {
+        let json = r#"{"width":2,"height":2,"cells":[4,0,0,0]}"#;
+        let error = serde_json::from_str::<OrthogonalMaze>(json).err().unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("Mismatched passages at (1, 0)"));
+    }
+}
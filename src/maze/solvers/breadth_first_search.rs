@@ -0,0 +1,46 @@
+use super::{shortest_path, Solver};
+use crate::maze::grid::Grid;
+use crate::utils::types::Coords;
+
+/// The "Breadth-First Search" strategy for solving mazes
+///
+/// Floods the maze outward from the start one step at a time, thus always finding the shortest
+/// path. It works equally well on perfect mazes and on mazes with loops, but has to keep the whole
+/// frontier of the flood in memory.
+pub struct BreadthFirstSearch;
+
+/// An implementation of the "Breadth-First Search" strategy for solving mazes
+///
+/// Here is how it works:
+///
+/// 1. Puts the start cell into a queue.
+///
+/// 2. Takes the next cell from the queue and puts all its reachable neighbours that have not been
+///    discovered yet into the queue, remembering the cell they were discovered from.
+///
+/// 3. Repeats step 2 until the goal is taken from the queue or the queue is empty.
+///
+/// 4. Walks the remembered cells back from the goal to the start.
+impl Solver for BreadthFirstSearch {
+    fn solve(&self, grid: &Grid, start: Coords, goal: Coords) -> Option<Vec<Coords>> {
+        shortest_path(grid, start, goal, |_| true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::solvers::tests::{generate_maze, generate_split_maze};
+
+    #[test]
+    fn solve() {
+        let (grid, path) = generate_maze();
+        assert_eq!(Some(path), BreadthFirstSearch.solve(&grid, (0, 0), (3, 3)));
+    }
+
+    #[test]
+    fn solve_unreachable_goal() {
+        let grid = generate_split_maze();
+        assert_eq!(None, BreadthFirstSearch.solve(&grid, (0, 0), (2, 2)));
+    }
+}
@@ -0,0 +1,110 @@
+use super::{index, neighbours, shortest_path, Solver};
+use crate::maze::grid::Grid;
+use crate::utils::types::Coords;
+
+/// The "Dead-End Filling" strategy for solving mazes
+///
+/// A simple strategy that looks at the entire maze at once rather than walking it. It fills every
+/// dead end, then every cell that became a dead end because of the filling, and so on, until only
+/// the cells that lead somewhere remain. In a perfect maze what remains is exactly the solution.
+/// In a maze with loops, the remaining cells may still branch, so the shortest way through them is
+/// picked.
+pub struct DeadEndFilling;
+
+/// An implementation of the "Dead-End Filling" strategy for solving mazes
+///
+/// Here is how it works:
+///
+/// 1. Finds all the dead ends in the maze, i.e. the cells with a single passage, except the start
+///    and the goal.
+///
+/// 2. Fills each dead end, walking from it and filling every cell on the way until reaching a
+///    junction that is still left with more than one open passage.
+///
+/// 3. Walks from the start to the goal through the cells that have not been filled.
+impl Solver for DeadEndFilling {
+    fn solve(&self, grid: &Grid, start: Coords, goal: Coords) -> Option<Vec<Coords>> {
+        let mut filled = vec![false; grid.width() * grid.height()];
+        let mut exits = vec![0; grid.width() * grid.height()];
+        let mut dead_ends = vec![];
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                exits[index(grid, (x, y))] = neighbours(grid, (x, y)).count();
+                if exits[index(grid, (x, y))] <= 1 {
+                    dead_ends.push((x, y));
+                }
+            }
+        }
+
+        while let Some(coords) = dead_ends.pop() {
+            if coords == start || coords == goal || filled[index(grid, coords)] {
+                continue;
+            }
+
+            filled[index(grid, coords)] = true;
+            for next in neighbours(grid, coords) {
+                if filled[index(grid, next)] {
+                    continue;
+                }
+
+                exits[index(grid, next)] -= 1;
+                if exits[index(grid, next)] == 1 {
+                    dead_ends.push(next);
+                }
+            }
+        }
+
+        shortest_path(grid, start, goal, |coords| !filled[index(grid, coords)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::grid::cell::Cell;
+    use crate::maze::solvers::tests::{generate_maze, generate_split_maze};
+
+    #[test]
+    fn solve() {
+        let (grid, path) = generate_maze();
+        assert_eq!(Some(path), DeadEndFilling.solve(&grid, (0, 0), (3, 3)));
+    }
+
+    #[test]
+    fn solve_from_dead_end_to_dead_end() {
+        let (grid, _) = generate_maze();
+        let path = DeadEndFilling.solve(&grid, (1, 0), (3, 3)).unwrap();
+        assert_eq!(
+            vec![(1, 0), (2, 0), (3, 0), (3, 1), (3, 2), (2, 2)],
+            &path[..6]
+        );
+    }
+
+    #[test]
+    fn solve_same_start_and_goal() {
+        let (grid, _) = generate_maze();
+        assert_eq!(
+            Some(vec![(2, 1)]),
+            DeadEndFilling.solve(&grid, (2, 1), (2, 1))
+        );
+    }
+
+    #[test]
+    fn solve_with_loops() {
+        let mut grid = Grid::new(2, 2);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::NORTH).unwrap();
+        grid.carve_passage((1, 1), Cell::WEST).unwrap();
+
+        let path = DeadEndFilling.solve(&grid, (0, 0), (1, 1)).unwrap();
+        assert_eq!(3, path.len());
+    }
+
+    #[test]
+    fn solve_unreachable_goal() {
+        let grid = generate_split_maze();
+        assert_eq!(None, DeadEndFilling.solve(&grid, (0, 0), (2, 2)));
+    }
+}
@@ -0,0 +1,160 @@
+//! Solvers for finding a path between two cells of a generated maze
+
+mod a_star;
+mod breadth_first_search;
+mod dead_end_filling;
+mod tremaux;
+mod wall_follower;
+
+pub use a_star::AStar;
+pub use breadth_first_search::BreadthFirstSearch;
+pub use dead_end_filling::DeadEndFilling;
+pub use tremaux::Tremaux;
+pub use wall_follower::{Hand, WallFollower};
+
+use crate::maze::grid::Grid;
+use crate::utils::types::Coords;
+
+/// A trait for solving a maze using a selected strategy
+pub trait Solver {
+    /// Returns the ordered list of cells leading from the start to the goal through the carved
+    /// passages of the given grid, both ends included
+    ///
+    /// Returns `None` if the goal cannot be reached from the start.
+    fn solve(&self, grid: &Grid, start: Coords, goal: Coords) -> Option<Vec<Coords>>;
+}
+
+/// Returns an iterator over the cells sharing a carved passage with a cell at the given
+/// coordinates
+fn neighbours(grid: &Grid, coords: Coords) -> impl Iterator<Item = Coords> + '_ {
+    grid.get_linked_cells(coords)
+}
+
+/// Returns an index of a cell at the given coordinates in a flat list of grid cells
+const fn index(grid: &Grid, (x, y): Coords) -> usize {
+    y * grid.width() + x
+}
+
+/// Walks the parents back from the goal and returns the path from the start to the goal
+fn backtrack(grid: &Grid, parents: &[Option<Coords>], goal: Coords) -> Vec<Coords> {
+    let mut path = vec![goal];
+    let mut coords = goal;
+
+    while let Some(parent) = parents[index(grid, coords)] {
+        path.push(parent);
+        coords = parent;
+    }
+
+    path.reverse();
+    path
+}
+
+/// Returns the shortest path from the start to the goal walking only the cells the predicate
+/// allows to step on
+fn shortest_path<P>(grid: &Grid, start: Coords, goal: Coords, passable: P) -> Option<Vec<Coords>>
+where
+    P: Fn(Coords) -> bool,
+{
+    let mut parents: Vec<Option<Coords>> = vec![None; grid.width() * grid.height()];
+    let mut discovered = vec![false; grid.width() * grid.height()];
+    let mut queue = std::collections::VecDeque::from([start]);
+    discovered[index(grid, start)] = true;
+
+    while let Some(coords) = queue.pop_front() {
+        if coords == goal {
+            return Some(backtrack(grid, &parents, goal));
+        }
+
+        for next in neighbours(grid, coords) {
+            if discovered[index(grid, next)] || !passable(next) {
+                continue;
+            }
+
+            discovered[index(grid, next)] = true;
+            parents[index(grid, next)] = Some(coords);
+            queue.push_back(next);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::maze::grid::cell::Cell;
+
+    /// Returns the maze below and the only path from its top left to its bottom right corner
+    ///
+    /// ```no_test
+    ///  _______
+    /// | |___  |
+    /// |_   _| |
+    /// |  _____|
+    /// |_______|
+    /// ```
+    pub fn generate_maze() -> (Grid, Vec<Coords>) {
+        let mut grid = Grid::new(4, 4);
+
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 1), Cell::EAST).unwrap();
+        grid.carve_passage((0, 2), Cell::EAST).unwrap();
+        grid.carve_passage((0, 2), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 3), Cell::EAST).unwrap();
+
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 2), Cell::EAST).unwrap();
+        grid.carve_passage((1, 3), Cell::EAST).unwrap();
+
+        grid.carve_passage((2, 0), Cell::EAST).unwrap();
+        grid.carve_passage((2, 2), Cell::EAST).unwrap();
+        grid.carve_passage((2, 3), Cell::EAST).unwrap();
+
+        grid.carve_passage((3, 1), Cell::NORTH).unwrap();
+        grid.carve_passage((3, 1), Cell::SOUTH).unwrap();
+
+        let path = vec![
+            (0, 0),
+            (0, 1),
+            (1, 1),
+            (1, 2),
+            (0, 2),
+            (0, 3),
+            (1, 3),
+            (2, 3),
+            (3, 3),
+        ];
+
+        (grid, path)
+    }
+
+    /// Returns a grid with two disjoint regions: the leftmost column and the rest of the cells
+    pub fn generate_split_maze() -> Grid {
+        let mut grid = Grid::new(3, 3);
+
+        for y in 0..2 {
+            grid.carve_passage((0, y), Cell::SOUTH).unwrap();
+            grid.carve_passage((1, y), Cell::SOUTH).unwrap();
+        }
+        for y in 0..3 {
+            grid.carve_passage((1, y), Cell::EAST).unwrap();
+        }
+
+        grid
+    }
+
+    #[test]
+    fn shortest_path_with_obstacle() {
+        let (grid, _) = generate_maze();
+        assert_eq!(None, shortest_path(&grid, (0, 0), (3, 3), |c| c != (0, 3)));
+    }
+
+    #[test]
+    fn backtrack_from_start() {
+        let (grid, _) = generate_maze();
+        let parents = vec![None; 16];
+        assert_eq!(vec![(0, 0)], backtrack(&grid, &parents, (0, 0)));
+    }
+}
@@ -0,0 +1,170 @@
+use super::{neighbours, Solver};
+use crate::maze::grid::Grid;
+use crate::utils::types::Coords;
+
+use std::collections::HashMap;
+
+type Passage = (Coords, Coords);
+
+/// The Trémaux's strategy for solving mazes
+///
+/// An efficient method that works in any maze, with or without loops, and requires nothing but
+/// drawing marks on the floor while walking. Each passage gets marked every time it's walked, and
+/// no passage is ever walked more than twice. Once the goal is reached, the passages marked exactly
+/// once form a direct way back to the start.
+pub struct Tremaux;
+
+impl Tremaux {
+    /// Chooses where to go from a cell given the cell the walker came from
+    fn choose_next(
+        marks: &HashMap<Passage, u8>,
+        grid: &Grid,
+        coords: Coords,
+        previous: Option<Coords>,
+    ) -> Option<Coords> {
+        let marks_of = |next: Coords| marks.get(&passage(coords, next)).copied().unwrap_or(0);
+        let others = || neighbours(grid, coords).filter(move |next| Some(*next) != previous);
+
+        // An already visited junction: turn back unless the way back has been walked twice
+        if let Some(previous) = previous {
+            if others().any(|next| marks_of(next) > 0) && marks_of(previous) == 1 {
+                return Some(previous);
+            }
+        }
+
+        others()
+            .filter(|next| marks_of(*next) < 2)
+            .min_by_key(|next| marks_of(*next))
+            .or(previous.filter(|previous| marks_of(*previous) < 2))
+    }
+}
+
+/// An implementation of the Trémaux's strategy for solving mazes
+///
+/// Here is how it works:
+///
+/// 1. Walks from the start, marking each passage once it's walked.
+///
+/// 2. On arriving at a junction with no marks, picks any unmarked passage.
+///
+/// 3. On arriving at a marked junction through a passage marked only once, turns back.
+///
+/// 4. Otherwise, picks the passage with the fewest marks, never walking a passage marked twice.
+///
+/// 5. Repeats until the goal is reached, then follows the passages marked exactly once from the
+///    start to the goal.
+impl Solver for Tremaux {
+    fn solve(&self, grid: &Grid, start: Coords, goal: Coords) -> Option<Vec<Coords>> {
+        let mut marks: HashMap<Passage, u8> = HashMap::new();
+        let mut coords = start;
+        let mut previous = None;
+
+        while coords != goal {
+            let next = Tremaux::choose_next(&marks, grid, coords, previous)?;
+            *marks.entry(passage(coords, next)).or_default() += 1;
+
+            previous = Some(coords);
+            coords = next;
+        }
+
+        let mut path = vec![start];
+        let mut coords = start;
+        let mut previous = None;
+
+        while coords != goal {
+            // Every passage walked away from the goal is walked back, so the passages marked once
+            // always form a route from the start to the goal
+            let next = neighbours(grid, coords)
+                .find(|next| {
+                    Some(*next) != previous && marks.get(&passage(coords, *next)) == Some(&1)
+                })
+                .expect("The passages marked once form a route to the goal");
+
+            path.push(next);
+            previous = Some(coords);
+            coords = next;
+        }
+
+        Some(path)
+    }
+}
+
+/// Returns a passage between two cells regardless of the direction it's walked in
+fn passage(a: Coords, b: Coords) -> Passage {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::grid::cell::Cell;
+    use crate::maze::solvers::tests::{generate_maze, generate_split_maze};
+
+    #[test]
+    fn solve() {
+        let (grid, path) = generate_maze();
+        assert_eq!(Some(path), Tremaux.solve(&grid, (0, 0), (3, 3)));
+    }
+
+    #[test]
+    fn solve_backwards() {
+        let (grid, mut path) = generate_maze();
+        path.reverse();
+        assert_eq!(Some(path), Tremaux.solve(&grid, (3, 3), (0, 0)));
+    }
+
+    #[test]
+    fn solve_with_loops() {
+        let mut grid = Grid::new(3, 3);
+        for y in 0..3 {
+            for x in 0..3 {
+                grid.carve_passage((x, y), Cell::EAST).ok();
+                grid.carve_passage((x, y), Cell::SOUTH).ok();
+            }
+        }
+
+        let path = Tremaux.solve(&grid, (0, 0), (2, 2)).unwrap();
+        assert_eq!(Some(&(0, 0)), path.first());
+        assert_eq!(Some(&(2, 2)), path.last());
+    }
+
+    #[test]
+    fn solve_every_pair_in_open_room() {
+        let mut grid = Grid::new(4, 3);
+        for y in 0..3 {
+            for x in 0..4 {
+                grid.carve_passage((x, y), Cell::EAST).ok();
+                grid.carve_passage((x, y), Cell::SOUTH).ok();
+            }
+        }
+
+        let cells: Vec<Coords> = (0..3).flat_map(|y| (0..4).map(move |x| (x, y))).collect();
+        for &start in &cells {
+            for &goal in &cells {
+                let path = Tremaux.solve(&grid, start, goal).unwrap();
+                assert_eq!((Some(&start), Some(&goal)), (path.first(), path.last()));
+                for (i, coords) in path.iter().enumerate() {
+                    assert!(!path[i + 1..].contains(coords));
+                }
+                for pair in path.windows(2) {
+                    assert!(neighbours(&grid, pair[0]).any(|next| next == pair[1]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solve_unreachable_goal() {
+        let grid = generate_split_maze();
+        assert_eq!(None, Tremaux.solve(&grid, (0, 0), (2, 2)));
+    }
+
+    #[test]
+    fn passage_is_undirected() {
+        assert_eq!(passage((1, 0), (0, 0)), passage((0, 0), (1, 0)));
+    }
+}
@@ -0,0 +1,182 @@
+use super::{index, Solver};
+use crate::maze::grid::{cell::Cell, Grid};
+use crate::utils::types::Coords;
+
+use std::collections::HashSet;
+
+/// An enumeration over the hands a wall follower keeps on the wall
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Hand {
+    /// Keeps the left hand on the wall, thus turning left whenever possible
+    Left,
+
+    /// Keeps the right hand on the wall, thus turning right whenever possible
+    Right,
+}
+
+/// The "Wall Follower" strategy for solving mazes
+///
+/// The best-known rule for traversing mazes, also known as either the left-hand rule or the
+/// right-hand rule. Keeping one hand in contact with a wall guarantees reaching the goal in a
+/// perfect maze, i.e. a maze without loops. In a maze with loops, the follower may walk around an
+/// island forever without ever seeing the goal. This implementation detects such a case and gives
+/// up.
+///
+/// The path returned is the walked route with all the dead ends and loops it ran into cut off.
+pub struct WallFollower {
+    hand: Hand,
+}
+
+impl WallFollower {
+    /// Create a new instance of the strategy with a given hand
+    ///
+    /// # Example
+    /// ```
+    /// use knossos::maze::{Hand, WallFollower};
+    ///
+    /// let solver = WallFollower::new(Hand::Left);
+    /// ```
+    pub const fn new(hand: Hand) -> WallFollower {
+        WallFollower { hand }
+    }
+
+    /// Returns the directions to try from a cell in the order of the rule, given the heading
+    const fn directions(&self, heading: Cell) -> [Cell; 4] {
+        let (turn, other) = match self.hand {
+            Hand::Left => (turn_left(heading), turn_right(heading)),
+            Hand::Right => (turn_right(heading), turn_left(heading)),
+        };
+        [turn, heading, other, turn_back(heading)]
+    }
+}
+
+/// An implementation of the "Wall Follower" strategy for solving mazes
+///
+/// Here is how it works:
+///
+/// 1. Starts at the start cell facing North.
+///
+/// 2. Tries to turn towards the hand, go straight, turn away from the hand and turn back, in this
+///    order, and moves to the first cell that is open.
+///
+/// 3. Repeats step 2 until the goal is reached or the follower finds itself in the same cell
+///    facing the same way it did before.
+///
+/// 4. Erases every loop from the walked route: whenever the route comes back to a cell it has
+///    already been to, everything walked in between is dropped.
+impl Solver for WallFollower {
+    fn solve(&self, grid: &Grid, start: Coords, goal: Coords) -> Option<Vec<Coords>> {
+        let mut path = vec![start];
+        let mut positions = vec![None; grid.width() * grid.height()];
+        let mut walked = HashSet::new();
+        let mut coords = start;
+        let mut heading = Cell::NORTH;
+        positions[index(grid, start)] = Some(0);
+
+        while coords != goal {
+            if !walked.insert((coords, heading)) {
+                return None;
+            }
+
+            let (dir, next) = self
+                .directions(heading)
+                .into_iter()
+                .find_map(|dir| grid.get_linked_cell_coords(coords, dir).map(|n| (dir, n)))?;
+
+            match positions[index(grid, next)] {
+                Some(position) => {
+                    for erased in path.drain(position + 1..) {
+                        positions[index(grid, erased)] = None;
+                    }
+                }
+                None => {
+                    positions[index(grid, next)] = Some(path.len());
+                    path.push(next);
+                }
+            }
+
+            coords = next;
+            heading = dir;
+        }
+
+        Some(path)
+    }
+}
+
+const fn turn_left(heading: Cell) -> Cell {
+    match heading {
+        Cell::NORTH => Cell::WEST,
+        Cell::WEST => Cell::SOUTH,
+        Cell::SOUTH => Cell::EAST,
+        _ => Cell::NORTH,
+    }
+}
+
+const fn turn_right(heading: Cell) -> Cell {
+    match heading {
+        Cell::NORTH => Cell::EAST,
+        Cell::EAST => Cell::SOUTH,
+        Cell::SOUTH => Cell::WEST,
+        _ => Cell::NORTH,
+    }
+}
+
+const fn turn_back(heading: Cell) -> Cell {
+    turn_left(turn_left(heading))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::solvers::tests::{generate_maze, generate_split_maze};
+
+    #[test]
+    fn solve_with_left_hand() {
+        let (grid, path) = generate_maze();
+        let solver = WallFollower::new(Hand::Left);
+        assert_eq!(Some(path), solver.solve(&grid, (0, 0), (3, 3)));
+    }
+
+    #[test]
+    fn solve_with_right_hand() {
+        let (grid, path) = generate_maze();
+        let solver = WallFollower::new(Hand::Right);
+        assert_eq!(Some(path), solver.solve(&grid, (0, 0), (3, 3)));
+    }
+
+    #[test]
+    fn solve_unreachable_goal() {
+        let grid = generate_split_maze();
+        let solver = WallFollower::new(Hand::Left);
+        assert_eq!(None, solver.solve(&grid, (0, 0), (2, 2)));
+    }
+
+    #[test]
+    fn give_up_around_an_island() {
+        let mut grid = Grid::new(3, 3);
+        for (coords, dir) in [
+            ((0, 0), Cell::EAST),
+            ((1, 0), Cell::EAST),
+            ((2, 0), Cell::SOUTH),
+            ((2, 1), Cell::SOUTH),
+            ((2, 2), Cell::WEST),
+            ((1, 2), Cell::WEST),
+            ((0, 2), Cell::NORTH),
+            ((0, 1), Cell::NORTH),
+            ((1, 1), Cell::EAST),
+        ] {
+            grid.carve_passage(coords, dir).unwrap();
+        }
+
+        let solver = WallFollower::new(Hand::Left);
+        assert_eq!(None, solver.solve(&grid, (0, 0), (1, 1)));
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Cell::WEST, turn_left(Cell::NORTH));
+        assert_eq!(Cell::EAST, turn_right(Cell::NORTH));
+        assert_eq!(Cell::SOUTH, turn_back(Cell::NORTH));
+        assert_eq!(Cell::WEST, turn_back(Cell::EAST));
+    }
+}
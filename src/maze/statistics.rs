@@ -0,0 +1,293 @@
+use super::grid::{cell::Cell, Grid};
+use crate::utils::types::Coords;
+
+use std::collections::VecDeque;
+
+/// Statistics and difficulty metrics of a maze
+///
+/// They describe the texture a generation algorithm leaves in a maze and make it possible to
+/// compare algorithms, their biases and methods objectively rather than by feel.
+///
+/// # Example
+/// ```rust
+/// use knossos::maze::*;
+///
+/// let maze = OrthogonalMazeBuilder::new()
+///     .algorithm(Box::new(BinaryTree::new(Bias::NorthEast)))
+///     .build();
+/// let stats = maze.statistics();
+///
+/// assert_eq!(100, stats.dead_ends + stats.corridors + stats.junctions);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MazeStatistics {
+    /// The number of cells with a single passage
+    pub dead_ends: usize,
+
+    /// The number of cells with exactly two passages, i.e. the cells a walker can only pass
+    /// through
+    pub corridors: usize,
+
+    /// The number of cells with three or four passages, i.e. the cells a walker has to make a
+    /// decision at
+    pub junctions: usize,
+
+    /// The length of the longest path in a maze (its diameter) as a number of steps between
+    /// cells
+    ///
+    /// In a perfect maze it's exact. In a maze with loops it's the length of the longest of the
+    /// shortest paths found by sweeping the maze twice, which is a close lower bound.
+    pub longest_path: usize,
+
+    /// The cells at both ends of the [longest path](MazeStatistics::longest_path)
+    pub longest_path_endpoints: (Coords, Coords),
+
+    /// The average number of steps between two decision points, i.e. dead ends or junctions,
+    /// along corridors
+    pub average_corridor_length: f64,
+
+    /// The average number of steps from a dead end back to the nearest junction
+    ///
+    /// It measures how much a maze "flows" like a river: a low value means lots of short
+    /// cul-de-sacs that are easy to spot, while a high value means long winding branches that
+    /// take a while to find out they lead nowhere.
+    pub river_factor: f64,
+
+    /// The number of passages between two horizontally adjacent cells
+    pub horizontal_passages: usize,
+
+    /// The number of passages between two vertically adjacent cells
+    pub vertical_passages: usize,
+}
+
+impl MazeStatistics {
+    /// Returns the directional bias of a maze passages in the `-1.0..=1.0` range
+    ///
+    /// Positive values mean there are more horizontal passages than vertical ones, negative values
+    /// mean the opposite, and zero means a perfect balance.
+    pub fn directional_bias(&self) -> f64 {
+        let total = self.horizontal_passages + self.vertical_passages;
+        if total == 0 {
+            return 0.0;
+        }
+
+        (self.horizontal_passages as f64 - self.vertical_passages as f64) / total as f64
+    }
+}
+
+/// Computes statistics of a given grid
+pub fn statistics(grid: &Grid) -> MazeStatistics {
+    let mut dead_ends = 0;
+    let mut corridors = 0;
+    let mut junctions = 0;
+    let mut horizontal_passages = 0;
+    let mut vertical_passages = 0;
+
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            match exits(grid, (x, y)) {
+                0 => {}
+                1 => dead_ends += 1,
+                2 => corridors += 1,
+                _ => junctions += 1,
+            }
+
+            if grid.is_carved((x, y), Cell::EAST) {
+                horizontal_passages += 1;
+            }
+            if grid.is_carved((x, y), Cell::SOUTH) {
+                vertical_passages += 1;
+            }
+        }
+    }
+
+    let (longest_path, longest_path_endpoints) = longest_path(grid);
+
+    MazeStatistics {
+        dead_ends,
+        corridors,
+        junctions,
+        longest_path,
+        longest_path_endpoints,
+        average_corridor_length: average_corridor_length(grid),
+        river_factor: river_factor(grid),
+        horizontal_passages,
+        vertical_passages,
+    }
+}
+
+/// Returns the number of steps from a given cell to every other cell in a flat list of grid
+/// cells, or `None` for the cells that cannot be reached
+pub fn distances(grid: &Grid, from: Coords) -> Vec<Option<usize>> {
+    let mut distances = vec![None; grid.width() * grid.height()];
+    let mut queue = VecDeque::from([from]);
+    distances[index(grid, from)] = Some(0);
+
+    while let Some(coords) = queue.pop_front() {
+        let distance = distances[index(grid, coords)].unwrap_or(0) + 1;
+
+        for next in grid.get_linked_cells(coords) {
+            if distances[index(grid, next)].is_none() {
+                distances[index(grid, next)] = Some(distance);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+fn longest_path(grid: &Grid) -> (usize, (Coords, Coords)) {
+    let farthest = |from: Coords| {
+        distances(grid, from)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, distance)| distance.map(|d| (d, (i % grid.width(), i / grid.width()))))
+            .max_by_key(|(distance, _)| *distance)
+            .unwrap_or((0, from))
+    };
+
+    let (_, start) = farthest(grid.enabled_cells().next().unwrap_or((0, 0)));
+    let (length, end) = farthest(start);
+    (length, (start, end))
+}
+
+fn average_corridor_length(grid: &Grid) -> f64 {
+    let mut segments = 0;
+    let mut steps = 0;
+
+    for coords in cells(grid).filter(|coords| exits(grid, *coords) != 2) {
+        for next in grid.get_linked_cells(coords) {
+            segments += 1;
+            steps += walk_corridor(grid, coords, next);
+        }
+    }
+
+    if segments == 0 {
+        return 0.0;
+    }
+
+    steps as f64 / segments as f64
+}
+
+fn river_factor(grid: &Grid) -> f64 {
+    let mut branches = 0;
+    let mut steps = 0;
+
+    for coords in cells(grid).filter(|coords| exits(grid, *coords) == 1) {
+        let next = grid.get_linked_cells(coords).next().unwrap();
+        branches += 1;
+        steps += walk_corridor(grid, coords, next);
+    }
+
+    if branches == 0 {
+        return 0.0;
+    }
+
+    steps as f64 / branches as f64
+}
+
+/// Walks from a cell through the next one and on along the corridor until reaching a dead end, a
+/// junction or the cell the walk started at, and returns the number of steps made
+fn walk_corridor(grid: &Grid, from: Coords, next: Coords) -> usize {
+    let mut previous = from;
+    let mut coords = next;
+    let mut steps = 1;
+
+    while exits(grid, coords) == 2 && coords != from {
+        let next = grid
+            .get_linked_cells(coords)
+            .find(|next| *next != previous)
+            .unwrap();
+        previous = coords;
+        coords = next;
+        steps += 1;
+    }
+
+    steps
+}
+
+fn exits(grid: &Grid, coords: Coords) -> usize {
+    grid.get_linked_cells(coords).count()
+}
+
+fn cells(grid: &Grid) -> impl Iterator<Item = Coords> + '_ {
+    (0..grid.height()).flat_map(move |y| (0..grid.width()).map(move |x| (x, y)))
+}
+
+const fn index(grid: &Grid, (x, y): Coords) -> usize {
+    y * grid.width() + x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::solvers::tests::generate_maze;
+
+    #[test]
+    fn cell_counts() {
+        let (grid, _) = generate_maze();
+        let stats = statistics(&grid);
+
+        assert_eq!(4, stats.dead_ends);
+        assert_eq!(10, stats.corridors);
+        assert_eq!(2, stats.junctions);
+    }
+
+    #[test]
+    fn passage_counts() {
+        let (grid, _) = generate_maze();
+        let stats = statistics(&grid);
+
+        assert_eq!(10, stats.horizontal_passages);
+        assert_eq!(5, stats.vertical_passages);
+        assert!((stats.directional_bias() - 1.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn longest_path_of_maze() {
+        let (grid, _) = generate_maze();
+        let stats = statistics(&grid);
+
+        assert_eq!(11, stats.longest_path);
+        assert_eq!(((1, 0), (3, 3)), stats.longest_path_endpoints);
+    }
+
+    #[test]
+    fn corridors_and_rivers() {
+        let (grid, _) = generate_maze();
+        let stats = statistics(&grid);
+
+        assert!((stats.average_corridor_length - 15.0 / 5.0).abs() < f64::EPSILON);
+        assert!((stats.river_factor - 14.0 / 4.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn single_cell() {
+        let grid = Grid::new(1, 1);
+        let stats = statistics(&grid);
+
+        assert_eq!(0, stats.dead_ends + stats.corridors + stats.junctions);
+        assert_eq!(0, stats.longest_path);
+        assert_eq!(((0, 0), (0, 0)), stats.longest_path_endpoints);
+        assert_eq!(0.0, stats.average_corridor_length);
+        assert_eq!(0.0, stats.river_factor);
+        assert_eq!(0.0, stats.directional_bias());
+    }
+
+    #[test]
+    fn distances_from_cell() {
+        let (grid, path) = generate_maze();
+        let distances = distances(&grid, (0, 0));
+
+        for (step, coords) in path.iter().enumerate() {
+            assert_eq!(Some(step), distances[index(&grid, *coords)]);
+        }
+    }
+
+    #[test]
+    fn distances_to_unreachable_cells() {
+        let grid = Grid::new(2, 1);
+        assert_eq!(vec![Some(0), None], distances(&grid, (0, 0)));
+    }
+}
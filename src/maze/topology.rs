@@ -0,0 +1,56 @@
+/// A topology of an orthogonal maze, i.e. which borders of a grid wrap around to the opposite ones
+///
+/// In a wrapped grid, the cells along a border are adjacent to the cells along the opposite one,
+/// so passages can lead out of a maze on one side and back into it on the other, like on a screen
+/// wrapping playfield. A grid needs at least three cells across for its borders to wrap, since
+/// otherwise two cells would be adjacent on both sides.
+///
+/// The [AldousBroder](crate::maze::AldousBroder), [GrowingTree](crate::maze::GrowingTree),
+/// [Houston](crate::maze::Houston), [HuntAndKill](crate::maze::HuntAndKill),
+/// [Kruskal](crate::maze::Kruskal), [OriginShift](crate::maze::OriginShift),
+/// [Prim](crate::maze::Prim), [RecursiveBacktracking](crate::maze::RecursiveBacktracking) and
+/// [Wilson](crate::maze::Wilson) algorithms carve the passages across the wrapped borders as they
+/// do across any other wall. The [Sidewinder](crate::maze::Sidewinder) algorithm only carves them
+/// across the Western and Eastern borders. The [BinaryTree](crate::maze::BinaryTree),
+/// [ChamberDivision](crate::maze::ChamberDivision), [Eller](crate::maze::Eller) and
+/// [RecursiveDivision](crate::maze::RecursiveDivision) algorithms rely on the borders to keep a
+/// maze perfect, so they carve the passages within them.
+///
+/// # Example
+/// ```rust
+/// use knossos::maze::*;
+///
+/// let maze = OrthogonalMazeBuilder::new()
+///     .topology(Topology::Torus)
+///     .algorithm(Box::new(Kruskal))
+///     .build();
+///
+/// assert!(maze.is_perfect());
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    /// A flat grid surrounded by the walls on all the sides
+    #[default]
+    Plane,
+
+    /// A grid whose Western and Eastern borders wrap around to each other, like the side of a
+    /// cylinder
+    Cylinder,
+
+    /// A grid whose Western and Eastern as well as Northern and Southern borders wrap around to
+    /// each other, like the surface of a torus
+    Torus,
+}
+
+impl Topology {
+    /// Returns `true` if the Western and Eastern borders wrap around. Otherwise, returns `false`
+    pub const fn wraps_horizontally(self) -> bool {
+        matches!(self, Topology::Cylinder | Topology::Torus)
+    }
+
+    /// Returns `true` if the Northern and Southern borders wrap around. Otherwise, returns `false`
+    pub const fn wraps_vertically(self) -> bool {
+        matches!(self, Topology::Torus)
+    }
+}
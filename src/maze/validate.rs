@@ -2,16 +2,20 @@ use crate::utils::types::Coords;
 use rand::prelude::*;
 use super::grid::{Grid, cell::Cell};
 
-/// A utility to validate if a given grid is valid, i.e. all the cells are reachable.
+/// A utility to validate if a given grid is valid, i.e. all the enabled cells are reachable.
 ///
 /// The recursive backtracker is one of the simplest and most efficient algorithms
-/// for this kind of work. If an algorithm does not visit all the cells, we make a
-/// conclusion that it's not valid.
+/// for this kind of work. If an algorithm does not visit all the enabled cells, we make a
+/// conclusion that it's not valid. A grid with all the cells disabled is not valid either.
 pub fn validate(grid: &Grid) -> bool {
+    let Some(start) = grid.enabled_cells().next() else {
+        return false;
+    };
+
     let mut visited: Vec<Coords> = Vec::new();
-    visited.push((0, 0));
-    visit((0, 0), grid, &mut visited);
-    visited.len() == grid.width() * grid.height()
+    visited.push(start);
+    visit(start, grid, &mut visited);
+    visited.len() == grid.enabled_cells().count()
 }
 
 fn visit(coords: Coords, grid: &Grid, visited: &mut Vec<Coords>) {
@@ -19,19 +23,15 @@ fn visit(coords: Coords, grid: &Grid, visited: &mut Vec<Coords>) {
     dirs.shuffle(&mut rand::rng());
 
     for dir in dirs {
-        let next = match grid.get_next_cell_coords(coords, dir) {
-            Ok(next) => next,
-            Err(_) => continue,
+        let next = match grid.get_linked_cell_coords(coords, dir) {
+            Some(next) => next,
+            None => continue,
         };
 
         if visited.contains(&next) {
             continue;
         }
 
-        if !grid.is_carved(coords, dir) {
-            continue;
-        }
-
         visited.push(next);
         visit(next, grid, visited);
     }
@@ -1 +1,10 @@
+/// A pair of `x` and `y` coordinates of a cell in a maze, starting from the top left corner
 pub type Coords = (usize, usize);
+
+/// A pair of a ring index, starting from the center, and an index of a cell in the ring, starting
+/// from the top and going clockwise, of a cell in a polar maze
+pub type PolarCoords = (usize, usize);
+
+/// A triple of `x` and `y` coordinates of a cell within a level, starting from the top left
+/// corner, and a level index, starting from the ground one, of a cell in a layered maze
+pub type LayeredCoords = (usize, usize, usize);
@@ -0,0 +1,37 @@
+#![cfg(feature = "cli")]
+
+use assert_cmd::Command;
+use assert_fs::fixture::TempDir;
+
+const MAZE_ARGS: [&str; 8] = ["-A", "prim", "-W", "6", "-H", "4", "--seed", "5"];
+
+#[test]
+fn animate_ends_with_generated_maze() {
+    let output_dir = TempDir::new().unwrap();
+    let file_path = format!("{}/maze.txt", output_dir.path().display());
+
+    let mut cmd = Command::cargo_bin(assert_cmd::crate_name!()).unwrap();
+    cmd.arg("generate")
+        .args(MAZE_ARGS)
+        .args(["ascii", "--output-path", &file_path])
+        .assert()
+        .success();
+    let maze = std::fs::read_to_string(&file_path).unwrap();
+
+    let mut cmd = Command::cargo_bin(assert_cmd::crate_name!()).unwrap();
+    let output = cmd
+        .arg("animate")
+        .args(MAZE_ARGS)
+        .args(["--delay", "0", "--step", "3"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with("\x1b[2J\x1b[?25l\x1b[H"));
+    assert!(output.ends_with(&format!("\x1b[H{}\x1b[?25h", maze)));
+    assert!(output.contains("\x1b[41m"));
+    assert!(output.matches("\x1b[H").count() > 2);
+}
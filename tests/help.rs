@@ -1,3 +1,5 @@
+#![cfg(feature = "cli")]
+
 use assert_cmd::Command;
 
 const CLI_HELP_STR: &str = "Rust library for generating and rendering mazes
@@ -6,6 +8,7 @@ Usage: knossos <COMMAND>
 
 Commands:
   generate  Generates a maze
+  animate   Replays generating a maze in the terminal
   help      Print this message or the help of the given subcommand(s)
 
 Options:
@@ -25,7 +28,7 @@ Commands:
 
 Options:
   -A, --algorithm <ALGORITHM>
-          Maze generation algorithm [default: recursive-backtracking] [possible values: aldous-broder, binary-tree, eller, growing-tree, hunt-and-kill, kruskal, prim, recursive-backtracking, recursive-division, sidewinder]
+          Maze generation algorithm [default: recursive-backtracking] [possible values: aldous-broder, binary-tree, chamber-division, eller, growing-tree, houston, hunt-and-kill, kruskal, origin-shift, prim, recursive-backtracking, recursive-division, sidewinder, wilson]
   -H, --height <HEIGHT>
           Grid height in a number of cells [default: 10]
   -W, --width <WIDTH>
@@ -36,6 +39,10 @@ Options:
           Bias to use for the \"Binary Tree\" algorithm [default: north-east] [possible values: north-west, north-east, south-west, south-east]
       --growing-method[=<GROWING_METHOD>]
           Growing method to use for the \"Growing Tree\" algorithm [default: newest] [possible values: newest, oldest, random, middle, newest50-random50, newest75-random25, newest25-random75]
+      --room-size <ROOM_SIZE>
+          Largest width and height of a room to leave open for the \"Chamber Division\" algorithm [default: 1]
+      --stop-probability <STOP_PROBABILITY>
+          Probability to leave open a chamber that fits into a room for the \"Chamber Division\" algorithm [default: 1]
   -h, --help
           Print help (see more with '--help')
 ";
@@ -55,7 +62,7 @@ Options:
           Maze generation algorithm
           
           [default: recursive-backtracking]
-          [possible values: aldous-broder, binary-tree, eller, growing-tree, hunt-and-kill, kruskal, prim, recursive-backtracking, recursive-division, sidewinder]
+          [possible values: aldous-broder, binary-tree, chamber-division, eller, growing-tree, houston, hunt-and-kill, kruskal, origin-shift, prim, recursive-backtracking, recursive-division, sidewinder, wilson]
 
   -H, --height <HEIGHT>
           Grid height in a number of cells
@@ -95,6 +102,16 @@ Options:
           - newest75-random25: A combination of the [Newest](Method::Newest) and [Random](Method::Random) methods with 75/25 split
           - newest25-random75: A combination of the [Newest](Method::Newest) and [Random](Method::Random) methods with 25/75 split
 
+      --room-size <ROOM_SIZE>
+          Largest width and height of a room to leave open for the \"Chamber Division\" algorithm
+          
+          [default: 1]
+
+      --stop-probability <STOP_PROBABILITY>
+          Probability to leave open a chamber that fits into a room for the \"Chamber Division\" algorithm
+          
+          [default: 1]
+
   -h, --help
           Print help (see a summary with '-h')
 ";
@@ -136,6 +153,35 @@ Options:
   -h, --help                       Print help
 ";
 
+const ANIMATE_HELP_STR: &str = "Replays generating a maze in the terminal
+
+Usage: knossos animate [OPTIONS]
+
+Options:
+  -A, --algorithm <ALGORITHM>
+          Maze generation algorithm [default: recursive-backtracking] [possible values: aldous-broder, binary-tree, chamber-division, eller, growing-tree, houston, hunt-and-kill, kruskal, origin-shift, prim, recursive-backtracking, recursive-division, sidewinder, wilson]
+  -H, --height <HEIGHT>
+          Grid height in a number of cells [default: 10]
+  -W, --width <WIDTH>
+          Grid width in a number of cells [default: 10]
+      --seed <SEED>
+          Seed value for deterministic generation (must be a valid u64)
+      --bias[=<BIAS>]
+          Bias to use for the \"Binary Tree\" algorithm [default: north-east] [possible values: north-west, north-east, south-west, south-east]
+      --growing-method[=<GROWING_METHOD>]
+          Growing method to use for the \"Growing Tree\" algorithm [default: newest] [possible values: newest, oldest, random, middle, newest50-random50, newest75-random25, newest25-random75]
+      --room-size <ROOM_SIZE>
+          Largest width and height of a room to leave open for the \"Chamber Division\" algorithm [default: 1]
+      --stop-probability <STOP_PROBABILITY>
+          Probability to leave open a chamber that fits into a room for the \"Chamber Division\" algorithm [default: 1]
+      --delay <DELAY>
+          Delay between the frames in milliseconds [default: 30]
+      --step <STEP>
+          Number of generation steps to replay between the frames [default: 1]
+  -h, --help
+          Print help (see more with '--help')
+";
+
 #[test]
 fn cli_help() {
     let mut cmd = Command::cargo_bin(assert_cmd::crate_name!()).unwrap();
@@ -194,3 +240,13 @@ fn command_generate_game_map_help() {
         .success()
         .stdout(GENERATE_GAME_MAP_HELP_STR);
 }
+
+#[test]
+fn command_animate_help() {
+    let mut cmd = Command::cargo_bin(assert_cmd::crate_name!()).unwrap();
+    cmd.arg("animate")
+        .arg("-h")
+        .assert()
+        .success()
+        .stdout(ANIMATE_HELP_STR);
+}
@@ -43,6 +43,19 @@ fn build_identical_mazes_with_same_seed() {
     assert_eq!(old.to_string(), new.to_string());
 }
 
+#[test]
+fn build_identical_mazes_with_same_seed_and_eller_algorithm() {
+    let build = || {
+        OrthogonalMazeBuilder::new()
+            .width(30)
+            .height(30)
+            .algorithm(Box::new(Eller))
+            .seed(Some(40))
+            .build()
+    };
+    assert_eq!(build().to_string(), build().to_string());
+}
+
 #[test]
 fn build_valid_maze_with_aldou_broder_algorithm() {
     assert!(maze!(AldousBroder).is_valid());
@@ -56,9 +69,60 @@ fn build_valid_maze_with_binary_tree_algorithm() {
     assert!(maze!(BinaryTree::new(Bias::SouthEast)).is_valid());
 }
 
+#[test]
+fn build_valid_maze_with_chamber_division_algorithm() {
+    assert!(maze!(ChamberDivision::new()).is_perfect());
+    assert!(maze!(ChamberDivision::new().room_size(4, 4).stop_probability(0.5)).is_valid());
+}
+
+#[test]
+fn build_chamber_division_mazes_with_rooms() {
+    let build = |algorithm: ChamberDivision| {
+        OrthogonalMazeBuilder::new()
+            .width(16)
+            .height(12)
+            .algorithm(Box::new(algorithm))
+            .seed(Some(9))
+            .build()
+    };
+    // The top left cell of a 2x2 block of the cells linked with each other in a room
+    let is_room_corner = |maze: &OrthogonalMaze, (x, y): (usize, usize)| {
+        maze.is_carved((x, y), Cell::EAST)
+            && maze.is_carved((x, y), Cell::SOUTH)
+            && maze.is_carved((x + 1, y + 1), Cell::WEST)
+            && maze.is_carved((x + 1, y + 1), Cell::NORTH)
+    };
+    let has_rooms = |maze: &OrthogonalMaze| {
+        (0..maze.height() - 1).any(|y| (0..maze.width() - 1).any(|x| is_room_corner(maze, (x, y))))
+    };
+
+    let maze = build(ChamberDivision::new().room_size(4, 4));
+    assert!(maze.is_valid());
+    assert!(!maze.is_perfect());
+    assert!(has_rooms(&maze));
+
+    // A whole grid that fits into a room is left open
+    let maze = build(ChamberDivision::new().room_size(16, 12));
+    assert_eq!(2 * 16 * 12 - 16 - 12, maze.passages().count());
+
+    let maze = build(ChamberDivision::new().room_size(4, 4).stop_probability(0.0));
+    assert!(maze.is_perfect());
+    assert!(!has_rooms(&maze));
+}
+
 #[test]
 fn build_valid_maze_with_eller_algorithm() {
     assert!(maze!(Eller).is_valid());
+
+    for seed in 0..30 {
+        let maze = OrthogonalMazeBuilder::new()
+            .width(9)
+            .height(7)
+            .algorithm(Box::new(Eller))
+            .seed(Some(seed))
+            .build();
+        assert!(maze.is_perfect());
+    }
 }
 
 #[test]
@@ -72,6 +136,13 @@ fn build_valid_maze_with_growing_tree_algorithm() {
     assert!(maze!(GrowingTree::new(Method::Newest75Random25)).is_valid());
 }
 
+#[test]
+fn build_valid_maze_with_houston_algorithm() {
+    assert!(maze!(Houston::new()).is_valid());
+    assert!(maze!(Houston::new().threshold(0.0)).is_valid());
+    assert!(maze!(Houston::new().threshold(1.0)).is_valid());
+}
+
 #[test]
 fn build_valid_maze_with_hunt_and_kill_algorithm() {
     assert!(maze!(HuntAndKill::new()).is_valid());
@@ -82,6 +153,12 @@ fn build_valid_maze_with_kruskal_algorithm() {
     assert!(maze!(Kruskal).is_valid());
 }
 
+#[test]
+fn build_valid_maze_with_origin_shift_algorithm() {
+    assert!(maze!(OriginShift::new()).is_valid());
+    assert!(maze!(OriginShift::new().shifts(0)).is_perfect());
+}
+
 #[test]
 fn build_valid_maze_with_prim_algorithm() {
     assert!(maze!(Prim::new()).is_valid());
@@ -102,6 +179,37 @@ fn build_valid_maze_with_sidewinder_algorithm() {
     assert!(maze!(Sidewinder).is_valid());
 }
 
+#[test]
+fn build_valid_maze_with_wilson_algorithm() {
+    assert!(maze!(Wilson).is_valid());
+}
+
+#[test]
+fn build_uniform_spanning_trees() {
+    let algorithms: [fn() -> Box<dyn Algorithm>; 3] = [
+        || Box::new(AldousBroder),
+        || Box::new(Houston::new()),
+        || Box::new(Wilson),
+    ];
+
+    // A 2x2 maze is one of the 4 spanning trees, each of which must be equally likely
+    for algorithm in algorithms {
+        let mut counts = std::collections::HashMap::new();
+        for seed in 0..2000 {
+            let maze = OrthogonalMazeBuilder::new()
+                .width(2)
+                .height(2)
+                .algorithm(algorithm())
+                .seed(Some(seed))
+                .build();
+            *counts.entry(maze.to_string()).or_insert(0) += 1;
+        }
+
+        assert_eq!(4, counts.len());
+        assert!(counts.values().all(|count| (400..600).contains(count)));
+    }
+}
+
 macro_rules! to_absolute_path {
     ($path:expr) => {
         std::env::current_dir().unwrap().join($path).display()
@@ -204,6 +312,39 @@ fn save_maze_as_png_returns_error() {
     assert_save_maze_error!("this is not valid path/", Image::new(), expected);
 }
 
+#[test]
+fn save_maze_as_gif() {
+    let output_dir = TempDir::new().unwrap();
+    let file_path = format!("{}/maze.gif", output_dir.path().display());
+    let maze = OrthogonalMazeBuilder::new()
+        .width(6)
+        .height(4)
+        .algorithm(Box::new(Prim::new()))
+        .record_events(true)
+        .build();
+
+    let expected = format!(
+        "Maze was successfully saved as an animation: {}",
+        &file_path
+    );
+    assert_eq!(
+        expected,
+        maze.save(&file_path, Animation::new().step(5)).unwrap()
+    );
+
+    use image::{codecs::gif::GifDecoder, AnimationDecoder};
+    let file = std::io::BufReader::new(std::fs::File::open(&file_path).unwrap());
+    let frames = GifDecoder::new(file).unwrap().into_frames().count();
+    assert_eq!(maze.events().len() / 5 + 1, frames);
+}
+
+#[test]
+fn save_maze_as_gif_returns_error() {
+    let maze = maze!();
+    let result = maze.save("this is not valid path/", Animation::new());
+    assert!(result.unwrap_err().reason.starts_with("Couldn't create"));
+}
+
 #[test]
 fn format_maze() {
     let ascii = OrthogonalMazeBuilder::new()
@@ -229,3 +370,967 @@ fn format_maze() {
 
     assert_eq!(expected, ascii);
 }
+
+#[test]
+fn query_generated_maze_cells_and_passages() {
+    let maze = OrthogonalMazeBuilder::new()
+        .width(8)
+        .height(6)
+        .seed(Some(40))
+        .build();
+
+    assert_eq!(8, maze.width());
+    assert_eq!(6, maze.height());
+    assert_eq!(48, maze.cells().count());
+    assert_eq!(47, maze.passages().count());
+
+    for (from, to) in maze.passages() {
+        assert!(maze.neighbours(from).any(|next| next == to));
+        assert!(maze.neighbours(to).any(|next| next == from));
+    }
+
+    for coords in maze.cells() {
+        let passages = maze.cell(coords).unwrap();
+        let walls = maze.walls(coords).unwrap();
+        assert_eq!(Cell::all(), passages | walls);
+        assert_eq!(passages.iter().count(), maze.neighbours(coords).count());
+    }
+}
+
+struct Spiral;
+
+impl Algorithm for Spiral {
+    fn generate(&mut self, grid: &mut Grid, _rng: &mut rand::rngs::StdRng) {
+        let mut coords = (0, 0);
+        let mut dirs = [Cell::EAST, Cell::SOUTH, Cell::WEST, Cell::NORTH]
+            .into_iter()
+            .cycle();
+        let mut dir = dirs.next().unwrap();
+        let mut turns = 0;
+
+        while turns < 4 {
+            match grid.get_next_cell_coords(coords, dir) {
+                Ok(next) if !grid.is_cell_visited(next) => {
+                    coords = grid.carve_passage(coords, dir).unwrap();
+                    turns = 0;
+                }
+                Ok(_) | Err(TransitError { .. }) => {
+                    dir = dirs.next().unwrap();
+                    turns += 1;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn build_valid_maze_with_custom_algorithm() {
+    let maze = OrthogonalMazeBuilder::new()
+        .width(7)
+        .height(5)
+        .algorithm(Box::new(Spiral))
+        .build();
+
+    assert!(maze.is_valid());
+    assert_eq!(34, maze.passages().count());
+}
+
+#[test]
+fn solve_maze_with_every_solver() {
+    let maze = OrthogonalMazeBuilder::new()
+        .width(15)
+        .height(12)
+        .seed(Some(7))
+        .build();
+
+    let expected = maze.solve(BreadthFirstSearch, (0, 0), (14, 11)).unwrap();
+    for step in expected.windows(2) {
+        assert!(maze.neighbours(step[0]).any(|next| next == step[1]));
+    }
+
+    assert_eq!(
+        Some(&expected),
+        maze.solve(AStar, (0, 0), (14, 11)).as_ref()
+    );
+    assert_eq!(
+        Some(&expected),
+        maze.solve(DeadEndFilling, (0, 0), (14, 11)).as_ref()
+    );
+    assert_eq!(
+        Some(&expected),
+        maze.solve(Tremaux, (0, 0), (14, 11)).as_ref()
+    );
+    assert_eq!(
+        Some(&expected),
+        maze.solve(WallFollower::new(Hand::Left), (0, 0), (14, 11))
+            .as_ref()
+    );
+    assert_eq!(
+        Some(&expected),
+        maze.solve(WallFollower::new(Hand::Right), (0, 0), (14, 11))
+            .as_ref()
+    );
+}
+
+#[test]
+fn solve_maze_out_of_bounds() {
+    let maze = maze!();
+    assert_eq!(None, maze.solve(BreadthFirstSearch, (0, 0), (10, 10)));
+}
+
+#[test]
+fn format_solved_maze_as_image() {
+    let maze = OrthogonalMazeBuilder::new().seed(Some(40)).build();
+    let path = maze.solve(AStar, (0, 0), (9, 9)).unwrap();
+
+    let plain = maze.format(Image::new()).into_inner();
+    let solved = maze
+        .format(Image::new().path(path).start((0, 0)).goal((9, 9)))
+        .into_inner();
+
+    assert_eq!(plain.dimensions(), solved.dimensions());
+    assert_ne!(plain.as_raw(), solved.as_raw());
+}
+
+#[test]
+fn format_solved_maze_as_narrow_ascii() {
+    let maze = OrthogonalMazeBuilder::new()
+        .width(5)
+        .height(4)
+        .seed(Some(3))
+        .build();
+    let path = maze.solve(BreadthFirstSearch, (0, 0), (4, 3)).unwrap();
+
+    let text = maze
+        .format(
+            AsciiNarrow
+                .with_path(path.clone())
+                .start((0, 0))
+                .goal((4, 3)),
+        )
+        .into_inner();
+    let rows: Vec<Vec<char>> = text.lines().skip(1).map(|l| l.chars().collect()).collect();
+    let cell = |(x, y): (usize, usize)| rows[y][2 * x + 1];
+
+    assert_eq!('S', cell((0, 0)));
+    assert_eq!('G', cell((4, 3)));
+    assert!(path[1..path.len() - 1].iter().all(|c| cell(*c) == '*'));
+    let marked = (0..4)
+        .flat_map(|y| (0..5).map(move |x| (x, y)))
+        .filter(|c| cell(*c) == '*')
+        .count();
+    assert_eq!(path.len() - 2, marked);
+}
+
+#[test]
+fn compare_statistics_of_generated_mazes() {
+    let algorithms: Vec<Box<dyn Algorithm>> = vec![
+        Box::new(AldousBroder),
+        Box::new(BinaryTree::new(Bias::NorthEast)),
+        Box::new(GrowingTree::new(Method::Newest)),
+        Box::new(GrowingTree::new(Method::Random)),
+        Box::new(Houston::new()),
+        Box::new(HuntAndKill::new()),
+        Box::new(Kruskal),
+        Box::new(Prim::new()),
+        Box::new(RecursiveBacktracking),
+        Box::new(RecursiveDivision),
+        Box::new(Sidewinder),
+        Box::new(Wilson),
+    ];
+
+    for algorithm in algorithms {
+        let maze = OrthogonalMazeBuilder::new()
+            .width(20)
+            .height(15)
+            .algorithm(algorithm)
+            .seed(Some(42))
+            .build();
+        let stats = maze.statistics();
+
+        assert_eq!(300, stats.dead_ends + stats.corridors + stats.junctions);
+        assert_eq!(299, stats.horizontal_passages + stats.vertical_passages);
+
+        let (start, end) = stats.longest_path_endpoints;
+        assert_eq!(Some(stats.longest_path), maze.solution_length(start, end));
+        assert!(maze.solution_length((0, 0), (19, 14)).unwrap() <= stats.longest_path);
+    }
+
+    let backtracker = OrthogonalMazeBuilder::new()
+        .algorithm(Box::new(RecursiveBacktracking))
+        .seed(Some(42))
+        .build()
+        .statistics();
+    let prim = OrthogonalMazeBuilder::new()
+        .algorithm(Box::new(Prim::new()))
+        .seed(Some(42))
+        .build()
+        .statistics();
+
+    assert!(backtracker.dead_ends < prim.dead_ends);
+    assert!(backtracker.river_factor > prim.river_factor);
+}
+
+#[test]
+fn format_distances_as_heat_map() {
+    let maze = OrthogonalMazeBuilder::new()
+        .algorithm(Box::new(Sidewinder))
+        .seed(Some(40))
+        .build();
+    let distances = maze.distances((0, 0));
+
+    assert!(distances.iter().all(|distance| *distance < 100));
+    assert_eq!(
+        maze.solution_length((0, 0), (9, 9)),
+        Some(distances[9 * 10 + 9])
+    );
+
+    let plain = maze.format(Image::new()).into_inner();
+    let heat_map = maze.format(Image::new().heat_map((0, 0))).into_inner();
+
+    assert_eq!(plain.dimensions(), heat_map.dimensions());
+    assert_ne!(plain.as_raw(), heat_map.as_raw());
+}
+
+#[test]
+fn parse_formatted_maze_back() {
+    let maze = OrthogonalMazeBuilder::new()
+        .width(9)
+        .height(6)
+        .seed(Some(40))
+        .build();
+    let expected = maze.to_string();
+
+    let text = maze.format(AsciiNarrow).into_inner();
+    let parsed = OrthogonalMaze::from_ascii_narrow(&text).unwrap();
+    assert_eq!(expected, parsed.to_string());
+
+    let text = maze.format(AsciiBroad).into_inner();
+    let parsed = OrthogonalMaze::from_ascii_broad(&text).unwrap();
+    assert_eq!(expected, parsed.to_string());
+
+    let path = maze.solve(BreadthFirstSearch, (0, 0), (8, 5)).unwrap();
+    let text = maze
+        .format(AsciiBroad.with_path(path).start((0, 0)).goal((8, 5)))
+        .into_inner();
+    let parsed = OrthogonalMaze::from_ascii_broad(&text).unwrap();
+    assert_eq!(expected, parsed.to_string());
+
+    for span in 1..=3 {
+        let text = maze.format(GameMap::new().span(span)).into_inner();
+        let parsed = OrthogonalMaze::from_game_map(&text, span, '#', '.').unwrap();
+        assert_eq!(expected, parsed.to_string());
+    }
+
+    let formatter = GameMap::new().with_start_goal().seed(Some(40));
+    let text = maze.format(formatter).into_inner();
+    let parsed = OrthogonalMaze::from_game_map(&text, 2, '#', '.').unwrap();
+    assert_eq!(expected, parsed.to_string());
+
+    let maze = OrthogonalMazeBuilder::new()
+        .width(8)
+        .height(6)
+        .algorithm(Box::new(ChamberDivision::new().room_size(3, 3)))
+        .seed(Some(1))
+        .build();
+    let expected = maze.to_string();
+
+    let text = maze.format(AsciiBroad).into_inner();
+    assert!(text
+        .lines()
+        .step_by(2)
+        .any(|line| line.chars().step_by(4).any(|ch| ch == ' ')));
+    let parsed = OrthogonalMaze::from_ascii_broad(&text).unwrap();
+    assert_eq!(expected, parsed.to_string());
+
+    let text = maze.format(AsciiNarrow).into_inner();
+    let parsed = OrthogonalMaze::from_ascii_narrow(&text).unwrap();
+    assert_eq!(expected, parsed.to_string());
+}
+
+#[test]
+fn parse_malformed_maze() {
+    let error = OrthogonalMaze::from_ascii_broad("+---+\n|   |\n+---+\n|   |\n")
+        .err()
+        .unwrap();
+
+    assert_eq!(5, error.line);
+    assert_eq!(1, error.column);
+}
+
+#[test]
+fn save_maze_as_svg() {
+    let output_dir = TempDir::new().unwrap();
+    let file_path = format!("{}/maze.svg", output_dir.path().display());
+    let expected = format!(
+        "Maze was successfully written to a file: {}",
+        to_absolute_path!(&file_path)
+    );
+    assert_save_maze!(&file_path, Svg::new(), expected);
+
+    let svg = std::fs::read_to_string(&file_path).unwrap();
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.ends_with("</svg>\n"));
+}
+
+#[test]
+fn build_braided_mazes() {
+    let algorithms: Vec<Box<dyn Algorithm>> = vec![
+        Box::new(AldousBroder),
+        Box::new(BinaryTree::new(Bias::SouthWest)),
+        Box::new(Eller),
+        Box::new(GrowingTree::new(Method::Oldest)),
+        Box::new(Kruskal),
+        Box::new(Prim::new()),
+        Box::new(RecursiveBacktracking),
+        Box::new(Sidewinder),
+    ];
+
+    for algorithm in algorithms {
+        let braided = OrthogonalMazeBuilder::new()
+            .algorithm(algorithm)
+            .seed(Some(40))
+            .braid(0.5)
+            .build();
+
+        assert!(braided.is_valid());
+        assert!(!braided.is_perfect());
+    }
+
+    let maze = || {
+        OrthogonalMazeBuilder::new()
+            .width(20)
+            .height(20)
+            .seed(Some(40))
+    };
+    assert!(maze().build().is_perfect());
+
+    let perfect = maze().build().statistics();
+    let half = maze().braid(0.5).build().statistics();
+    let full = maze().braid(1.0).build().statistics();
+
+    assert!(half.dead_ends < perfect.dead_ends);
+    assert!(half.dead_ends > 0);
+    assert_eq!(0, full.dead_ends);
+    assert_eq!(
+        maze().braid(0.5).build().to_string(),
+        maze().braid(0.5).build().to_string()
+    );
+}
+
+#[test]
+fn build_mazes_with_rooms() {
+    let algorithms: Vec<Box<dyn Algorithm>> = vec![
+        Box::new(AldousBroder),
+        Box::new(BinaryTree::new(Bias::NorthWest)),
+        Box::new(ChamberDivision::new()),
+        Box::new(Eller),
+        Box::new(GrowingTree::new(Method::Newest)),
+        Box::new(Houston::new()),
+        Box::new(HuntAndKill::new()),
+        Box::new(Kruskal),
+        Box::new(OriginShift::new()),
+        Box::new(Prim::new()),
+        Box::new(RecursiveBacktracking),
+        Box::new(RecursiveDivision),
+        Box::new(Sidewinder),
+        Box::new(Wilson),
+    ];
+    let layout = Rooms::new(4).width(3..=5).height(2..=4).doors(1..=3);
+
+    for algorithm in algorithms {
+        let maze = OrthogonalMazeBuilder::new()
+            .width(24)
+            .height(16)
+            .rooms(layout.clone())
+            .algorithm(algorithm)
+            .seed(Some(6))
+            .build();
+
+        assert!(maze.is_valid());
+        assert_eq!(4, maze.rooms().len());
+        for room in maze.rooms() {
+            assert!((3..=5).contains(&room.width) && (2..=4).contains(&room.height));
+            assert!((1..=3).contains(&room.doors.len()));
+
+            let (x, y) = room.position;
+            for coords in maze.cells().filter(|coords| room.contains(*coords)) {
+                let exits = maze
+                    .neighbours(coords)
+                    .filter(|next| !room.contains(*next))
+                    .count();
+                let doors = room
+                    .doors
+                    .iter()
+                    .filter(|(door, _)| *door == coords)
+                    .count();
+                assert_eq!(doors, exits);
+            }
+            assert_eq!(
+                2 * room.width * room.height - room.width - room.height,
+                maze.passages()
+                    .filter(|(from, to)| room.contains(*from) && room.contains(*to))
+                    .count()
+            );
+            assert!(x > 0 && y > 0 && x + room.width < 24 && y + room.height < 16);
+        }
+    }
+
+    let maze = || {
+        OrthogonalMazeBuilder::new()
+            .width(24)
+            .height(16)
+            .rooms(layout.clone())
+            .seed(Some(6))
+    };
+    assert_eq!(maze().build().rooms(), maze().build().rooms());
+    assert_eq!(maze().build().to_string(), maze().build().to_string());
+    assert_eq!(0, maze().braid(1.0).build().statistics().dead_ends);
+    assert!(OrthogonalMazeBuilder::new().build().rooms().is_empty());
+}
+
+#[test]
+fn record_generation_events() {
+    let algorithms: [fn() -> Box<dyn Algorithm>; 15] = [
+        || Box::new(AldousBroder),
+        || Box::new(BinaryTree::new(Bias::SouthEast)),
+        || Box::new(ChamberDivision::new().room_size(3, 3)),
+        || Box::new(Eller),
+        || Box::new(GrowingTree::new(Method::Newest50Random50)),
+        || Box::new(Houston::new()),
+        || Box::new(HuntAndKill::new()),
+        || Box::new(Kruskal),
+        || Box::new(OriginShift::new()),
+        || Box::new(Prim::new()),
+        || Box::new(RecursiveBacktracking),
+        || Box::new(RecursiveDivision),
+        || Box::new(Sidewinder),
+        || Box::new(Wilson),
+        || Box::new(GrowingTree::new(Method::Random)),
+    ];
+
+    for (i, algorithm) in algorithms.into_iter().enumerate() {
+        let builder = || {
+            OrthogonalMazeBuilder::new()
+                .width(12)
+                .height(9)
+                .weave(i == 7 || i == 10)
+                .rooms(Rooms::new(1).width(2..=3).height(2..=3))
+                .braid(if i == 14 { 0.5 } else { 0.0 })
+                .algorithm(algorithm())
+                .seed(Some(21))
+        };
+        let maze = builder().build();
+        let recorded = builder().record_events(true).build();
+
+        // Recording the events changes nothing in a maze
+        assert!(maze.events().is_empty());
+        assert_eq!(maze.to_string(), recorded.to_string(), "{i}");
+        assert!(recorded
+            .events()
+            .iter()
+            .any(|event| matches!(event, Event::Visit(_))));
+
+        // Replaying the passages, tunnels and walls rebuilds the maze
+        let mut grid = Grid::new(12, 9);
+        for event in recorded.events() {
+            match *event {
+                Event::Carve { from, dir } => _ = grid.carve_passage(from, dir).unwrap(),
+                Event::Tunnel { from, dir } => _ = grid.carve_tunnel(from, dir).unwrap(),
+                Event::Wall { from, dir } => _ = grid.build_wall(from, dir).unwrap(),
+                _ => {}
+            }
+        }
+        assert_eq!(recorded.to_string(), grid.to_string());
+
+        // The cells an algorithm keeps track of are all done with in the end
+        let mut tracked = std::collections::HashSet::new();
+        for event in recorded.events() {
+            match *event {
+                Event::Mark(coords) => assert!(tracked.insert(coords)),
+                Event::Backtrack(coords) => assert!(tracked.remove(&coords)),
+                _ => {}
+            }
+        }
+        assert!(tracked.is_empty(), "{i}");
+    }
+}
+
+#[test]
+fn build_masked_mazes_with_rooms() {
+    let mask = Mask::from_ascii(
+        ".X..X..\n\
+         ...X...\n\
+         X.X..X.\n\
+         ....X..\n\
+         .X.....\n\
+         ..X.X.X\n\
+         X...X..\n\
+         ..X....\n",
+    )
+    .unwrap();
+    let layout = Rooms::new(2).width(1..=3).height(1..=3);
+
+    for seed in 0..100 {
+        for topology in [Topology::Plane, Topology::Cylinder, Topology::Torus] {
+            let maze = OrthogonalMazeBuilder::new()
+                .width(7)
+                .height(8)
+                .mask(mask.clone())
+                .topology(topology)
+                .weave(seed % 2 == 0)
+                .rooms(layout.clone())
+                .algorithm(Box::new(AldousBroder))
+                .seed(Some(seed))
+                .build();
+
+            assert!(maze.is_valid(), "{seed} {topology:?}");
+            for room in maze.rooms() {
+                assert!(!room.doors.is_empty(), "{seed} {topology:?}");
+                for &(coords, dir) in &room.doors {
+                    assert!(maze.is_carved(coords, dir));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn build_masked_mazes() {
+    let mask = Mask::from_ascii(
+        "XX....XX\n\
+         X......X\n\
+         ........\n\
+         ...XX...\n\
+         ...XX...\n\
+         ........\n\
+         X......X\n\
+         XX....XX\n",
+    )
+    .unwrap();
+
+    let perfect: Vec<Box<dyn Algorithm>> = vec![
+        Box::new(AldousBroder),
+        Box::new(BinaryTree::new(Bias::NorthWest)),
+        Box::new(Eller),
+        Box::new(GrowingTree::new(Method::Newest50Random50)),
+        Box::new(Houston::new()),
+        Box::new(HuntAndKill::new()),
+        Box::new(Kruskal),
+        Box::new(OriginShift::new()),
+        Box::new(Prim::new()),
+        Box::new(RecursiveBacktracking),
+        Box::new(RecursiveDivision),
+        Box::new(Sidewinder),
+        Box::new(Wilson),
+    ];
+    let others: Vec<Box<dyn Algorithm>> = vec![Box::new(ChamberDivision::new())];
+
+    for (algorithm, is_perfect) in perfect
+        .into_iter()
+        .map(|algorithm| (algorithm, true))
+        .chain(others.into_iter().map(|algorithm| (algorithm, false)))
+    {
+        let maze = OrthogonalMazeBuilder::new()
+            .width(3)
+            .height(3)
+            .mask(mask.clone())
+            .algorithm(algorithm)
+            .seed(Some(12))
+            .build();
+
+        assert_eq!((8, 8), (maze.width(), maze.height()));
+        for coords in maze.cells().filter(|coords| !mask.is_enabled(*coords)) {
+            assert!(!maze.is_enabled(coords));
+            assert_eq!(Some(Cell::empty()), maze.cell(coords));
+        }
+        for (from, to) in maze.passages() {
+            assert!(maze.is_enabled(from) && maze.is_enabled(to));
+        }
+        assert!(maze.is_valid());
+        if is_perfect {
+            assert!(maze.is_perfect());
+            assert_eq!(mask.count() - 1, maze.passages().count());
+        }
+    }
+}
+
+#[test]
+fn build_weave_mazes() {
+    let algorithms: Vec<Box<dyn Algorithm>> = vec![
+        Box::new(GrowingTree::new(Method::Newest)),
+        Box::new(Kruskal),
+        Box::new(RecursiveBacktracking),
+    ];
+
+    for algorithm in algorithms {
+        let maze = OrthogonalMazeBuilder::new()
+            .width(12)
+            .height(12)
+            .weave(true)
+            .algorithm(algorithm)
+            .seed(Some(3))
+            .build();
+
+        assert!(maze.is_perfect());
+        assert!(maze
+            .cells()
+            .any(|coords| !maze.tunnel(coords).unwrap().is_empty()));
+    }
+}
+
+#[test]
+fn build_wrapped_mazes() {
+    let wrapping: Vec<Box<dyn Algorithm>> = vec![
+        Box::new(AldousBroder),
+        Box::new(GrowingTree::new(Method::Random)),
+        Box::new(Houston::new()),
+        Box::new(HuntAndKill::new()),
+        Box::new(Kruskal),
+        Box::new(OriginShift::new()),
+        Box::new(Prim::new()),
+        Box::new(RecursiveBacktracking),
+        Box::new(Wilson),
+    ];
+    let others: Vec<Box<dyn Algorithm>> = vec![
+        Box::new(BinaryTree::new(Bias::NorthEast)),
+        Box::new(ChamberDivision::new()),
+        Box::new(RecursiveDivision),
+        Box::new(Sidewinder),
+    ];
+
+    let wraps_horizontally =
+        |maze: &OrthogonalMaze| (0..8).any(|y| maze.is_carved((11, y), Cell::EAST));
+    let wraps_vertically =
+        |maze: &OrthogonalMaze| (0..12).any(|x| maze.is_carved((x, 7), Cell::SOUTH));
+
+    for (algorithm, is_wrapping) in wrapping
+        .into_iter()
+        .map(|algorithm| (algorithm, true))
+        .chain(others.into_iter().map(|algorithm| (algorithm, false)))
+    {
+        let maze = OrthogonalMazeBuilder::new()
+            .width(12)
+            .height(8)
+            .topology(Topology::Torus)
+            .algorithm(algorithm)
+            .seed(Some(5))
+            .build();
+
+        assert_eq!(Topology::Torus, maze.topology());
+        assert!(maze.is_perfect());
+        assert_eq!(is_wrapping, wraps_vertically(&maze));
+    }
+
+    let maze = OrthogonalMazeBuilder::new()
+        .width(12)
+        .height(8)
+        .topology(Topology::Cylinder)
+        .algorithm(Box::new(Sidewinder))
+        .seed(Some(5))
+        .build();
+    assert!(maze.is_perfect());
+    assert!(wraps_horizontally(&maze));
+    assert!(!wraps_vertically(&maze));
+
+    let maze = OrthogonalMazeBuilder::new()
+        .width(12)
+        .height(8)
+        .topology(Topology::Torus)
+        .weave(true)
+        .seed(Some(5))
+        .build();
+    assert!(maze.is_perfect());
+    assert!(wraps_horizontally(&maze) && wraps_vertically(&maze));
+}
+
+#[test]
+fn mutate_maze_with_origin_shift() {
+    let maze = || {
+        OrthogonalMazeBuilder::new()
+            .width(8)
+            .height(6)
+            .algorithm(Box::new(Kruskal))
+            .seed(Some(21))
+            .build()
+    };
+
+    let (mut first, mut second) = (maze(), maze());
+    let mut first_shifter = OriginShifter::new(&first, Some(4)).unwrap();
+    let mut second_shifter = OriginShifter::new(&second, Some(4)).unwrap();
+
+    for _ in 0..20 {
+        first_shifter.shift(&mut first, 3).unwrap();
+        second_shifter.shift(&mut second, 3).unwrap();
+
+        assert!(first.is_perfect());
+        assert_eq!(first.to_string(), second.to_string());
+        assert_eq!(first_shifter.origin(), second_shifter.origin());
+    }
+    assert_ne!(maze().to_string(), first.to_string());
+
+    let weave = OrthogonalMazeBuilder::new()
+        .width(12)
+        .height(12)
+        .weave(true)
+        .algorithm(Box::new(Kruskal))
+        .seed(Some(3))
+        .build();
+    assert!(OriginShifter::new(&weave, None).is_err());
+
+    let braided = OrthogonalMazeBuilder::new()
+        .braid(1.0)
+        .seed(Some(3))
+        .build();
+    assert!(OriginShifter::new(&braided, None).is_err());
+}
+
+#[test]
+fn build_polar_mazes() {
+    let algorithms: Vec<Box<dyn GraphAlgorithm>> = vec![
+        Box::new(AldousBroder),
+        Box::new(GrowingTree::new(Method::Newest50Random50)),
+        Box::new(Houston::new()),
+        Box::new(HuntAndKill::new()),
+        Box::new(Kruskal),
+        Box::new(Prim::new()),
+        Box::new(RecursiveBacktracking),
+        Box::new(Wilson),
+    ];
+
+    for algorithm in algorithms {
+        let maze = PolarMazeBuilder::new()
+            .rings(6)
+            .algorithm(algorithm)
+            .seed(Some(5))
+            .build();
+
+        assert!(maze.is_perfect());
+        assert_eq!(90, maze.passages().count());
+        for (from, to) in maze.passages() {
+            assert!(maze.neighbours(to).contains(&from));
+        }
+    }
+}
+
+#[test]
+fn format_polar_maze() {
+    let maze = PolarMazeBuilder::new().rings(3).build();
+
+    let image = maze
+        .format(Image::new().wall(2).passage(4).margin(1))
+        .into_inner();
+    assert_eq!((40, 40), image.dimensions());
+
+    let svg = maze.format(Svg::new()).into_inner();
+    assert!(svg.contains("<circle"));
+}
+
+#[test]
+fn build_hex_mazes() {
+    let algorithms: Vec<Box<dyn GraphAlgorithm>> = vec![
+        Box::new(AldousBroder),
+        Box::new(GrowingTree::new(Method::Oldest)),
+        Box::new(Houston::new()),
+        Box::new(HuntAndKill::new()),
+        Box::new(Kruskal),
+        Box::new(Prim::new()),
+        Box::new(RecursiveBacktracking),
+        Box::new(Wilson),
+    ];
+
+    for algorithm in algorithms {
+        let maze = HexMazeBuilder::new()
+            .width(7)
+            .height(5)
+            .algorithm(algorithm)
+            .seed(Some(5))
+            .build();
+
+        assert!(maze.is_perfect());
+        assert_eq!(34, maze.passages().count());
+        for coords in maze.cells() {
+            assert_eq!(
+                maze.cell(coords).unwrap().bits().count_ones() as usize,
+                maze.neighbours(coords).len()
+            );
+        }
+    }
+}
+
+#[test]
+fn format_hex_maze() {
+    let maze = HexMazeBuilder::new().width(3).height(2).build();
+
+    let text = maze.format(AsciiNarrow).into_inner();
+    assert_eq!(maze.to_string(), text);
+    assert_eq!(6, text.lines().count());
+
+    let image = maze
+        .format(Image::new().wall(2).passage(18).margin(0))
+        .into_inner();
+    assert_eq!((60, 52), image.dimensions());
+}
+
+#[test]
+fn build_delta_and_upsilon_mazes() {
+    let algorithms = || -> Vec<Box<dyn GraphAlgorithm>> {
+        vec![
+            Box::new(AldousBroder),
+            Box::new(GrowingTree::new(Method::Newest)),
+            Box::new(Houston::new()),
+            Box::new(HuntAndKill::new()),
+            Box::new(Kruskal),
+            Box::new(Prim::new()),
+            Box::new(RecursiveBacktracking),
+            Box::new(Wilson),
+        ]
+    };
+
+    for algorithm in algorithms() {
+        let maze = DeltaMazeBuilder::new()
+            .width(8)
+            .height(5)
+            .algorithm(algorithm)
+            .seed(Some(7))
+            .build();
+
+        assert!(maze.is_perfect());
+        assert_eq!(39, maze.passages().count());
+        for coords in maze.cells() {
+            assert_eq!(
+                maze.cell(coords).unwrap().bits().count_ones() as usize,
+                maze.neighbours(coords).len()
+            );
+        }
+    }
+
+    for algorithm in algorithms() {
+        let maze = UpsilonMazeBuilder::new()
+            .width(5)
+            .height(6)
+            .algorithm(algorithm)
+            .seed(Some(7))
+            .build();
+
+        assert!(maze.is_perfect());
+        assert_eq!(29, maze.passages().count());
+        for coords in maze.cells() {
+            assert_eq!(
+                maze.cell(coords).unwrap().bits().count_ones() as usize,
+                maze.neighbours(coords).len()
+            );
+        }
+    }
+}
+
+#[test]
+fn format_delta_and_upsilon_mazes() {
+    let formatter = || Image::new().wall(2).passage(18).margin(0);
+
+    let maze = DeltaMazeBuilder::new().width(2).height(1).build();
+    let image = maze.format(formatter()).into_inner();
+    assert_eq!((54, 32), image.dimensions());
+
+    let maze = UpsilonMazeBuilder::new().width(2).height(1).build();
+    let image = maze.format(formatter()).into_inner();
+    assert_eq!((85, 51), image.dimensions());
+}
+
+#[test]
+fn build_layered_mazes() {
+    let algorithms: Vec<Box<dyn GraphAlgorithm>> = vec![
+        Box::new(GrowingTree::new(Method::Random)),
+        Box::new(Houston::new()),
+        Box::new(Kruskal),
+        Box::new(RecursiveBacktracking),
+        Box::new(Wilson),
+    ];
+
+    for algorithm in algorithms {
+        let maze = LayeredMazeBuilder::new()
+            .width(5)
+            .height(4)
+            .levels(3)
+            .algorithm(algorithm)
+            .seed(Some(9))
+            .build();
+
+        assert!(maze.is_perfect());
+        assert_eq!(59, maze.passages().count());
+        for level in 1..maze.levels() {
+            assert!(maze
+                .cells()
+                .any(|(x, y, z)| z == level && maze.is_carved((x, y, z), LayeredCell::DOWN)));
+        }
+    }
+}
+
+#[test]
+fn format_layered_maze() {
+    let maze = LayeredMazeBuilder::new()
+        .width(4)
+        .height(3)
+        .levels(2)
+        .build();
+
+    let text = maze.format(AsciiNarrow).into_inner();
+    assert_eq!(maze.to_string(), text);
+    assert_eq!(4, text.lines().count());
+    assert!(text.contains('U') || text.contains('X'));
+
+    let floors: Vec<String> = GameMap::new()
+        .span(1)
+        .format_levels(maze.grid())
+        .into_iter()
+        .map(|floor| floor.into_inner())
+        .collect();
+    assert_eq!(2, floors.len());
+    assert!(floors[1].contains('D') || floors[1].contains('X'));
+
+    let image = maze
+        .format(Image::new().wall(2).passage(16).margin(0))
+        .into_inner();
+    assert_eq!((164, 56), image.dimensions());
+}
+
+#[test]
+fn format_wrapped_maze() {
+    let maze = OrthogonalMazeBuilder::new()
+        .width(3)
+        .height(3)
+        .topology(Topology::Torus)
+        .algorithm(Box::new(RecursiveBacktracking))
+        .seed(Some(2))
+        .build();
+
+    assert_eq!("     _ \n|_|___|\n|_    |\n  | |_ \n", maze.to_string());
+    assert_eq!(
+        "+   +   +---+\n|   |       |\n+---+---+---+\n|           |\n+---+   +   +\n    |   |    \n+   +   +---+\n",
+        maze.format(AsciiBroad).into_inner()
+    );
+    assert_eq!(
+        "#.#.###\n#.#...#\n#######\n#.....#\n###.#.#\n..#.#..\n#.#.###\n",
+        maze.format(GameMap::new().span(1)).into_inner()
+    );
+}
+
+#[test]
+fn format_masked_maze() {
+    let mask = Mask::from_ascii("X..\n...\n").unwrap();
+    let maze = OrthogonalMazeBuilder::new()
+        .mask(mask)
+        .algorithm(Box::new(BinaryTree::new(Bias::SouthEast)))
+        .seed(Some(1))
+        .build();
+
+    assert_eq!("   ___ \n _|_  |\n|_____|\n", maze.to_string());
+    assert_eq!(
+        "    +---+---+\n    |       |\n+---+---+   +\n|           |\n+---+---+---+\n",
+        maze.format(AsciiBroad).into_inner()
+    );
+    assert_eq!(
+        "#######\n###...#\n#####.#\n#.....#\n#######\n",
+        maze.format(GameMap::new().span(1)).into_inner()
+    );
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "serde")]
+
+use knossos::maze::*;
+
+#[test]
+fn reload_maze_from_json() {
+    let maze = OrthogonalMazeBuilder::new()
+        .width(12)
+        .height(7)
+        .seed(Some(40))
+        .build();
+
+    let json = serde_json::to_string(&maze).unwrap();
+    let reloaded: OrthogonalMaze = serde_json::from_str(&json).unwrap();
+
+    assert!(reloaded.is_valid());
+    assert_eq!(maze.to_string(), reloaded.to_string());
+    assert_eq!(
+        maze.format(AsciiBroad).into_inner(),
+        reloaded.format(AsciiBroad).into_inner()
+    );
+}
+
+#[test]
+fn reload_weave_mazes_from_json() {
+    for seed in 0..50 {
+        let algorithms: Vec<Box<dyn Algorithm>> = vec![
+            Box::new(RecursiveBacktracking),
+            Box::new(GrowingTree::new(Method::Newest)),
+        ];
+
+        for algorithm in algorithms {
+            let maze = OrthogonalMazeBuilder::new()
+                .width(12)
+                .height(9)
+                .weave(true)
+                .algorithm(algorithm)
+                .seed(Some(seed))
+                .build();
+
+            let json = serde_json::to_string(&maze).unwrap();
+            let reloaded: OrthogonalMaze = serde_json::from_str(&json).unwrap();
+
+            assert!(reloaded.is_valid());
+            for coords in maze.cells() {
+                assert_eq!(maze.cell(coords), reloaded.cell(coords));
+                assert_eq!(maze.tunnel(coords), reloaded.tunnel(coords));
+            }
+        }
+    }
+}
+
+#[test]
+fn reload_masked_maze_from_json() {
+    let mask = Mask::from_ascii(
+        "XX....XX\n\
+         X......X\n\
+         ........\n\
+         ...XX...\n\
+         ........\n\
+         XX....XX\n",
+    )
+    .unwrap();
+    let maze = OrthogonalMazeBuilder::new()
+        .mask(mask.clone())
+        .seed(Some(8))
+        .build();
+
+    let json = serde_json::to_string(&maze).unwrap();
+    let reloaded: OrthogonalMaze = serde_json::from_str(&json).unwrap();
+
+    assert!(reloaded.is_valid());
+    assert_eq!(maze.to_string(), reloaded.to_string());
+    for coords in maze.cells() {
+        assert_eq!(mask.is_enabled(coords), reloaded.is_enabled(coords));
+    }
+}
+
+#[test]
+fn reload_maze_with_rooms_from_json() {
+    let maze = OrthogonalMazeBuilder::new()
+        .width(20)
+        .height(14)
+        .rooms(Rooms::new(3).doors(1..=3))
+        .seed(Some(4))
+        .build();
+
+    let json = serde_json::to_string(&maze).unwrap();
+    let reloaded: OrthogonalMaze = serde_json::from_str(&json).unwrap();
+
+    assert!(reloaded.is_valid());
+    assert!(!maze.rooms().is_empty());
+    assert_eq!(maze.rooms(), reloaded.rooms());
+    assert_eq!(maze.to_string(), reloaded.to_string());
+}
+
+#[test]
+fn reject_invalid_maze_json() {
+    let json = r#"{"width":2,"height":1,"cells":[4,0]}"#;
+    assert!(serde_json::from_str::<OrthogonalMaze>(json).is_err());
+}